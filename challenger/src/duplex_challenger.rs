@@ -0,0 +1,309 @@
+use alloc::vec::Vec;
+
+use p3_field::{Field, PrimeField64};
+use p3_symmetric::CryptographicPermutation;
+
+use crate::{CanObserve, CanSample, CanSampleBits, CanSampleBounded, FieldChallenger};
+
+/// A challenger built on a duplex sponge over a permutation of `WIDTH` elements, of which `RATE`
+/// are absorbed or squeezed per permutation.
+///
+/// Observed elements are buffered until `RATE` of them arrive or a challenge is sampled; they then
+/// overwrite the start of the state, which is permuted. Challenges are read from the first `RATE`
+/// elements of the state, in order, and the state is permuted again once they are used up or as
+/// soon as something new is observed.
+#[derive(Clone, Debug)]
+pub struct DuplexChallenger<F, P, const WIDTH: usize, const RATE: usize> {
+    sponge_state: [F; WIDTH],
+    input_buffer: Vec<F>,
+    /// The unread challenges, in reverse order.
+    output_buffer: Vec<F>,
+    permutation: P,
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> DuplexChallenger<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    pub fn new(permutation: P) -> Self {
+        assert!(RATE < WIDTH, "the capacity must not be empty");
+        Self {
+            sponge_state: [F::zero(); WIDTH],
+            input_buffer: Vec::with_capacity(RATE),
+            output_buffer: Vec::with_capacity(RATE),
+            permutation,
+        }
+    }
+
+    fn duplexing(&mut self) {
+        debug_assert!(self.input_buffer.len() <= RATE);
+        for (x, input) in self
+            .sponge_state
+            .iter_mut()
+            .zip(self.input_buffer.drain(..))
+        {
+            *x = input;
+        }
+        self.permutation.permute_mut(&mut self.sponge_state);
+
+        self.output_buffer.clear();
+        self.output_buffer
+            .extend(self.sponge_state[..RATE].iter().rev().copied());
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> CanObserve<F>
+    for DuplexChallenger<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    fn observe(&mut self, value: F) {
+        // Challenges squeezed before this observation must not be handed out after it.
+        self.output_buffer.clear();
+        self.input_buffer.push(value);
+        if self.input_buffer.len() == RATE {
+            self.duplexing();
+        }
+    }
+}
+
+/// Observes a digest, such as a Merkle root.
+impl<F, P, const N: usize, const WIDTH: usize, const RATE: usize> CanObserve<[F; N]>
+    for DuplexChallenger<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    fn observe(&mut self, values: [F; N]) {
+        for value in values {
+            self.observe(value);
+        }
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> CanSample<F>
+    for DuplexChallenger<F, P, WIDTH, RATE>
+where
+    F: Field,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    fn sample(&mut self) -> F {
+        if !self.input_buffer.is_empty() || self.output_buffer.is_empty() {
+            self.duplexing();
+        }
+        self.output_buffer
+            .pop()
+            .expect("the output buffer is refilled by duplexing")
+    }
+}
+
+/// Samples the low `bits` bits of a field element.
+///
+/// These are not exactly uniform, as the order of the field is not a power of two. `bits` must be
+/// such that `2^bits` divides `p - 1`: then every value is the low bits of `(p - 1) / 2^bits`
+/// field elements, except `0`, which is the low bits of one more, so the only bias is that `0` is
+/// more likely than any other value, by `1 / p`. For Baby Bear, `p = 15 * 2^27 + 1`, so this
+/// allows up to 27 bits. Beyond that, some values would be twice as likely as others; use
+/// `CanSampleBounded` instead.
+impl<F, P, const WIDTH: usize, const RATE: usize> CanSampleBits<usize>
+    for DuplexChallenger<F, P, WIDTH, RATE>
+where
+    F: PrimeField64,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    fn sample_bits(&mut self, bits: usize) -> usize {
+        assert!(
+            bits < 64 && (F::ORDER_U64 - 1).is_multiple_of(1 << bits),
+            "2^{bits} does not divide the order of the multiplicative group"
+        );
+        let x: F = self.sample();
+        x.as_canonical_u64() as usize & ((1 << bits) - 1)
+    }
+}
+
+/// Samples an integer below `bound` by rejection: field elements in the last, incomplete run of
+/// `bound` consecutive values are rejected and sampled again, so every integer below `bound` is
+/// equally likely. Prover and verifier reject the same challenges, so they stay in sync.
+impl<F, P, const WIDTH: usize, const RATE: usize> CanSampleBounded<usize>
+    for DuplexChallenger<F, P, WIDTH, RATE>
+where
+    F: PrimeField64,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    fn sample_bounded(&mut self, bound: usize) -> usize {
+        assert!(
+            bound > 0 && bound as u64 <= F::ORDER_U64,
+            "the bound must be positive and at most the order of the field"
+        );
+        let bound = bound as u64;
+        let limit = F::ORDER_U64 - F::ORDER_U64 % bound;
+        loop {
+            let x: F = self.sample();
+            let x = x.as_canonical_u64();
+            if x < limit {
+                return (x % bound) as usize;
+            }
+        }
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> FieldChallenger<F>
+    for DuplexChallenger<F, P, WIDTH, RATE>
+where
+    F: PrimeField64,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_baby_bear::BabyBear;
+    use p3_field::extension::BinomialExtensionField;
+    use p3_field::{AbstractExtensionField, AbstractField, PrimeField32};
+    use p3_poseidon2::{poseidon2_baby_bear_16, Poseidon2BabyBear};
+
+    use super::*;
+
+    type F = BabyBear;
+    type EF = BinomialExtensionField<F, 4>;
+    type Challenger = DuplexChallenger<F, Poseidon2BabyBear<16>, 16, 8>;
+
+    fn challenger() -> Challenger {
+        DuplexChallenger::new(poseidon2_baby_bear_16())
+    }
+
+    fn canonical(xs: &[F]) -> Vec<u32> {
+        xs.iter().map(|x| x.as_canonical_u32()).collect()
+    }
+
+    #[test]
+    fn matches_explicit_duplexing() {
+        let perm = poseidon2_baby_bear_16();
+        let mut challenger = challenger();
+        let inputs: [F; 11] = core::array::from_fn(|i| F::from_canonical_usize(i + 1));
+        challenger.observe_slice(&inputs);
+        let samples: [F; 10] = challenger.sample_array();
+
+        // The first 8 inputs fill the rate; the other 3 overwrite the start of the state.
+        let mut state = [F::zero(); 16];
+        state[..8].copy_from_slice(&inputs[..8]);
+        perm.permute_mut(&mut state);
+        state[..3].copy_from_slice(&inputs[8..]);
+        perm.permute_mut(&mut state);
+        assert_eq!(samples[..8], state[..8]);
+        // Sampling past the rate squeezes again, with no input.
+        perm.permute_mut(&mut state);
+        assert_eq!(samples[8..], state[..2]);
+    }
+
+    #[test]
+    fn observing_invalidates_samples() {
+        let perm = poseidon2_baby_bear_16();
+        let mut challenger = challenger();
+        challenger.observe(F::one());
+        let _: F = challenger.sample();
+        challenger.observe(F::two());
+        let x: F = challenger.sample();
+
+        // The second challenge is not left over from the first squeeze, but squeezed afresh from a
+        // state into which the second observation was absorbed.
+        let mut state = [F::zero(); 16];
+        state[0] = F::one();
+        perm.permute_mut(&mut state);
+        state[0] = F::two();
+        perm.permute_mut(&mut state);
+        assert_eq!(x, state[0]);
+    }
+
+    #[test]
+    fn extension_elements() {
+        let mut a = challenger();
+        let mut b = challenger();
+        let x = EF::from_base_slice(&[1, 2, 3, 4].map(F::from_canonical_u32));
+        a.observe_ext_element(x);
+        b.observe_slice(AbstractExtensionField::<F>::as_base_slice(&x));
+        let challenge: EF = a.sample_ext_element();
+        let coeffs: [F; 4] = b.sample_array();
+        assert_eq!(
+            AbstractExtensionField::<F>::as_base_slice(&challenge),
+            coeffs
+        );
+    }
+
+    #[test]
+    fn sample_bits_in_range() {
+        let mut challenger = challenger();
+        for bits in 0..=27 {
+            for _ in 0..10 {
+                assert!(challenger.sample_bits(bits) < 1 << bits);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn sample_bits_rejects_biased_widths() {
+        challenger().sample_bits(28);
+    }
+
+    #[test]
+    fn sample_bounded() {
+        let mut challenger = challenger();
+        let mut seen = [false; 3];
+        for _ in 0..30 {
+            seen[challenger.sample_bounded(3)] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+
+        // A bound just above half the field rejects almost half of all challenges, and the
+        // accepted ones are returned as they are.
+        let bound = (F::ORDER_U64 / 2 + 1) as usize;
+        for _ in 0..10 {
+            let mut expected = challenger.clone();
+            let x = loop {
+                let x: F = expected.sample();
+                if (x.as_canonical_u32() as usize) < bound {
+                    break x.as_canonical_u32() as usize;
+                }
+            };
+            assert_eq!(challenger.sample_bounded(bound), x);
+        }
+    }
+
+    /// A fixed transcript of every kind of operation, pinned so that a change to the challenger
+    /// that would desynchronize an old prover from a new verifier is caught.
+    #[test]
+    fn test_vectors() {
+        let mut challenger = challenger();
+        let counting = |n: usize| (0..n).map(F::from_canonical_usize).collect::<Vec<_>>();
+
+        challenger.observe_slice(&counting(5));
+        let base: [F; 3] = challenger.sample_array();
+        assert_eq!(canonical(&base), [221400204, 836235761, 1897224488]);
+
+        challenger.observe_ext_element(EF::from_base_slice(&counting(4)));
+        challenger.observe([F::from_canonical_u32(7); 8]);
+        let ext: EF = challenger.sample_ext_element();
+        assert_eq!(
+            canonical(AbstractExtensionField::<F>::as_base_slice(&ext)),
+            [1318568530, 1842595734, 674709156, 457703549]
+        );
+
+        let indices: Vec<usize> = (0..4).map(|_| challenger.sample_bits(20)).collect();
+        assert_eq!(indices, [473351, 668621, 138067, 41423]);
+
+        challenger.observe_slice(&counting(20));
+        let last: [F; 10] = challenger.sample_array();
+        assert_eq!(
+            canonical(&last),
+            [
+                1732056160, 73210227, 1581355604, 526121845, 890711885, 1444215235, 1857307650,
+                390275341, 254503591, 1626984000
+            ]
+        );
+    }
+}
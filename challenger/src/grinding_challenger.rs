@@ -0,0 +1,78 @@
+use p3_field::PrimeField64;
+use p3_maybe_rayon::prelude::*;
+use p3_symmetric::CryptographicPermutation;
+
+use crate::{CanObserve, CanSampleBits, DuplexChallenger};
+
+/// A challenger supporting proof-of-work grinding, in which the prover searches for a witness
+/// that makes the transcript sample `bits` zero bits. This costs the prover about `2^bits`
+/// attempts and the verifier one, and adds `bits` of security to the challenges sampled after it.
+pub trait GrindingChallenger:
+    CanObserve<Self::Witness> + CanSampleBits<usize> + Sync + Clone
+{
+    type Witness;
+
+    /// Finds a witness for the current transcript, and observes it.
+    fn grind(&mut self, bits: usize) -> Self::Witness;
+
+    /// Observes `witness` and checks that the next `bits` bits sampled are zero.
+    fn check_witness(&mut self, bits: usize, witness: Self::Witness) -> bool {
+        self.observe(witness);
+        self.sample_bits(bits) == 0
+    }
+}
+
+impl<F, P, const WIDTH: usize, const RATE: usize> GrindingChallenger
+    for DuplexChallenger<F, P, WIDTH, RATE>
+where
+    F: PrimeField64,
+    P: CryptographicPermutation<[F; WIDTH]>,
+{
+    type Witness = F;
+
+    fn grind(&mut self, bits: usize) -> F {
+        let witness = (0..F::ORDER_U64)
+            .into_par_iter()
+            .map(F::from_canonical_u64)
+            .find_any(|&witness| self.clone().check_witness(bits, witness))
+            .expect("no witness exists for this many bits");
+        assert!(self.check_witness(bits, witness));
+        witness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::AbstractField;
+    use p3_poseidon2::{poseidon2_baby_bear_16, Poseidon2BabyBear};
+
+    use super::*;
+    use crate::CanSample;
+
+    type F = BabyBear;
+    type Challenger = DuplexChallenger<F, Poseidon2BabyBear<16>, 16, 8>;
+
+    #[test]
+    fn grind_and_check() {
+        let bits = 8;
+        let mut prover = Challenger::new(poseidon2_baby_bear_16());
+        prover.observe(F::from_canonical_u32(42));
+        let mut verifier = prover.clone();
+
+        let witness = prover.grind(bits);
+        assert!(verifier.clone().check_witness(bits, witness));
+
+        // Some witness is rejected, since only about one in 2^bits is accepted.
+        let rejected = (0..)
+            .map(F::from_canonical_u32)
+            .find(|&w| !verifier.clone().check_witness(bits, w))
+            .unwrap();
+        assert_ne!(rejected, witness);
+
+        // Both transcripts include the witness afterwards.
+        assert!(verifier.check_witness(bits, witness));
+        let (x, y): (F, F) = (prover.sample(), verifier.sample());
+        assert_eq!(x, y);
+    }
+}
@@ -0,0 +1,71 @@
+//! Fiat–Shamir challengers, which turn an interactive protocol into a non-interactive one by
+//! deriving the verifier's challenges from a transcript of the prover's messages.
+//!
+//! The prover and the verifier must observe and sample the same values in the same order, so
+//! that both derive the same challenges.
+
+#![no_std]
+
+extern crate alloc;
+
+mod duplex_challenger;
+mod grinding_challenger;
+
+use alloc::vec::Vec;
+
+pub use duplex_challenger::*;
+pub use grinding_challenger::*;
+use p3_field::{AbstractExtensionField, Field};
+
+/// A challenger that can absorb values of type `T` into its transcript.
+pub trait CanObserve<T> {
+    fn observe(&mut self, value: T);
+
+    fn observe_slice(&mut self, values: &[T])
+    where
+        T: Clone,
+    {
+        for value in values {
+            self.observe(value.clone());
+        }
+    }
+}
+
+/// A challenger that can squeeze challenges of type `T` from its transcript.
+pub trait CanSample<T> {
+    fn sample(&mut self) -> T;
+
+    fn sample_array<const N: usize>(&mut self) -> [T; N] {
+        core::array::from_fn(|_| self.sample())
+    }
+
+    fn sample_vec(&mut self, n: usize) -> Vec<T> {
+        (0..n).map(|_| self.sample()).collect()
+    }
+}
+
+/// A challenger that can sample integers of a given number of bits, such as query indices.
+pub trait CanSampleBits<T> {
+    fn sample_bits(&mut self, bits: usize) -> T;
+}
+
+/// A challenger that can sample integers below any bound, not only a power of two.
+///
+/// This generally costs more than `CanSampleBits`, as challenges may have to be rejected.
+pub trait CanSampleBounded<T> {
+    fn sample_bounded(&mut self, bound: T) -> T;
+}
+
+/// A challenger over the field `F`, which can also absorb and squeeze extension field elements
+/// through their coefficients.
+pub trait FieldChallenger<F: Field>:
+    CanObserve<F> + CanSample<F> + CanSampleBits<usize> + Sync
+{
+    fn observe_ext_element<EF: AbstractExtensionField<F>>(&mut self, value: EF) {
+        self.observe_slice(value.as_base_slice());
+    }
+
+    fn sample_ext_element<EF: AbstractExtensionField<F>>(&mut self) -> EF {
+        EF::from_base_slice(&self.sample_vec(EF::D))
+    }
+}
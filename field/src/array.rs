@@ -1,10 +1,17 @@
 use core::array;
 use core::iter::{Product, Sum};
-use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use crate::{AbstractField, Field};
+use crate::{AbstractField, Field, PackedField};
 
+/// `N` lanes of `F`, operated on lane by lane.
+///
+/// This is a `PackedField` for any power-of-two `N`, so it serves as a portable packing that
+/// relies on autovectorization. `PackedField` requires the scalar to support arithmetic with
+/// the packed type on the left, which the orphan rule only lets the crate defining `F` provide,
+/// e.g. `impl<const N: usize> Add<FieldArray<BabyBear, N>> for BabyBear`.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(transparent)]
 pub struct FieldArray<F: Field, const N: usize>(pub [F; N]);
 
 impl<F: Field, const N: usize> Default for FieldArray<F, N> {
@@ -188,6 +195,16 @@ impl<F: Field, const N: usize> MulAssign<F> for FieldArray<F, N> {
     }
 }
 
+impl<F: Field, const N: usize> Div<F> for FieldArray<F, N> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline]
+    fn div(self, rhs: F) -> Self::Output {
+        self * rhs.inverse()
+    }
+}
+
 impl<F: Field, const N: usize> Sum for FieldArray<F, N> {
     #[inline]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
@@ -201,3 +218,88 @@ impl<F: Field, const N: usize> Product for FieldArray<F, N> {
         iter.reduce(|lhs, rhs| lhs * rhs).unwrap_or(Self::one())
     }
 }
+
+// Safety: `FieldArray<F, N>` is `repr(transparent)` over `[F; N]`, so it can be cast to and from
+// `[F; WIDTH]`, and `WIDTH = N` is asserted to be a power of two.
+unsafe impl<F: Field, const N: usize> PackedField for FieldArray<F, N>
+where
+    F: Add<Self, Output = Self> + Mul<Self, Output = Self> + Sub<Self, Output = Self>,
+{
+    type Scalar = F;
+
+    const WIDTH: usize = {
+        assert!(
+            N.is_power_of_two(),
+            "FieldArray width must be a power of two"
+        );
+        N
+    };
+
+    #[inline]
+    fn from_slice(slice: &[F]) -> &Self {
+        assert_eq!(slice.len(), N);
+        unsafe {
+            // Safety: `slice` holds exactly `N` elements, and `[F; N]` can be transmuted to
+            // `FieldArray<F, N>` since the latter is `repr(transparent)`. They have the same
+            // alignment, so the reference cast is safe too.
+            &*slice.as_ptr().cast()
+        }
+    }
+
+    #[inline]
+    fn from_slice_mut(slice: &mut [F]) -> &mut Self {
+        assert_eq!(slice.len(), N);
+        unsafe {
+            // Safety: `slice` holds exactly `N` elements, and `[F; N]` can be transmuted to
+            // `FieldArray<F, N>` since the latter is `repr(transparent)`. They have the same
+            // alignment, so the reference cast is safe too.
+            &mut *slice.as_mut_ptr().cast()
+        }
+    }
+
+    #[inline]
+    fn from_fn<Fn>(f: Fn) -> Self
+    where
+        Fn: FnMut(usize) -> F,
+    {
+        Self(array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[F] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [F] {
+        &mut self.0
+    }
+
+    #[inline]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        assert!(
+            block_len.is_power_of_two() && block_len <= N,
+            "unsupported block length"
+        );
+        if block_len == N {
+            return (*self, other);
+        }
+        // Pair up block `2k` and `2k + 1` of each input: the first output holds the even blocks
+        // of `self` and `other` side by side, and the second output holds the odd blocks.
+        let even = array::from_fn(|i| {
+            if i & block_len == 0 {
+                self.0[i]
+            } else {
+                other.0[i - block_len]
+            }
+        });
+        let odd = array::from_fn(|i| {
+            if i & block_len == 0 {
+                self.0[i + block_len]
+            } else {
+                other.0[i]
+            }
+        });
+        (Self(even), Self(odd))
+    }
+}
@@ -1,7 +1,11 @@
 use alloc::vec;
 use alloc::vec::Vec;
 
+use p3_maybe_rayon::current_num_threads;
+use p3_maybe_rayon::prelude::*;
+
 use crate::field::Field;
+use crate::kernels;
 
 /// Batch multiplicative inverses with Montgomery's trick
 /// This is Montgomery's trick. At a high level, we invert the product of the given field
@@ -97,3 +101,81 @@ pub fn batch_multiplicative_inverse<F: Field>(x: &[F]) -> Vec<F> {
 
     buf
 }
+
+/// Batch multiplicative inverses with Montgomery's trick, vectorized over `F::Packing`. See
+/// `kernels::batch_multiplicative_inverse_packed`.
+///
+/// # Panics
+/// Panics if any element of `x` is zero.
+pub fn batch_multiplicative_inverse_packed<F: Field>(x: &[F]) -> Vec<F> {
+    kernels::batch_multiplicative_inverse_packed::<F::Packing>(x)
+}
+
+/// Like `batch_multiplicative_inverse_packed`, but writes the inverses to `out` instead of
+/// allocating a new vector.
+///
+/// # Panics
+/// Panics if any element of `x` is zero, or if `x` and `out` differ in length.
+pub fn batch_multiplicative_inverse_packed_into<F: Field>(x: &[F], out: &mut [F]) {
+    kernels::batch_multiplicative_inverse_packed_into::<F::Packing>(x, out);
+}
+
+/// Like `batch_multiplicative_inverse_packed`, but splits `x` into one chunk per thread via
+/// `p3_maybe_rayon`. Each chunk performs a single field inversion.
+///
+/// # Panics
+/// Panics if any element of `x` is zero.
+pub fn par_batch_multiplicative_inverse<F: Field>(x: &[F]) -> Vec<F> {
+    // Below this many elements per chunk, the extra inversions and task overhead outweigh the
+    // parallelism.
+    const MIN_CHUNK_LEN: usize = 1 << 10;
+
+    let chunk_len = x.len().div_ceil(current_num_threads()).max(MIN_CHUNK_LEN);
+    let mut result = vec![F::zero(); x.len()];
+    result
+        .par_chunks_mut(chunk_len)
+        .zip(x.par_chunks(chunk_len))
+        .for_each(|(out, x)| batch_multiplicative_inverse_packed_into(x, out));
+    result
+}
+
+/// Like `batch_multiplicative_inverse_packed`, but maps zero to zero instead of panicking.
+pub fn batch_multiplicative_inverse_allowing_zero<F: Field>(x: &[F]) -> Vec<F> {
+    invert_allowing_zero(x, batch_multiplicative_inverse_packed)
+}
+
+/// Like `par_batch_multiplicative_inverse`, but maps zero to zero instead of panicking.
+pub fn par_batch_multiplicative_inverse_allowing_zero<F: Field>(x: &[F]) -> Vec<F> {
+    invert_allowing_zero(x, par_batch_multiplicative_inverse)
+}
+
+/// Like `batch_multiplicative_inverse_packed`, but returns the indices of any zeros in `x` as an
+/// error instead of panicking.
+pub fn try_batch_multiplicative_inverse<F: Field>(x: &[F]) -> Result<Vec<F>, Vec<usize>> {
+    let zeros: Vec<usize> = x
+        .iter()
+        .enumerate()
+        .filter(|(_, xi)| xi.is_zero())
+        .map(|(i, _)| i)
+        .collect();
+    if zeros.is_empty() {
+        Ok(batch_multiplicative_inverse_packed(x))
+    } else {
+        Err(zeros)
+    }
+}
+
+/// Invert `x` with `invert` after swapping each zero for one, then put the zeros back.
+fn invert_allowing_zero<F: Field>(x: &[F], invert: fn(&[F]) -> Vec<F>) -> Vec<F> {
+    let nonzero: Vec<F> = x
+        .iter()
+        .map(|&xi| if xi.is_zero() { F::one() } else { xi })
+        .collect();
+    let mut result = invert(&nonzero);
+    for (r, xi) in result.iter_mut().zip(x) {
+        if xi.is_zero() {
+            *r = F::zero();
+        }
+    }
+    result
+}
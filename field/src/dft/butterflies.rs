@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+
+use p3_maybe_rayon::prelude::*;
+
+use crate::field::Field;
+use crate::kernels;
+
+type Butterfly<F> = fn(&mut [F], &mut [F], F);
+
+/// Run the decimation-in-time layers over a matrix whose rows are in bit-reversed order. The
+/// output rows are in natural order. `root` must have order `2^log_height`.
+pub(crate) fn dit_layers<F: Field>(values: &mut [F], width: usize, log_height: usize, root: F) {
+    let twiddles = twiddles(log_height, root);
+    for layer in 0..log_height {
+        apply_layer(
+            values,
+            width,
+            layer,
+            log_height,
+            &twiddles,
+            kernels::dit_butterfly::<F::Packing>,
+        );
+    }
+}
+
+/// Run the decimation-in-frequency layers over a matrix whose rows are in natural order. The
+/// output rows are in bit-reversed order. `root` must have order `2^log_height`.
+pub(crate) fn dif_layers<F: Field>(values: &mut [F], width: usize, log_height: usize, root: F) {
+    let twiddles = twiddles(log_height, root);
+    for layer in (0..log_height).rev() {
+        apply_layer(
+            values,
+            width,
+            layer,
+            log_height,
+            &twiddles,
+            kernels::dif_butterfly::<F::Packing>,
+        );
+    }
+}
+
+/// `[root^0, ..., root^(n/2 - 1)]`, where `n = 2^log_height`.
+fn twiddles<F: Field>(log_height: usize, root: F) -> Vec<F> {
+    let half_height = (1 << log_height) >> 1;
+    root.powers().take(half_height).collect()
+}
+
+/// Apply `butterfly` to every pair of rows `2^layer` apart within blocks of `2^(layer + 1)` rows.
+fn apply_layer<F: Field>(
+    values: &mut [F],
+    width: usize,
+    layer: usize,
+    log_height: usize,
+    twiddles: &[F],
+    butterfly: Butterfly<F>,
+) {
+    let half_block = 1 << layer;
+    let block_len = 2 * half_block * width;
+    let stride = 1 << (log_height - layer - 1);
+    let num_blocks = values.len() / block_len;
+
+    // Parallelize over whichever dimension has more work: blocks in the early layers, butterflies
+    // within a block in the late ones.
+    if num_blocks >= half_block {
+        values.par_chunks_exact_mut(block_len).for_each(|block| {
+            let (lo, hi) = block.split_at_mut(half_block * width);
+            lo.chunks_exact_mut(width)
+                .zip(hi.chunks_exact_mut(width))
+                .enumerate()
+                .for_each(|(j, (lo_row, hi_row))| butterfly(lo_row, hi_row, twiddles[j * stride]));
+        });
+    } else {
+        values.chunks_exact_mut(block_len).for_each(|block| {
+            let (lo, hi) = block.split_at_mut(half_block * width);
+            lo.par_chunks_exact_mut(width)
+                .zip(hi.par_chunks_exact_mut(width))
+                .enumerate()
+                .for_each(|(j, (lo_row, hi_row))| butterfly(lo_row, hi_row, twiddles[j * stride]));
+        });
+    }
+}
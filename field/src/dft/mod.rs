@@ -0,0 +1,131 @@
+//! Radix-2 number-theoretic transforms over the two-adic subgroups of a `TwoAdicField`.
+//!
+//! Batched methods operate on a row-major matrix stored as a flat slice with `width` columns.
+//! Each column holds one vector of length `height = values.len() / width` and is transformed
+//! independently, so `height` must be a power of two.
+
+use alloc::vec::Vec;
+
+use p3_util::{log2_strict_usize, reverse_matrix_index_bits};
+
+use crate::field::{Field, TwoAdicField};
+use crate::helpers::scale_slice_in_place;
+
+mod butterflies;
+mod radix_2_dif;
+mod radix_2_dit;
+
+pub use radix_2_dif::*;
+pub use radix_2_dit::*;
+
+/// A DFT over the two-adic subgroup `H = <g>` of order `height`, where
+/// `g = F::two_adic_generator(log2(height))`. The forward transform maps the coefficients of a
+/// polynomial to its evaluations over `H`: `out[k] = \sum_i values[i] * g^{ik}`.
+pub trait TwoAdicSubgroupDft<F: TwoAdicField>: Clone + Default {
+    /// Compute the DFT of each column, with both input and output in natural order.
+    fn dft_batch(&self, values: &mut [F], width: usize);
+
+    /// Compute the DFT of each column, with the input in natural order and the output in
+    /// bit-reversed order.
+    fn dft_batch_bit_reversed(&self, values: &mut [F], width: usize) {
+        self.dft_batch(values, width);
+        reverse_matrix_index_bits(values, width);
+    }
+
+    /// Compute the inverse DFT of each column, with both input and output in natural order.
+    fn idft_batch(&self, values: &mut [F], width: usize) {
+        // idft(x)[i] = dft(x)[-i] / n, so run a forward transform and undo the index negation.
+        self.dft_batch(values, width);
+        let height = values.len() / width;
+        for i in 1..height / 2 {
+            let (lo, hi) = values.split_at_mut((height - i) * width);
+            lo[i * width..(i + 1) * width].swap_with_slice(&mut hi[..width]);
+        }
+        scale_slice_in_place(F::from_canonical_usize(height).inverse(), values);
+    }
+
+    /// Compute the inverse DFT of each column, with the input in bit-reversed order and the
+    /// output in natural order.
+    fn idft_batch_bit_reversed(&self, values: &mut [F], width: usize) {
+        reverse_matrix_index_bits(values, width);
+        self.idft_batch(values, width);
+    }
+
+    /// Evaluate each column over the coset `shift * H`.
+    fn coset_dft_batch(&self, values: &mut [F], width: usize, shift: F) {
+        scale_rows_by_powers(values, width, shift);
+        self.dft_batch(values, width);
+    }
+
+    /// Interpolate each column from its evaluations over the coset `shift * H`.
+    fn coset_idft_batch(&self, values: &mut [F], width: usize, shift: F) {
+        self.idft_batch(values, width);
+        scale_rows_by_powers(values, width, shift.inverse());
+    }
+
+    /// Compute a low-degree extension of each column: interpolate over `H`, then evaluate over
+    /// the coset `shift * K`, where `K` is the subgroup of order `height << added_bits`.
+    fn coset_lde_batch(
+        &self,
+        mut values: Vec<F>,
+        width: usize,
+        added_bits: usize,
+        shift: F,
+    ) -> Vec<F> {
+        self.idft_batch(&mut values, width);
+        values.resize(values.len() << added_bits, F::zero());
+        self.coset_dft_batch(&mut values, width, shift);
+        values
+    }
+
+    fn dft(&self, values: &mut [F]) {
+        self.dft_batch(values, 1);
+    }
+
+    fn dft_bit_reversed(&self, values: &mut [F]) {
+        self.dft_batch_bit_reversed(values, 1);
+    }
+
+    fn idft(&self, values: &mut [F]) {
+        self.idft_batch(values, 1);
+    }
+
+    fn idft_bit_reversed(&self, values: &mut [F]) {
+        self.idft_batch_bit_reversed(values, 1);
+    }
+
+    fn coset_dft(&self, values: &mut [F], shift: F) {
+        self.coset_dft_batch(values, 1, shift);
+    }
+
+    fn coset_idft(&self, values: &mut [F], shift: F) {
+        self.coset_idft_batch(values, 1, shift);
+    }
+
+    fn coset_lde(&self, values: Vec<F>, added_bits: usize, shift: F) -> Vec<F> {
+        self.coset_lde_batch(values, 1, added_bits, shift)
+    }
+}
+
+/// Returns `log2(height)` of a row-major matrix with the given `width`.
+///
+/// # Panics
+/// Panics if `values` is not made of whole rows or the height is not a power of two.
+fn log2_height<T>(values: &[T], width: usize) -> usize {
+    assert!(width > 0, "width must be positive");
+    assert_eq!(
+        values.len() % width,
+        0,
+        "length {} is not a multiple of width {width}",
+        values.len()
+    );
+    log2_strict_usize(values.len() / width)
+}
+
+/// Multiply row `i` by `shift^i`.
+fn scale_rows_by_powers<F: Field>(values: &mut [F], width: usize, shift: F) {
+    values
+        .chunks_exact_mut(width)
+        .zip(shift.powers())
+        .for_each(|(row, s)| scale_slice_in_place(s, row));
+}
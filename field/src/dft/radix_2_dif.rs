@@ -0,0 +1,32 @@
+use p3_util::reverse_matrix_index_bits;
+
+use super::butterflies::dif_layers;
+use super::{log2_height, TwoAdicSubgroupDft};
+use crate::field::TwoAdicField;
+use crate::helpers::scale_slice_in_place;
+
+/// The radix-2 decimation-in-frequency transform. Its butterflies consume natural-order input
+/// and produce bit-reversed output, so `dft_batch_bit_reversed` needs no permutation, while
+/// natural-order output pays for one at the end.
+#[derive(Default, Clone, Debug)]
+pub struct Radix2Dif;
+
+impl<F: TwoAdicField> TwoAdicSubgroupDft<F> for Radix2Dif {
+    fn dft_batch(&self, values: &mut [F], width: usize) {
+        self.dft_batch_bit_reversed(values, width);
+        reverse_matrix_index_bits(values, width);
+    }
+
+    fn dft_batch_bit_reversed(&self, values: &mut [F], width: usize) {
+        let log_height = log2_height(values, width);
+        dif_layers(values, width, log_height, F::two_adic_generator(log_height));
+    }
+
+    fn idft_batch(&self, values: &mut [F], width: usize) {
+        let log_height = log2_height(values, width);
+        let root_inv = F::two_adic_generator(log_height).inverse();
+        dif_layers(values, width, log_height, root_inv);
+        reverse_matrix_index_bits(values, width);
+        scale_slice_in_place(F::from_canonical_usize(1 << log_height).inverse(), values);
+    }
+}
@@ -0,0 +1,32 @@
+use p3_util::reverse_matrix_index_bits;
+
+use super::butterflies::dit_layers;
+use super::{log2_height, TwoAdicSubgroupDft};
+use crate::field::TwoAdicField;
+use crate::helpers::scale_slice_in_place;
+
+/// The radix-2 decimation-in-time transform. Its butterflies consume bit-reversed input and
+/// produce natural-order output, so natural-order forward transforms pay for one extra
+/// permutation up front, while `idft_batch_bit_reversed` needs none.
+#[derive(Default, Clone, Debug)]
+pub struct Radix2Dit;
+
+impl<F: TwoAdicField> TwoAdicSubgroupDft<F> for Radix2Dit {
+    fn dft_batch(&self, values: &mut [F], width: usize) {
+        let log_height = log2_height(values, width);
+        reverse_matrix_index_bits(values, width);
+        dit_layers(values, width, log_height, F::two_adic_generator(log_height));
+    }
+
+    fn idft_batch(&self, values: &mut [F], width: usize) {
+        reverse_matrix_index_bits(values, width);
+        self.idft_batch_bit_reversed(values, width);
+    }
+
+    fn idft_batch_bit_reversed(&self, values: &mut [F], width: usize) {
+        let log_height = log2_height(values, width);
+        let root_inv = F::two_adic_generator(log_height).inverse();
+        dit_layers(values, width, log_height, root_inv);
+        scale_slice_in_place(F::from_canonical_usize(1 << log_height).inverse(), values);
+    }
+}
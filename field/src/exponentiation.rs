@@ -63,6 +63,29 @@ pub fn exp_1725656503<AF: AbstractField>(val: AF) -> AF {
     p1100110110110110110110110110000 * p111
 }
 
+pub fn exp_2013265919<AF: AbstractField>(val: AF) -> AF {
+    // Note that 2013265919 = p - 2 for the BabyBear prime p = 2^31 - 2^27 + 1.
+    // Thus by Fermat's little theorem, a^{2013265919} = a^{-1} for all non-zero a \in F_p.
+    // Note the binary expansion: 2013265919 = 1110111111111111111111111111111_2
+    // This uses 30 Squares + 7 Multiplications => 37 Operations total.
+    let p1 = val;
+    let p100000000 = p1.exp_power_of_2(8);
+    let p100000001 = p100000000.clone() * p1.clone();
+    let p10000000000000000 = p100000000.exp_power_of_2(8);
+    let p10000000100000001 = p10000000000000000 * p100000001;
+    let p10000000100000001000 = p10000000100000001.exp_power_of_2(3);
+    let p1000000010000000100000000 = p10000000100000001000.clone().exp_power_of_2(5);
+    let p1000000010000000100000001 = p1000000010000000100000000 * p1;
+    let p1000010010000100100001001 = p1000000010000000100000001.clone() * p10000000100000001000;
+    let p10000000100000001000000010 = p1000000010000000100000001.square();
+    let p11000010110000101100001011 =
+        p10000000100000001000000010.clone() * p1000010010000100100001001;
+    let p100000001000000010000000100 = p10000000100000001000000010.square();
+    let p111000011110000111100001111 = p100000001000000010000000100 * p11000010110000101100001011;
+    let p1110000111100001111000011110000 = p111000011110000111100001111.clone().exp_power_of_2(4);
+    p1110000111100001111000011110000 * p111000011110000111100001111
+}
+
 pub fn exp_10540996611094048183<AF: AbstractField>(val: AF) -> AF {
     // Note that 7*10540996611094048183 = 4*(2^64 - 2**32) + 1 = 1 mod (p - 1).
     // Thus as a^{p - 1} = 1 for all a \in F_p, (a^{10540996611094048183})^7 = a.
@@ -2,7 +2,7 @@ use alloc::vec::Vec;
 use core::array;
 
 use crate::field::Field;
-use crate::{AbstractField, TwoAdicField};
+use crate::{kernels, AbstractField, PackedField, TwoAdicField};
 
 /// Computes `Z_H(x)`, where `Z_H` is the zerofier of a multiplicative subgroup of order `2^log_n`.
 pub fn two_adic_subgroup_zerofier<F: TwoAdicField>(log_n: usize, x: F) -> F {
@@ -33,9 +33,9 @@ pub fn cyclic_subgroup_coset_known_order<F: Field>(
 }
 
 #[must_use]
-pub fn add_vecs<F: Field>(v: Vec<F>, w: Vec<F>) -> Vec<F> {
-    assert_eq!(v.len(), w.len());
-    v.into_iter().zip(w).map(|(x, y)| x + y).collect()
+pub fn add_vecs<F: Field>(mut v: Vec<F>, w: Vec<F>) -> Vec<F> {
+    add_slices_in_place(&mut v, &w);
+    v
 }
 
 pub fn sum_vecs<F: Field, I: Iterator<Item = Vec<F>>>(iter: I) -> Vec<F> {
@@ -43,20 +43,88 @@ pub fn sum_vecs<F: Field, I: Iterator<Item = Vec<F>>>(iter: I) -> Vec<F> {
         .expect("sum_vecs: empty iterator")
 }
 
-pub fn scale_vec<F: Field>(s: F, vec: Vec<F>) -> Vec<F> {
-    vec.into_iter().map(|x| s * x).collect()
+pub fn scale_vec<F: Field>(s: F, mut vec: Vec<F>) -> Vec<F> {
+    scale_slice_in_place(s, &mut vec);
+    vec
 }
 
-/// `x += y * s`, where `s` is a scalar.
+/// `x += y`, elementwise.
+pub fn add_slices_in_place<F: Field>(x: &mut [F], y: &[F]) {
+    kernels::add_slices_in_place::<F::Packing>(x, y);
+}
+
+/// `x *= s`, where `s` is a scalar.
+pub fn scale_slice_in_place<F: Field>(s: F, x: &mut [F]) {
+    kernels::scale_slice_in_place::<F::Packing>(s, x);
+}
+
+/// `x += y * s`, where `s` is a scalar. For a slice `y`, `add_scaled_slice_in_place_packed` is
+/// faster.
 pub fn add_scaled_slice_in_place<F, Y>(x: &mut [F], y: Y, s: F)
 where
     F: Field,
     Y: Iterator<Item = F>,
 {
-    // TODO: Use PackedField
     x.iter_mut().zip(y).for_each(|(x_i, y_i)| *x_i += y_i * s);
 }
 
+/// `x += y * s`, where `s` is a scalar, vectorized over `F::Packing`.
+pub fn add_scaled_slice_in_place_packed<F: Field>(x: &mut [F], y: &[F], s: F) {
+    kernels::add_scaled_slice_in_place::<F::Packing>(x, y, s);
+}
+
+/// `x *= y`, elementwise.
+pub fn mul_slices_in_place<F: Field>(x: &mut [F], y: &[F]) {
+    kernels::mul_slices_in_place::<F::Packing>(x, y);
+}
+
+/// The inner product `sum_i x_i * y_i`.
+pub fn dot_product_slices<F: Field>(x: &[F], y: &[F]) -> F {
+    assert_eq!(x.len(), y.len());
+    let (x_packed, x_suffix) = F::Packing::pack_slice_with_suffix(x);
+    let (y_packed, y_suffix) = F::Packing::pack_slice_with_suffix(y);
+    let packed_sum = x_packed
+        .iter()
+        .zip(y_packed)
+        .fold(F::Packing::zero(), |acc, (&x_i, &y_i)| acc + x_i * y_i);
+    let suffix_sum = x_suffix
+        .iter()
+        .zip(y_suffix)
+        .fold(F::zero(), |acc, (&x_i, &y_i)| acc + x_i * y_i);
+    packed_sum.as_slice().iter().copied().sum::<F>() + suffix_sum
+}
+
+/// `x += sum_j ys[j] * weights[j]`, where each `weights[j]` is a scalar.
+///
+/// Each packed chunk of `x` is loaded and stored once, rather than once per slice as repeated
+/// calls to `add_scaled_slice_in_place_packed` would.
+pub fn add_linear_combination_in_place<F: Field>(x: &mut [F], ys: &[&[F]], weights: &[F]) {
+    assert_eq!(ys.len(), weights.len());
+    for y in ys {
+        assert_eq!(x.len(), y.len());
+    }
+    let width = F::Packing::WIDTH;
+    let (x_packed, x_suffix) = F::Packing::pack_slice_with_suffix_mut(x);
+    let ys_packed: Vec<&[F::Packing]> = ys
+        .iter()
+        .map(|y| F::Packing::pack_slice(&y[..x_packed.len() * width]))
+        .collect();
+    for (i, x_i) in x_packed.iter_mut().enumerate() {
+        *x_i += ys_packed
+            .iter()
+            .zip(weights)
+            .fold(F::Packing::zero(), |acc, (y, &w)| acc + y[i] * w);
+    }
+    let suffix_start = x_packed.len() * width;
+    for (i, x_i) in x_suffix.iter_mut().enumerate() {
+        *x_i += ys
+            .iter()
+            .zip(weights)
+            .map(|(y, &w)| y[suffix_start + i] * w)
+            .sum::<F>();
+    }
+}
+
 /// Extend a field `AF` element `x` to an arry of length `D`
 /// by filling zeros.
 pub fn field_to_array<AF: AbstractField, const D: usize>(x: AF) -> [AF; D] {
@@ -0,0 +1,195 @@
+//! Slice kernels that are generic over the packing they vectorize with.
+//!
+//! The slice functions at the crate root, such as `add_slices_in_place`, use `F::Packing`, which
+//! is fixed at compile time. The versions here take the packing as a type parameter instead, so
+//! a field crate can instantiate them with a SIMD packing that was only detected at runtime,
+//! inside a `#[target_feature]` function.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::batch_inverse::batch_multiplicative_inverse;
+use crate::packed::PackedField;
+use crate::AbstractField;
+
+/// `x += y`, elementwise.
+#[inline]
+pub fn add_slices_in_place<P: PackedField>(x: &mut [P::Scalar], y: &[P::Scalar]) {
+    assert_eq!(x.len(), y.len());
+    let (x_packed, x_suffix) = P::pack_slice_with_suffix_mut(x);
+    let (y_packed, y_suffix) = P::pack_slice_with_suffix(y);
+    x_packed
+        .iter_mut()
+        .zip(y_packed)
+        .for_each(|(x_i, &y_i)| *x_i += y_i);
+    x_suffix
+        .iter_mut()
+        .zip(y_suffix)
+        .for_each(|(x_i, &y_i)| *x_i += y_i);
+}
+
+/// `x *= s`, where `s` is a scalar.
+#[inline]
+pub fn scale_slice_in_place<P: PackedField>(s: P::Scalar, x: &mut [P::Scalar]) {
+    let (packed, suffix) = P::pack_slice_with_suffix_mut(x);
+    packed.iter_mut().for_each(|x_i| *x_i *= s);
+    suffix.iter_mut().for_each(|x_i| *x_i *= s);
+}
+
+/// `x += y * s`, where `s` is a scalar.
+#[inline]
+pub fn add_scaled_slice_in_place<P: PackedField>(
+    x: &mut [P::Scalar],
+    y: &[P::Scalar],
+    s: P::Scalar,
+) {
+    assert_eq!(x.len(), y.len());
+    let (x_packed, x_suffix) = P::pack_slice_with_suffix_mut(x);
+    let (y_packed, y_suffix) = P::pack_slice_with_suffix(y);
+    x_packed
+        .iter_mut()
+        .zip(y_packed)
+        .for_each(|(x_i, &y_i)| *x_i += y_i * s);
+    x_suffix
+        .iter_mut()
+        .zip(y_suffix)
+        .for_each(|(x_i, &y_i)| *x_i += y_i * s);
+}
+
+/// `x *= y`, elementwise.
+#[inline]
+pub fn mul_slices_in_place<P: PackedField>(x: &mut [P::Scalar], y: &[P::Scalar]) {
+    assert_eq!(x.len(), y.len());
+    let (x_packed, x_suffix) = P::pack_slice_with_suffix_mut(x);
+    let (y_packed, y_suffix) = P::pack_slice_with_suffix(y);
+    x_packed
+        .iter_mut()
+        .zip(y_packed)
+        .for_each(|(x_i, &y_i)| *x_i *= y_i);
+    x_suffix
+        .iter_mut()
+        .zip(y_suffix)
+        .for_each(|(x_i, &y_i)| *x_i *= y_i);
+}
+
+/// The decimation-in-time butterfly `(x, y) <- (x + y t, x - y t)`, applied elementwise to the
+/// rows `lo` and `hi`.
+#[inline]
+pub fn dit_butterfly<P: PackedField>(
+    lo: &mut [P::Scalar],
+    hi: &mut [P::Scalar],
+    twiddle: P::Scalar,
+) {
+    assert_eq!(lo.len(), hi.len());
+    let (lo_packed, lo_suffix) = P::pack_slice_with_suffix_mut(lo);
+    let (hi_packed, hi_suffix) = P::pack_slice_with_suffix_mut(hi);
+    lo_packed
+        .iter_mut()
+        .zip(hi_packed)
+        .for_each(|(x, y)| dit_butterfly_inner(x, y, twiddle));
+    lo_suffix
+        .iter_mut()
+        .zip(hi_suffix)
+        .for_each(|(x, y)| dit_butterfly_inner(x, y, twiddle));
+}
+
+/// The decimation-in-frequency butterfly `(x, y) <- (x + y, (x - y) t)`, applied elementwise to
+/// the rows `lo` and `hi`.
+#[inline]
+pub fn dif_butterfly<P: PackedField>(
+    lo: &mut [P::Scalar],
+    hi: &mut [P::Scalar],
+    twiddle: P::Scalar,
+) {
+    assert_eq!(lo.len(), hi.len());
+    let (lo_packed, lo_suffix) = P::pack_slice_with_suffix_mut(lo);
+    let (hi_packed, hi_suffix) = P::pack_slice_with_suffix_mut(hi);
+    lo_packed
+        .iter_mut()
+        .zip(hi_packed)
+        .for_each(|(x, y)| dif_butterfly_inner(x, y, twiddle));
+    lo_suffix
+        .iter_mut()
+        .zip(hi_suffix)
+        .for_each(|(x, y)| dif_butterfly_inner(x, y, twiddle));
+}
+
+#[inline(always)]
+fn dit_butterfly_inner<P: PackedField>(x: &mut P, y: &mut P, twiddle: P::Scalar) {
+    let y_t = *y * twiddle;
+    (*x, *y) = (*x + y_t, *x - y_t);
+}
+
+#[inline(always)]
+fn dif_butterfly_inner<P: PackedField>(x: &mut P, y: &mut P, twiddle: P::Scalar) {
+    (*x, *y) = (*x + *y, (*x - *y) * twiddle);
+}
+
+/// Batch multiplicative inverses with Montgomery's trick, vectorized over `P`. See
+/// `batch_multiplicative_inverse_packed_into`.
+///
+/// # Panics
+/// Panics if any element of `x` is zero.
+#[inline]
+pub fn batch_multiplicative_inverse_packed<P: PackedField>(x: &[P::Scalar]) -> Vec<P::Scalar> {
+    let mut result = vec![P::Scalar::zero(); x.len()];
+    batch_multiplicative_inverse_packed_into::<P>(x, &mut result);
+    result
+}
+
+/// Batch multiplicative inverses with Montgomery's trick, vectorized over `P`, written to `out`.
+///
+/// The bulk of `x` is viewed as packed values and split into `CHAINS` interleaved cumulative
+/// product arrays, so every SIMD lane of every packed accumulator is its own dependency chain.
+/// The `CHAINS * P::WIDTH` chain products and the unaligned tail are then inverted together by
+/// `batch_multiplicative_inverse`, so the whole batch still costs a single field inversion. The
+/// result is identical to `batch_multiplicative_inverse`. The cumulative products are kept in
+/// `out`, so apart from the handful of chain products nothing is allocated.
+///
+/// # Panics
+/// Panics if any element of `x` is zero, or if `x` and `out` differ in length.
+#[inline]
+pub fn batch_multiplicative_inverse_packed_into<P: PackedField>(
+    x: &[P::Scalar],
+    out: &mut [P::Scalar],
+) {
+    // Packed accumulators in flight. Unlike the scalar version this is not specialized to its
+    // value, since each packed multiplication already covers `P::WIDTH` chains.
+    const CHAINS: usize = 4;
+
+    assert_eq!(x.len(), out.len());
+    let (x_packed, x_suffix) = P::pack_slice_with_suffix(x);
+    let (out_packed, out_suffix) = P::pack_slice_with_suffix_mut(out);
+    let n = x_packed.len();
+
+    // out[i] = x[i] * x[i - CHAINS] * x[i - 2 * CHAINS] * ..., as in the scalar version.
+    let mut cumul_prod = [P::one(); CHAINS];
+    for (i, (&xi, out_i)) in x_packed.iter().zip(out_packed.iter_mut()).enumerate() {
+        cumul_prod[i % CHAINS] *= xi;
+        *out_i = cumul_prod[i % CHAINS];
+    }
+
+    // Invert every lane of every accumulator, and the scalar tail, in one batch.
+    let mut to_invert: Vec<P::Scalar> = cumul_prod
+        .iter()
+        .flat_map(|p| p.as_slice().iter().copied())
+        .collect();
+    to_invert.extend_from_slice(x_suffix);
+    let inverted = batch_multiplicative_inverse(&to_invert);
+    let (cumul_inv, suffix_inv) = inverted.split_at(CHAINS * P::WIDTH);
+    out_suffix.copy_from_slice(suffix_inv);
+    let mut a_inv: [P; CHAINS] =
+        core::array::from_fn(|k| *P::from_slice(&cumul_inv[k * P::WIDTH..(k + 1) * P::WIDTH]));
+
+    for i in (0..n).rev() {
+        // out[i - CHAINS] is the product of the earlier elements of this chain, so multiplying
+        // by the inverse of everything up to and including x[i] leaves x[i]^-1.
+        let prev = if i >= CHAINS {
+            out_packed[i - CHAINS]
+        } else {
+            P::one()
+        };
+        out_packed[i] = prev * a_inv[i % CHAINS];
+        a_inv[i % CHAINS] *= x_packed[i];
+    }
+}
@@ -6,11 +6,14 @@ extern crate alloc;
 
 mod array;
 mod batch_inverse;
+pub mod dft;
 mod exponentiation;
 pub mod extension;
 mod field;
 mod helpers;
+pub mod kernels;
 mod packed;
+mod polynomial;
 mod symbolic;
 
 pub use array::*;
@@ -19,4 +22,5 @@ pub use exponentiation::*;
 pub use field::*;
 pub use helpers::*;
 pub use packed::*;
+pub use polynomial::*;
 pub use symbolic::*;
@@ -95,6 +95,18 @@ pub unsafe trait PackedField: AbstractField<F = Self::Scalar>
         let n = buf.len() / Self::WIDTH;
         unsafe { slice::from_raw_parts_mut(buf_ptr, n) }
     }
+
+    /// Split `buf` into a packed prefix and a scalar suffix of fewer than `WIDTH` elements.
+    fn pack_slice_with_suffix(buf: &[Self::Scalar]) -> (&[Self], &[Self::Scalar]) {
+        let (packed, suffix) = buf.split_at(buf.len() - buf.len() % Self::WIDTH);
+        (Self::pack_slice(packed), suffix)
+    }
+
+    /// Mutable version of `pack_slice_with_suffix`.
+    fn pack_slice_with_suffix_mut(buf: &mut [Self::Scalar]) -> (&mut [Self], &mut [Self::Scalar]) {
+        let (packed, suffix) = buf.split_at_mut(buf.len() - buf.len() % Self::WIDTH);
+        (Self::pack_slice_mut(packed), suffix)
+    }
 }
 
 unsafe impl<F: Field> PackedField for F {
@@ -0,0 +1,370 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::batch_multiplicative_inverse;
+use crate::dft::{Radix2Dit, TwoAdicSubgroupDft};
+use crate::field::{ExtensionField, Field, TwoAdicField};
+
+/// A dense univariate polynomial `\sum_i coeffs[i] X^i`.
+///
+/// The coefficient vector never has trailing zeros, so the zero polynomial has no coefficients.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polynomial<F: Field> {
+    coeffs: Vec<F>,
+}
+
+impl<F: Field> Polynomial<F> {
+    /// Create a polynomial from its coefficients, lowest degree first.
+    pub fn new(coeffs: Vec<F>) -> Self {
+        let mut poly = Self { coeffs };
+        poly.trim();
+        poly
+    }
+
+    pub fn zero() -> Self {
+        Self { coeffs: Vec::new() }
+    }
+
+    pub fn constant(c: F) -> Self {
+        Self::new(vec![c])
+    }
+
+    /// Returns `c X^degree`.
+    pub fn monomial(c: F, degree: usize) -> Self {
+        let mut coeffs = vec![F::zero(); degree + 1];
+        coeffs[degree] = c;
+        Self::new(coeffs)
+    }
+
+    /// Returns `X^n - c`.
+    pub fn binomial(n: usize, c: F) -> Self {
+        let mut coeffs = vec![F::zero(); n + 1];
+        coeffs[0] = -c;
+        coeffs[n] += F::one();
+        Self::new(coeffs)
+    }
+
+    /// The coefficients, lowest degree first, without trailing zeros.
+    pub fn coeffs(&self) -> &[F] {
+        &self.coeffs
+    }
+
+    pub fn into_coeffs(self) -> Vec<F> {
+        self.coeffs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn leading_coeff(&self) -> Option<F> {
+        self.coeffs.last().copied()
+    }
+
+    /// Evaluate at a base field point using Horner's rule.
+    pub fn eval(&self, x: F) -> F {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, &c| acc * x + c)
+    }
+
+    /// Evaluate at an extension field point using Horner's rule.
+    pub fn eval_ext<EF: ExtensionField<F>>(&self, x: EF) -> EF {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(EF::zero(), |acc, &c| acc * x + c)
+    }
+
+    /// Returns `(q, r)` such that `self = q * divisor + r` and `deg(r) < deg(divisor)`.
+    ///
+    /// # Panics
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let divisor_degree = divisor
+            .degree()
+            .expect("Tried to divide by zero polynomial");
+        if self.coeffs.len() <= divisor_degree {
+            return (Self::zero(), self.clone());
+        }
+
+        let lead_inv = divisor.coeffs[divisor_degree].inverse();
+        let mut rem = self.coeffs.clone();
+        let mut quotient = vec![F::zero(); rem.len() - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let q = rem[i + divisor_degree] * lead_inv;
+            quotient[i] = q;
+            for (r, &d) in rem[i..=i + divisor_degree].iter_mut().zip(&divisor.coeffs) {
+                *r -= q * d;
+            }
+        }
+        rem.truncate(divisor_degree);
+        (Self::new(quotient), Self::new(rem))
+    }
+
+    /// Returns `(q, r)` such that `self = q * (X^n - c) + r` and `deg(r) < n`. This takes
+    /// `O(deg(self))` operations, versus `O(n deg(self))` for the general `div_rem`.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub fn div_rem_binomial(&self, n: usize, c: F) -> (Self, Self) {
+        assert!(n > 0, "X^0 - c is constant");
+        if self.coeffs.len() <= n {
+            return (Self::zero(), self.clone());
+        }
+
+        // Going from the top, each coefficient a_i of the running remainder with i >= n is the
+        // quotient coefficient q_{i-n}; subtracting q_{i-n} X^{i-n} (X^n - c) adds c q_{i-n} to
+        // a_{i-n}.
+        let mut rem = self.coeffs.clone();
+        let mut quotient = vec![F::zero(); rem.len() - n];
+        for i in (n..rem.len()).rev() {
+            let q = rem[i];
+            quotient[i - n] = q;
+            rem[i - n] += c * q;
+        }
+        rem.truncate(n);
+        (Self::new(quotient), Self::new(rem))
+    }
+
+    pub fn scale(&self, s: F) -> Self {
+        Self::new(self.coeffs.iter().map(|&c| c * s).collect())
+    }
+
+    /// The formal derivative.
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| c * F::from_canonical_usize(i))
+                .collect(),
+        )
+    }
+
+    /// Returns `\prod_i (X - roots[i])`.
+    pub fn from_roots(roots: &[F]) -> Self {
+        roots
+            .iter()
+            .map(|&r| Self::new(vec![-r, F::one()]))
+            .fold(Self::constant(F::one()), |acc, p| &acc * &p)
+    }
+
+    /// Lagrange interpolation of the unique polynomial of degree `< points.len()` passing through
+    /// the given `(x, y)` pairs. Takes `O(n^2)` operations.
+    ///
+    /// # Panics
+    /// Panics if two points share an `x` coordinate.
+    pub fn interpolate(points: &[(F, F)]) -> Self {
+        let xs: Vec<F> = points.iter().map(|&(x, _)| x).collect();
+        let vanishing = Self::from_roots(&xs);
+
+        // The barycentric weight of x_j is 1 / \prod_{k != j} (x_j - x_k) = 1 / Z'(x_j).
+        let vanishing_derivative = vanishing.derivative();
+        let denominators: Vec<F> = xs.iter().map(|&x| vanishing_derivative.eval(x)).collect();
+        assert!(
+            denominators.iter().all(|d| !d.is_zero()),
+            "interpolation points must be distinct"
+        );
+        let weights = batch_multiplicative_inverse(&denominators);
+
+        let mut coeffs = vec![F::zero(); points.len()];
+        for (&(x, y), w) in points.iter().zip(weights) {
+            let (basis, _) = vanishing.div_rem_binomial(1, x);
+            let scale = y * w;
+            for (c, &b) in coeffs.iter_mut().zip(basis.coeffs()) {
+                *c += scale * b;
+            }
+        }
+        Self::new(coeffs)
+    }
+
+    fn trim(&mut self) {
+        while self.coeffs.last().is_some_and(Field::is_zero) {
+            self.coeffs.pop();
+        }
+    }
+}
+
+impl<F: TwoAdicField> Polynomial<F> {
+    /// Returns `Z_{sH}(X) = X^n - s^n`, the zerofier of the coset `shift * H` of the subgroup of
+    /// order `n = 2^log_n`.
+    pub fn two_adic_coset_zerofier(log_n: usize, shift: F) -> Self {
+        Self::binomial(1 << log_n, shift.exp_power_of_2(log_n))
+    }
+
+    /// Returns `(q, r)` such that `self = q * Z_{sH} + r`, where `Z_{sH}` is the zerofier of the
+    /// coset `shift * H` of the subgroup of order `2^log_n`.
+    pub fn div_rem_two_adic_coset_zerofier(&self, log_n: usize, shift: F) -> (Self, Self) {
+        self.div_rem_binomial(1 << log_n, shift.exp_power_of_2(log_n))
+    }
+
+    /// Interpolate the unique polynomial of degree `< evals.len()` taking the given values over
+    /// the coset `shift * H`, where `H` has order `evals.len()`.
+    pub fn interpolate_coset(evals: &[F], shift: F) -> Self {
+        let mut coeffs = evals.to_vec();
+        Radix2Dit.coset_idft(&mut coeffs, shift);
+        Self::new(coeffs)
+    }
+
+    /// Evaluate over the coset `shift * H` of the subgroup of order `2^log_n`, which must exceed
+    /// the degree.
+    ///
+    /// # Panics
+    /// Panics if the polynomial has `2^log_n` or more coefficients.
+    pub fn evaluate_coset(&self, log_n: usize, shift: F) -> Vec<F> {
+        let n = 1 << log_n;
+        assert!(
+            self.coeffs.len() <= n,
+            "a degree {} polynomial is not determined by {n} evaluations",
+            self.coeffs.len() - 1
+        );
+        let mut evals = self.coeffs.clone();
+        evals.resize(n, F::zero());
+        Radix2Dit.coset_dft(&mut evals, shift);
+        evals
+    }
+}
+
+impl<F: Field> From<Vec<F>> for Polynomial<F> {
+    fn from(coeffs: Vec<F>) -> Self {
+        Self::new(coeffs)
+    }
+}
+
+impl<F: Field> Add for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn add(self, rhs: Self) -> Polynomial<F> {
+        let (long, short) = if self.coeffs.len() >= rhs.coeffs.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let mut coeffs = long.coeffs.clone();
+        for (c, &s) in coeffs.iter_mut().zip(&short.coeffs) {
+            *c += s;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<F: Field> Add for Polynomial<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        &self + &rhs
+    }
+}
+
+impl<F: Field> AddAssign for Polynomial<F> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = &*self + &rhs;
+    }
+}
+
+impl<F: Field> Sub for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn sub(self, rhs: Self) -> Polynomial<F> {
+        let len = self.coeffs.len().max(rhs.coeffs.len());
+        let coeffs = (0..len)
+            .map(|i| {
+                let a = self.coeffs.get(i).copied().unwrap_or_default();
+                let b = rhs.coeffs.get(i).copied().unwrap_or_default();
+                a - b
+            })
+            .collect();
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<F: Field> Sub for Polynomial<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        &self - &rhs
+    }
+}
+
+impl<F: Field> SubAssign for Polynomial<F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = &*self - &rhs;
+    }
+}
+
+impl<F: Field> Neg for Polynomial<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            coeffs: self.coeffs.into_iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+impl<F: Field> Mul for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn mul(self, rhs: Self) -> Polynomial<F> {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::zero();
+        }
+        let mut coeffs = vec![F::zero(); self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (c, &b) in coeffs[i..].iter_mut().zip(&rhs.coeffs) {
+                *c += a * b;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<F: Field> Mul for Polynomial<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        &self * &rhs
+    }
+}
+
+impl<F: Field> MulAssign for Polynomial<F> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = &*self * &rhs;
+    }
+}
+
+impl<F: Field> Mul<F> for Polynomial<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: F) -> Self {
+        self.scale(rhs)
+    }
+}
+
+impl<F: Field> MulAssign<F> for Polynomial<F> {
+    fn mul_assign(&mut self, rhs: F) {
+        *self = self.scale(rhs);
+    }
+}
+
+impl<F: Field> iter::Sum for Polynomial<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, p| acc + p)
+    }
+}
+
+impl<F: Field> iter::Product for Polynomial<F> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::constant(F::one()), |acc, p| acc * p)
+    }
+}
@@ -0,0 +1,191 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Write};
+use core::hash::Hash;
+
+use super::{NodeId, SymbolicDag, SymbolicField, SymbolicNode};
+use crate::field::PrimeField64;
+
+impl<F: PrimeField64, Var: Clone + Debug + Eq + Hash> SymbolicDag<F, Var> {
+    /// Generates the source of a straight-line Rust function computing `roots`:
+    ///
+    /// ```text
+    /// pub fn fn_name<AF: p3_field::AbstractField>(vars: &[AF]) -> [AF; roots.len()]
+    /// ```
+    ///
+    /// Variable `v` is read from `vars[var_index(v)]`. Each node is bound to a temporary once,
+    /// so shared subexpressions are computed once, and a temporary is moved into its last use
+    /// rather than cloned. Products of a single base are emitted as `square`, `cube` or
+    /// `exp_const_u64`.
+    ///
+    /// The output only refers to `AF` through fully qualified paths, so it can be `include!`d
+    /// anywhere `p3_field` is a dependency. It is only meaningful for `AF` whose `AF::F` is `F`,
+    /// since constants are emitted as canonical integers.
+    pub fn to_rust(
+        &self,
+        fn_name: &str,
+        roots: &[NodeId],
+        mut var_index: impl FnMut(&Var) -> usize,
+    ) -> String {
+        let len = roots.iter().max().map_or(0, |r| r.index() + 1);
+        let nodes = &self.nodes()[..len];
+
+        // Treat a product of powers of a single base `b` as `b^k`.
+        let mut powers: Vec<(NodeId, u64)> = Vec::with_capacity(len);
+        for (i, node) in nodes.iter().enumerate() {
+            let power = match *node {
+                SymbolicNode::Mul(a, b) => {
+                    let ((base_a, k_a), (base_b, k_b)) = (powers[a.index()], powers[b.index()]);
+                    let is_constant = matches!(nodes[base_a.index()], SymbolicNode::Constant(_));
+                    match k_a.checked_add(k_b) {
+                        Some(k) if base_a == base_b && !is_constant => Some((base_a, k)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            powers.push(power.unwrap_or((NodeId(i), 1)));
+        }
+        let dependencies = |i: usize| -> Vec<NodeId> {
+            match powers[i] {
+                (base, k) if k >= 2 => vec![base],
+                _ => nodes[i].operands().collect(),
+            }
+        };
+
+        // Count the uses of each node that will actually be emitted.
+        let mut needed = vec![false; len];
+        let mut uses = vec![0usize; len];
+        for root in roots {
+            needed[root.index()] = true;
+            uses[root.index()] += 1;
+        }
+        for i in (0..len).rev() {
+            if needed[i] {
+                for dependency in dependencies(i) {
+                    needed[dependency.index()] = true;
+                    uses[dependency.index()] += 1;
+                }
+            }
+        }
+
+        let var_indices: Vec<Option<usize>> = nodes
+            .iter()
+            .map(|node| match node {
+                SymbolicNode::Variable(v) => Some(var_index(v)),
+                _ => None,
+            })
+            .collect();
+        let mut names: Vec<Option<String>> = vec![None; len];
+        let mut num_temps = 0;
+        // An operand passed by value: variables are cloned out of `vars`, constants are built
+        // inline, and temporaries are cloned except at their last use.
+        let by_value =
+            |id: NodeId, names: &[Option<String>], uses: &mut [usize]| match &nodes[id.index()] {
+                SymbolicNode::Variable(_) => {
+                    format!("vars[{}].clone()", var_indices[id.index()].unwrap())
+                }
+                SymbolicNode::Constant(c) => constant_to_rust(*c),
+                _ => {
+                    let name = names[id.index()].clone().unwrap();
+                    uses[id.index()] -= 1;
+                    if uses[id.index()] == 0 {
+                        name
+                    } else {
+                        format!("{name}.clone()")
+                    }
+                }
+            };
+
+        let mut body = String::new();
+        for i in (0..len).filter(|&i| needed[i]) {
+            let expr = match (&nodes[i], powers[i]) {
+                (SymbolicNode::Variable(_) | SymbolicNode::Constant(_), _) => continue,
+                (_, (base, k)) if k >= 2 => {
+                    // Methods take `&self`, so the base is borrowed rather than moved.
+                    uses[base.index()] -= 1;
+                    let base = match &nodes[base.index()] {
+                        SymbolicNode::Variable(_) => {
+                            format!("vars[{}]", var_indices[base.index()].unwrap())
+                        }
+                        _ => names[base.index()].clone().unwrap(),
+                    };
+                    match k {
+                        2 => format!("AF::square(&{base})"),
+                        3 => format!("AF::cube(&{base})"),
+                        _ => format!("AF::exp_const_u64::<{k}>(&{base})"),
+                    }
+                }
+                (SymbolicNode::Add(a, b), _) => {
+                    let a = by_value(*a, &names, &mut uses);
+                    format!("{a} + {}", by_value(*b, &names, &mut uses))
+                }
+                (SymbolicNode::Sub(a, b), _) => {
+                    let a = by_value(*a, &names, &mut uses);
+                    format!("{a} - {}", by_value(*b, &names, &mut uses))
+                }
+                (SymbolicNode::Mul(a, b), _) => {
+                    let a = by_value(*a, &names, &mut uses);
+                    format!("{a} * {}", by_value(*b, &names, &mut uses))
+                }
+                (SymbolicNode::Neg(a), _) => format!("-{}", by_value(*a, &names, &mut uses)),
+            };
+            let name = format!("t{num_temps}");
+            num_temps += 1;
+            writeln!(body, "    let {name} = {expr};").unwrap();
+            names[i] = Some(name);
+        }
+
+        let outputs: Vec<String> = roots
+            .iter()
+            .map(|&root| by_value(root, &names, &mut uses))
+            .collect();
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            "pub fn {fn_name}<AF: p3_field::AbstractField>(vars: &[AF]) -> [AF; {}] {{",
+            roots.len()
+        )
+        .unwrap();
+        out.push_str(&body);
+        writeln!(out, "    [{}]", outputs.join(", ")).unwrap();
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl<F: PrimeField64, Var: Clone + Debug + Eq + Hash> SymbolicField<F, Var> {
+    /// Simplifies the expression and generates a straight-line Rust function computing it. See
+    /// `SymbolicDag::to_rust`.
+    pub fn to_rust(&self, fn_name: &str, var_index: impl FnMut(&Var) -> usize) -> String {
+        let mut dag = SymbolicDag::new();
+        let root = dag.insert(self);
+        dag.to_rust(fn_name, &[root], var_index)
+    }
+}
+
+/// A Rust expression of type `AF` for the constant `c`, using whichever of `c` and `-c` has the
+/// smaller canonical representative.
+fn constant_to_rust<F: PrimeField64>(c: F) -> String {
+    if c.is_zero() {
+        return "AF::zero()".into();
+    }
+    if c.is_one() {
+        return "AF::one()".into();
+    }
+    if c == F::two() {
+        return "AF::two()".into();
+    }
+    if c == F::neg_one() {
+        return "AF::neg_one()".into();
+    }
+    let value = c.as_canonical_u64();
+    if F::ORDER_U64 - value < value {
+        format!("-{}", constant_to_rust(-c))
+    } else {
+        format!("AF::from_canonical_u64({value})")
+    }
+}
@@ -0,0 +1,370 @@
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+
+use super::SymbolicField;
+use crate::field::Field;
+
+/// Index of a node within a `SymbolicDag`.
+///
+/// Operands are always inserted before the nodes that use them, so a node's id is greater than
+/// the ids of its operands and iterating in id order is a valid evaluation order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(pub(crate) usize);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A node of a `SymbolicDag`, with operands referenced by id.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SymbolicNode<F: Field, Var> {
+    Variable(Var),
+    Constant(F),
+    Add(NodeId, NodeId),
+    Sub(NodeId, NodeId),
+    Neg(NodeId),
+    Mul(NodeId, NodeId),
+}
+
+impl<F: Field, Var> SymbolicNode<F, Var> {
+    /// The ids of this node's operands.
+    pub fn operands(&self) -> impl Iterator<Item = NodeId> {
+        let (a, b) = match *self {
+            Self::Variable(_) | Self::Constant(_) => (None, None),
+            Self::Neg(a) => (Some(a), None),
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => (Some(a), Some(b)),
+        };
+        a.into_iter().chain(b)
+    }
+}
+
+/// A hash-consed expression DAG.
+///
+/// Structurally equal nodes are stored once, so common subexpressions are shared. The `add`,
+/// `sub`, `neg` and `mul` constructors additionally fold constants and apply algebraic
+/// identities (`x + 0 = x`, `x * 0 = 0`, `x - x = 0`, `-(-x) = x`, ...) before interning.
+#[derive(Clone, Debug)]
+pub struct SymbolicDag<F: Field, Var: Eq + Hash> {
+    nodes: Vec<SymbolicNode<F, Var>>,
+    ids: HashMap<SymbolicNode<F, Var>, NodeId>,
+}
+
+impl<F: Field, Var: Eq + Hash> Default for SymbolicDag<F, Var> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+}
+
+impl<F: Field, Var: Clone + Debug + Eq + Hash> SymbolicDag<F, Var> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of distinct nodes in the DAG.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, id: NodeId) -> &SymbolicNode<F, Var> {
+        &self.nodes[id.0]
+    }
+
+    /// All nodes, in an order where operands precede their users.
+    pub fn nodes(&self) -> &[SymbolicNode<F, Var>] {
+        &self.nodes
+    }
+
+    /// The number of distinct nodes `root` depends on, including itself.
+    pub fn reachable_count(&self, root: NodeId) -> usize {
+        self.reachable_from(&[root]).iter().filter(|&&r| r).count()
+    }
+
+    /// Returns the id of `node`, adding it if no structurally equal node exists yet. No
+    /// simplification is applied.
+    pub fn intern(&mut self, node: SymbolicNode<F, Var>) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        id
+    }
+
+    pub fn variable(&mut self, var: Var) -> NodeId {
+        self.intern(SymbolicNode::Variable(var))
+    }
+
+    pub fn constant(&mut self, c: F) -> NodeId {
+        self.intern(SymbolicNode::Constant(c))
+    }
+
+    pub fn add(&mut self, a: NodeId, b: NodeId) -> NodeId {
+        match (self.as_constant(a), self.as_constant(b)) {
+            (Some(x), Some(y)) => return self.constant(x + y),
+            (Some(x), _) if x.is_zero() => return b,
+            (_, Some(y)) if y.is_zero() => return a,
+            _ => {}
+        }
+        if a == b {
+            let two = self.constant(F::two());
+            return self.mul(two, a);
+        }
+        if let SymbolicNode::Neg(y) = self.nodes[b.0] {
+            return self.sub(a, y);
+        }
+        if let SymbolicNode::Neg(x) = self.nodes[a.0] {
+            return self.sub(b, x);
+        }
+
+        let (a, b) = self.commutative_order(a, b);
+        // c1 + (c2 + y) = (c1 + c2) + y
+        if let (Some(x), SymbolicNode::Add(c, y)) = (self.as_constant(a), &self.nodes[b.0]) {
+            if let Some(z) = self.as_constant(*c) {
+                let y = *y;
+                let sum = self.constant(x + z);
+                return self.add(sum, y);
+            }
+        }
+        self.intern(SymbolicNode::Add(a, b))
+    }
+
+    pub fn sub(&mut self, a: NodeId, b: NodeId) -> NodeId {
+        match (self.as_constant(a), self.as_constant(b)) {
+            (Some(x), Some(y)) => return self.constant(x - y),
+            (_, Some(y)) if y.is_zero() => return a,
+            (Some(x), _) if x.is_zero() => return self.neg(b),
+            // x - c = (-c) + x, so that constants collect on the left of additions.
+            (_, Some(y)) => {
+                let neg_y = self.constant(-y);
+                return self.add(neg_y, a);
+            }
+            _ => {}
+        }
+        if a == b {
+            return self.constant(F::zero());
+        }
+        if let SymbolicNode::Neg(y) = self.nodes[b.0] {
+            return self.add(a, y);
+        }
+        self.intern(SymbolicNode::Sub(a, b))
+    }
+
+    pub fn neg(&mut self, a: NodeId) -> NodeId {
+        match self.nodes[a.0] {
+            SymbolicNode::Constant(x) => self.constant(-x),
+            SymbolicNode::Neg(x) => x,
+            SymbolicNode::Sub(x, y) => self.sub(y, x),
+            _ => self.intern(SymbolicNode::Neg(a)),
+        }
+    }
+
+    pub fn mul(&mut self, a: NodeId, b: NodeId) -> NodeId {
+        match (self.as_constant(a), self.as_constant(b)) {
+            (Some(x), Some(y)) => return self.constant(x * y),
+            (Some(x), _) | (_, Some(x)) if x.is_zero() => return self.constant(F::zero()),
+            (Some(x), _) if x.is_one() => return b,
+            (_, Some(y)) if y.is_one() => return a,
+            (Some(x), _) if x == F::neg_one() => return self.neg(b),
+            (_, Some(y)) if y == F::neg_one() => return self.neg(a),
+            _ => {}
+        }
+        if let (SymbolicNode::Neg(x), SymbolicNode::Neg(y)) = (&self.nodes[a.0], &self.nodes[b.0]) {
+            let (x, y) = (*x, *y);
+            return self.mul(x, y);
+        }
+
+        let (a, b) = self.commutative_order(a, b);
+        // c1 * (c2 * y) = (c1 * c2) * y
+        if let (Some(x), SymbolicNode::Mul(c, y)) = (self.as_constant(a), &self.nodes[b.0]) {
+            if let Some(z) = self.as_constant(*c) {
+                let y = *y;
+                let product = self.constant(x * z);
+                return self.mul(product, y);
+            }
+        }
+        self.intern(SymbolicNode::Mul(a, b))
+    }
+
+    /// Adds `expr` to the DAG, simplifying as it goes, and returns the id of its root.
+    pub fn insert(&mut self, expr: &SymbolicField<F, Var>) -> NodeId {
+        self.insert_with(expr, true)
+    }
+
+    /// Adds `expr` to the DAG with hash-consing only, and returns the id of its root.
+    pub fn insert_unsimplified(&mut self, expr: &SymbolicField<F, Var>) -> NodeId {
+        self.insert_with(expr, false)
+    }
+
+    /// Rebuilds the expression rooted at `root` as a `SymbolicField`. Each DAG node becomes a
+    /// single shared `Rc`, however many times it is used.
+    pub fn to_symbolic(&self, root: NodeId) -> SymbolicField<F, Var> {
+        let reachable = self.reachable_from(&[root]);
+        let mut built: Vec<Option<Rc<SymbolicField<F, Var>>>> = vec![None; root.0 + 1];
+        for i in (0..=root.0).filter(|&i| reachable[i]) {
+            let get = |id: NodeId| built[id.0].clone().expect("operand built before its user");
+            let expr = match self.nodes[i] {
+                SymbolicNode::Variable(ref v) => SymbolicField::Variable(v.clone()),
+                SymbolicNode::Constant(c) => SymbolicField::Constant(c),
+                SymbolicNode::Add(a, b) => SymbolicField::Add(get(a), get(b)),
+                SymbolicNode::Sub(a, b) => SymbolicField::Sub(get(a), get(b)),
+                SymbolicNode::Neg(a) => SymbolicField::Neg(get(a)),
+                SymbolicNode::Mul(a, b) => SymbolicField::Mul(get(a), get(b)),
+            };
+            built[i] = Some(Rc::new(expr));
+        }
+        (*built[root.0].take().unwrap()).clone()
+    }
+
+    fn as_constant(&self, id: NodeId) -> Option<F> {
+        match self.nodes[id.0] {
+            SymbolicNode::Constant(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Orders the operands of a commutative operation canonically: a constant goes on the left,
+    /// otherwise the smaller id does.
+    fn commutative_order(&self, a: NodeId, b: NodeId) -> (NodeId, NodeId) {
+        let a_const = self.as_constant(a).is_some();
+        let b_const = self.as_constant(b).is_some();
+        if (b_const && !a_const) || (a_const == b_const && b < a) {
+            (b, a)
+        } else {
+            (a, b)
+        }
+    }
+
+    /// Marks the nodes any of `roots` depends on, indexed by id up to the largest root.
+    pub(crate) fn reachable_from(&self, roots: &[NodeId]) -> Vec<bool> {
+        let Some(max_root) = roots.iter().max() else {
+            return Vec::new();
+        };
+        let mut reachable = vec![false; max_root.0 + 1];
+        for root in roots {
+            reachable[root.0] = true;
+        }
+        for i in (0..=max_root.0).rev() {
+            if reachable[i] {
+                for operand in self.nodes[i].operands() {
+                    reachable[operand.0] = true;
+                }
+            }
+        }
+        reachable
+    }
+
+    fn insert_with(&mut self, expr: &SymbolicField<F, Var>, simplify: bool) -> NodeId {
+        let ids = expr.fold_subtrees(|e, id_of| match e {
+            SymbolicField::Variable(v) => self.variable(v.clone()),
+            SymbolicField::Constant(c) => self.constant(*c),
+            SymbolicField::Add(a, b) if simplify => self.add(*id_of(a), *id_of(b)),
+            SymbolicField::Sub(a, b) if simplify => self.sub(*id_of(a), *id_of(b)),
+            SymbolicField::Neg(a) if simplify => self.neg(*id_of(a)),
+            SymbolicField::Mul(a, b) if simplify => self.mul(*id_of(a), *id_of(b)),
+            SymbolicField::Add(a, b) => self.intern(SymbolicNode::Add(*id_of(a), *id_of(b))),
+            SymbolicField::Sub(a, b) => self.intern(SymbolicNode::Sub(*id_of(a), *id_of(b))),
+            SymbolicField::Neg(a) => self.intern(SymbolicNode::Neg(*id_of(a))),
+            SymbolicField::Mul(a, b) => self.intern(SymbolicNode::Mul(*id_of(a), *id_of(b))),
+        });
+        ids[&(expr as *const _)]
+    }
+}
+
+/// Node counts for a `SymbolicField` expression.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SymbolicStats {
+    /// Nodes in the fully expanded tree, counting a shared subtree once per use. Saturates at
+    /// `usize::MAX`.
+    pub tree_nodes: usize,
+    /// Distinct `Rc` allocations, i.e. the nodes actually held in memory.
+    pub allocated_nodes: usize,
+    /// Structurally distinct nodes, i.e. the size after common-subexpression elimination.
+    pub unique_nodes: usize,
+}
+
+impl<F: Field, Var: Clone + Debug> SymbolicField<F, Var> {
+    /// The direct subexpressions of this node.
+    pub fn operands(&self) -> impl Iterator<Item = &Self> {
+        let (a, b) = match self {
+            Self::Variable(_) | Self::Constant(_) => (None, None),
+            Self::Neg(a) => (Some(&**a), None),
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => (Some(&**a), Some(&**b)),
+        };
+        a.into_iter().chain(b)
+    }
+
+    /// Returns an equivalent expression with constants folded, algebraic identities applied and
+    /// common subexpressions shared.
+    pub fn simplify(&self) -> Self
+    where
+        Var: Eq + Hash,
+    {
+        let mut dag = SymbolicDag::new();
+        let root = dag.insert(self);
+        dag.to_symbolic(root)
+    }
+
+    pub fn stats(&self) -> SymbolicStats
+    where
+        Var: Eq + Hash,
+    {
+        let sizes = self.fold_subtrees(|e, size_of| {
+            e.operands()
+                .map(size_of)
+                .fold(1usize, |acc, &size| acc.saturating_add(size))
+        });
+
+        let mut dag = SymbolicDag::new();
+        dag.insert_unsimplified(self);
+
+        SymbolicStats {
+            tree_nodes: sizes[&(self as *const Self)],
+            allocated_nodes: sizes.len(),
+            unique_nodes: dag.len(),
+        }
+    }
+
+    /// Computes `f(e, value_of)` for every distinct subtree `e` of `self`, operands first, where
+    /// `value_of` looks up the result already computed for an operand. Results are keyed by
+    /// subtree address, so a subtree shared through an `Rc` is visited once however many times
+    /// it is used.
+    ///
+    /// The traversal uses an explicit stack, since long sums and products produce very deep
+    /// trees.
+    pub(crate) fn fold_subtrees<T>(
+        &self,
+        mut f: impl for<'v> FnMut(&Self, &'v dyn Fn(&Self) -> &'v T) -> T,
+    ) -> HashMap<*const Self, T> {
+        let mut values: HashMap<*const Self, T> = HashMap::new();
+        let mut stack = vec![(self, false)];
+        while let Some((e, operands_done)) = stack.pop() {
+            let key = e as *const Self;
+            if values.contains_key(&key) {
+                continue;
+            }
+            if !operands_done {
+                stack.push((e, true));
+                stack.extend(e.operands().map(|operand| (operand, false)));
+                continue;
+            }
+            let value = f(e, &|operand| &values[&(operand as *const Self)]);
+            values.insert(key, value);
+        }
+        values
+    }
+}
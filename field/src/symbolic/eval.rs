@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::hash::Hash;
+
+use super::{NodeId, SymbolicDag, SymbolicField, SymbolicNode};
+use crate::field::{AbstractExtensionField, AbstractField, Field};
+
+impl<F: Field, Var: Clone + Debug> SymbolicField<F, Var> {
+    /// Evaluates the expression, substituting `var(v)` for each variable `v`.
+    ///
+    /// `AF` may be `F` itself, `F::Packing` to evaluate several rows at once, or a `FieldArray`.
+    /// Shared subexpressions are evaluated once.
+    pub fn eval<AF: AbstractField<F = F>>(&self, var: impl FnMut(&Var) -> AF) -> AF {
+        self.eval_with(AF::from_f, var)
+    }
+
+    /// Like `eval`, but evaluates into an extension of `F`, such as when variables are assigned
+    /// extension field challenges.
+    pub fn eval_ext<EF: AbstractExtensionField<F>>(&self, var: impl FnMut(&Var) -> EF) -> EF {
+        self.eval_with(EF::from_base, var)
+    }
+
+    /// Evaluates the expression, lifting each constant with `constant` and substituting
+    /// `var(v)` for each variable `v`.
+    pub fn eval_with<T: AbstractField>(
+        &self,
+        mut constant: impl FnMut(F) -> T,
+        mut var: impl FnMut(&Var) -> T,
+    ) -> T {
+        let mut values = self.fold_subtrees::<T>(|e, value_of| match e {
+            Self::Variable(v) => var(v),
+            Self::Constant(c) => constant(*c),
+            Self::Add(a, b) => value_of(a).clone() + value_of(b).clone(),
+            Self::Sub(a, b) => value_of(a).clone() - value_of(b).clone(),
+            Self::Neg(a) => -value_of(a).clone(),
+            Self::Mul(a, b) => value_of(a).clone() * value_of(b).clone(),
+        });
+        values.remove(&(self as *const Self)).unwrap()
+    }
+
+    /// The total degree of the expression as a polynomial in its variables.
+    ///
+    /// This is the syntactic degree: cancellations such as `x * x - x * x` are not detected, so
+    /// it is an upper bound. Calling `simplify` first tightens it. Saturates at `usize::MAX`.
+    pub fn degree(&self) -> usize {
+        let degrees = self.fold_subtrees::<usize>(|e, degree_of| match e {
+            Self::Variable(_) => 1,
+            Self::Constant(_) => 0,
+            Self::Add(a, b) | Self::Sub(a, b) => *degree_of(a).max(degree_of(b)),
+            Self::Neg(a) => *degree_of(a),
+            Self::Mul(a, b) => degree_of(a).saturating_add(*degree_of(b)),
+        });
+        degrees[&(self as *const Self)]
+    }
+}
+
+impl<F: Field, Var: Clone + Debug + Eq + Hash> SymbolicDag<F, Var> {
+    /// Evaluates each of `roots`, computing every node they depend on exactly once.
+    pub fn eval<AF: AbstractField<F = F>>(
+        &self,
+        roots: &[NodeId],
+        var: impl FnMut(&Var) -> AF,
+    ) -> Vec<AF> {
+        self.eval_with(roots, AF::from_f, var)
+    }
+
+    /// Like `eval`, but evaluates into an extension of `F`.
+    pub fn eval_ext<EF: AbstractExtensionField<F>>(
+        &self,
+        roots: &[NodeId],
+        var: impl FnMut(&Var) -> EF,
+    ) -> Vec<EF> {
+        self.eval_with(roots, EF::from_base, var)
+    }
+
+    /// Evaluates each of `roots`, lifting each constant with `constant` and substituting
+    /// `var(v)` for each variable `v`.
+    pub fn eval_with<T: AbstractField>(
+        &self,
+        roots: &[NodeId],
+        mut constant: impl FnMut(F) -> T,
+        mut var: impl FnMut(&Var) -> T,
+    ) -> Vec<T> {
+        let Some(&max_root) = roots.iter().max() else {
+            return Vec::new();
+        };
+        let needed = self.reachable_from(roots);
+
+        // Nodes are in dependency order, so one forward pass suffices.
+        let mut values: Vec<Option<T>> = Vec::with_capacity(max_root.index() + 1);
+        for (node, &needed) in self.nodes()[..=max_root.index()].iter().zip(&needed) {
+            if !needed {
+                values.push(None);
+                continue;
+            }
+            let value_of = |id: NodeId| values[id.index()].clone().unwrap();
+            let value = match node {
+                SymbolicNode::Variable(v) => var(v),
+                SymbolicNode::Constant(c) => constant(*c),
+                SymbolicNode::Add(a, b) => value_of(*a) + value_of(*b),
+                SymbolicNode::Sub(a, b) => value_of(*a) - value_of(*b),
+                SymbolicNode::Neg(a) => -value_of(*a),
+                SymbolicNode::Mul(a, b) => value_of(*a) * value_of(*b),
+            };
+            values.push(Some(value));
+        }
+        roots
+            .iter()
+            .map(|root| values[root.index()].clone().unwrap())
+            .collect()
+    }
+
+    /// The total degree of the expression rooted at `root`. See `SymbolicField::degree`.
+    pub fn degree(&self, root: NodeId) -> usize {
+        let mut degrees: Vec<usize> = Vec::with_capacity(root.index() + 1);
+        for node in &self.nodes()[..=root.index()] {
+            let degree = match *node {
+                SymbolicNode::Variable(_) => 1,
+                SymbolicNode::Constant(_) => 0,
+                SymbolicNode::Add(a, b) | SymbolicNode::Sub(a, b) => {
+                    degrees[a.index()].max(degrees[b.index()])
+                }
+                SymbolicNode::Neg(a) => degrees[a.index()],
+                SymbolicNode::Mul(a, b) => degrees[a.index()].saturating_add(degrees[b.index()]),
+            };
+            degrees.push(degree);
+        }
+        degrees[root.index()]
+    }
+}
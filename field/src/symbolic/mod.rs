@@ -5,6 +5,12 @@ use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use crate::field::{AbstractField, Field};
 
+mod codegen;
+mod dag;
+mod eval;
+
+pub use dag::*;
+
 #[derive(Clone, Debug)]
 pub enum SymbolicField<F: Field, Var> {
     Variable(Var),
@@ -0,0 +1,122 @@
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, Field, FieldArray, PackedField};
+use rand::{thread_rng, Rng};
+
+type F = BabyBear;
+
+fn array<const N: usize>(values: [u32; N]) -> FieldArray<F, N> {
+    FieldArray(values.map(F::from_canonical_u32))
+}
+
+#[test]
+fn interleave_width_4() {
+    let a = array([0, 1, 2, 3]);
+    let b = array([4, 5, 6, 7]);
+
+    assert_eq!(
+        a.interleave(b, 1),
+        (array([0, 4, 2, 6]), array([1, 5, 3, 7]))
+    );
+    assert_eq!(
+        a.interleave(b, 2),
+        (array([0, 1, 4, 5]), array([2, 3, 6, 7]))
+    );
+    assert_eq!(a.interleave(b, 4), (a, b));
+}
+
+#[test]
+fn interleave_width_8() {
+    let a = array([0, 1, 2, 3, 4, 5, 6, 7]);
+    let b = array([8, 9, 10, 11, 12, 13, 14, 15]);
+
+    assert_eq!(
+        a.interleave(b, 1),
+        (
+            array([0, 8, 2, 10, 4, 12, 6, 14]),
+            array([1, 9, 3, 11, 5, 13, 7, 15])
+        )
+    );
+    assert_eq!(
+        a.interleave(b, 2),
+        (
+            array([0, 1, 8, 9, 4, 5, 12, 13]),
+            array([2, 3, 10, 11, 6, 7, 14, 15])
+        )
+    );
+    assert_eq!(
+        a.interleave(b, 4),
+        (
+            array([0, 1, 2, 3, 8, 9, 10, 11]),
+            array([4, 5, 6, 7, 12, 13, 14, 15])
+        )
+    );
+    assert_eq!(a.interleave(b, 8), (a, b));
+}
+
+#[test]
+fn interleave_is_an_involution() {
+    let mut rng = thread_rng();
+    let a = FieldArray::<F, 16>(rng.gen());
+    let b = FieldArray::<F, 16>(rng.gen());
+    for block_len in [1, 2, 4, 8, 16] {
+        let (c, d) = a.interleave(b, block_len);
+        assert_eq!(c.interleave(d, block_len), (a, b));
+    }
+}
+
+#[test]
+#[should_panic]
+fn interleave_rejects_bad_block_len() {
+    let a = array([0, 1, 2, 3]);
+    a.interleave(a, 3);
+}
+
+#[test]
+fn pack_slice_round_trip() {
+    let mut rng = thread_rng();
+    let mut values: Vec<F> = (0..18).map(|_| rng.gen()).collect();
+
+    let (packed, suffix) = FieldArray::<F, 4>::pack_slice_with_suffix(&values);
+    assert_eq!(packed.len(), 4);
+    assert_eq!(suffix, &values[16..]);
+    for (i, p) in packed.iter().enumerate() {
+        assert_eq!(p.as_slice(), &values[4 * i..4 * (i + 1)]);
+        assert_eq!(p, FieldArray::from_slice(&values[4 * i..4 * (i + 1)]));
+    }
+
+    let packed = FieldArray::<F, 4>::pack_slice_mut(&mut values[..16]);
+    packed[1] = FieldArray::from_fn(F::from_canonical_usize);
+    assert_eq!(&values[4..8], array([0, 1, 2, 3]).as_slice());
+}
+
+#[test]
+fn arithmetic_matches_scalar() {
+    let mut rng = thread_rng();
+    let a = FieldArray::<F, 8>(rng.gen());
+    let b = FieldArray::<F, 8>(rng.gen());
+    let s = F::generator().exp_u64(rng.gen());
+
+    for i in 0..8 {
+        let (x, y) = (a.0[i], b.0[i]);
+        assert_eq!((a + b).0[i], x + y);
+        assert_eq!((a - b).0[i], x - y);
+        assert_eq!((a * b).0[i], x * y);
+        assert_eq!((-a).0[i], -x);
+        assert_eq!((s + a).0[i], s + x);
+        assert_eq!((s - a).0[i], s - x);
+        assert_eq!((s * a).0[i], s * x);
+        assert_eq!((a / s).0[i], x / s);
+    }
+}
+
+#[test]
+fn baby_bear_packing_is_consistent() {
+    type P = <F as Field>::Packing;
+    let mut rng = thread_rng();
+    let values: Vec<F> = (0..2 * P::WIDTH).map(|_| rng.gen()).collect();
+    let packed = P::pack_slice(&values);
+    let product = packed[0] * packed[1];
+    for i in 0..P::WIDTH {
+        assert_eq!(product.as_slice()[i], values[i] * values[P::WIDTH + i]);
+    }
+}
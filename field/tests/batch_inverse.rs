@@ -0,0 +1,122 @@
+use p3_baby_bear::BabyBear;
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{
+    batch_multiplicative_inverse, batch_multiplicative_inverse_allowing_zero,
+    batch_multiplicative_inverse_packed, batch_multiplicative_inverse_packed_into, kernels,
+    par_batch_multiplicative_inverse, par_batch_multiplicative_inverse_allowing_zero,
+    try_batch_multiplicative_inverse, AbstractField, Field, FieldArray, PackedField,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::{thread_rng, Rng};
+
+type EF4 = BinomialExtensionField<BabyBear, 4>;
+
+fn test_packed_matches_scalar<F: Field>()
+where
+    Standard: Distribution<F>,
+{
+    let mut rng = thread_rng();
+    let len = 4 * <F as Field>::Packing::WIDTH;
+    for n in (0..3 * len).chain([1000, 1001]) {
+        let x: Vec<F> = (0..n)
+            .map(|_| loop {
+                let x = rng.gen::<F>();
+                if !x.is_zero() {
+                    break x;
+                }
+            })
+            .collect();
+        let expected = batch_multiplicative_inverse(&x);
+        assert_eq!(batch_multiplicative_inverse_packed(&x), expected, "n = {n}");
+        let mut out = vec![F::zero(); n];
+        batch_multiplicative_inverse_packed_into(&x, &mut out);
+        assert_eq!(out, expected, "n = {n}");
+        assert_eq!(par_batch_multiplicative_inverse(&x), expected, "n = {n}");
+        assert!(x.iter().zip(&expected).all(|(&a, &b)| (a * b).is_one()));
+    }
+}
+
+#[test]
+fn packed_matches_scalar_baby_bear() {
+    test_packed_matches_scalar::<BabyBear>();
+}
+
+#[test]
+fn packed_matches_scalar_quartic_extension() {
+    test_packed_matches_scalar::<EF4>();
+}
+
+/// The packed path with a fixed packing of width 4, whatever `F::Packing` is on the host.
+#[test]
+fn packed_matches_scalar_field_array() {
+    let mut rng = thread_rng();
+    for n in (0..48).chain([1000, 1001]) {
+        let x: Vec<BabyBear> = (0..n)
+            .map(|_| rng.gen_range(1..1 << 30))
+            .map(BabyBear::from_canonical_u32)
+            .collect();
+        assert_eq!(
+            kernels::batch_multiplicative_inverse_packed::<FieldArray<BabyBear, 4>>(&x),
+            batch_multiplicative_inverse(&x),
+            "n = {n}"
+        );
+    }
+}
+
+#[test]
+fn par_matches_scalar_many_chunks() {
+    let mut rng = thread_rng();
+    let x: Vec<BabyBear> = (0..(1 << 14) + 3)
+        .map(|_| rng.gen_range(1..1 << 30))
+        .map(BabyBear::from_canonical_u32)
+        .collect();
+    assert_eq!(
+        par_batch_multiplicative_inverse(&x),
+        batch_multiplicative_inverse(&x)
+    );
+}
+
+fn test_allowing_zero<F: Field>()
+where
+    Standard: Distribution<F>,
+{
+    let mut rng = thread_rng();
+    for n in [0, 1, 7, 100, 5000] {
+        let mut x: Vec<F> = (0..n).map(|_| rng.gen()).collect();
+        let mut zeros: Vec<usize> = (0..n).step_by(3).collect();
+        if n == 100 {
+            zeros.clear();
+        }
+        for &i in &zeros {
+            x[i] = F::zero();
+        }
+
+        let inv = batch_multiplicative_inverse_allowing_zero(&x);
+        assert_eq!(par_batch_multiplicative_inverse_allowing_zero(&x), inv);
+        for (a, b) in x.iter().zip(&inv) {
+            if a.is_zero() {
+                assert!(b.is_zero());
+            } else {
+                assert!((*a * *b).is_one());
+            }
+        }
+
+        match try_batch_multiplicative_inverse(&x) {
+            Ok(result) => {
+                assert!(zeros.is_empty());
+                assert_eq!(result, inv);
+            }
+            Err(indices) => assert_eq!(indices, zeros),
+        }
+    }
+}
+
+#[test]
+fn allowing_zero_baby_bear() {
+    test_allowing_zero::<BabyBear>();
+}
+
+#[test]
+fn allowing_zero_quartic_extension() {
+    test_allowing_zero::<EF4>();
+}
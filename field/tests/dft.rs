@@ -0,0 +1,142 @@
+use p3_baby_bear::BabyBear;
+use p3_field::dft::{Radix2Dif, Radix2Dit, TwoAdicSubgroupDft};
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{Field, TwoAdicField};
+use p3_util::{log2_strict_usize, reverse_matrix_index_bits};
+use rand::distributions::{Distribution, Standard};
+use rand::{thread_rng, Rng};
+
+type EF4 = BinomialExtensionField<BabyBear, 4>;
+
+/// Evaluate every column over `shift * H` by Horner's rule.
+fn naive_coset_dft<F: TwoAdicField>(values: &[F], width: usize, shift: F) -> Vec<F> {
+    let height = values.len() / width;
+    let g = F::two_adic_generator(log2_strict_usize(height));
+    let mut out = vec![F::zero(); values.len()];
+    for (k, x) in g.shifted_powers(shift).take(height).enumerate() {
+        for col in 0..width {
+            out[k * width + col] = (0..height)
+                .rev()
+                .fold(F::zero(), |acc, i| acc * x + values[i * width + col]);
+        }
+    }
+    out
+}
+
+fn random_values<F: Field>(len: usize) -> Vec<F>
+where
+    Standard: Distribution<F>,
+{
+    thread_rng().sample_iter(Standard).take(len).collect()
+}
+
+fn test_dft_matches_naive<F: TwoAdicField, Dft: TwoAdicSubgroupDft<F>>()
+where
+    Standard: Distribution<F>,
+{
+    let dft = Dft::default();
+    for log_height in 0..7 {
+        for width in [1, 3, 8] {
+            let values = random_values::<F>(width << log_height);
+            let expected = naive_coset_dft(&values, width, F::one());
+
+            let mut natural = values.clone();
+            dft.dft_batch(&mut natural, width);
+            assert_eq!(natural, expected);
+
+            let mut bit_reversed = values.clone();
+            dft.dft_batch_bit_reversed(&mut bit_reversed, width);
+            reverse_matrix_index_bits(&mut bit_reversed, width);
+            assert_eq!(bit_reversed, expected);
+        }
+    }
+}
+
+fn test_idft_inverts_dft<F: TwoAdicField, Dft: TwoAdicSubgroupDft<F>>()
+where
+    Standard: Distribution<F>,
+{
+    let dft = Dft::default();
+    for log_height in 0..7 {
+        let width = 5;
+        let values = random_values::<F>(width << log_height);
+
+        let mut evals = values.clone();
+        dft.dft_batch(&mut evals, width);
+        dft.idft_batch(&mut evals, width);
+        assert_eq!(evals, values);
+
+        let mut evals = values.clone();
+        dft.dft_batch_bit_reversed(&mut evals, width);
+        dft.idft_batch_bit_reversed(&mut evals, width);
+        assert_eq!(evals, values);
+    }
+}
+
+fn test_coset_dft<F: TwoAdicField, Dft: TwoAdicSubgroupDft<F>>()
+where
+    Standard: Distribution<F>,
+{
+    let dft = Dft::default();
+    let shift = F::generator();
+    let width = 2;
+    let values = random_values::<F>(width << 5);
+
+    let mut evals = values.clone();
+    dft.coset_dft_batch(&mut evals, width, shift);
+    assert_eq!(evals, naive_coset_dft(&values, width, shift));
+
+    dft.coset_idft_batch(&mut evals, width, shift);
+    assert_eq!(evals, values);
+}
+
+fn test_coset_lde<F: TwoAdicField, Dft: TwoAdicSubgroupDft<F>>()
+where
+    Standard: Distribution<F>,
+{
+    let dft = Dft::default();
+    let shift = F::generator();
+    let width = 3;
+    let coeffs = random_values::<F>(width << 4);
+
+    let mut evals = coeffs.clone();
+    dft.dft_batch(&mut evals, width);
+    let lde = dft.coset_lde_batch(evals, width, 2, shift);
+
+    let mut padded = coeffs;
+    padded.resize(padded.len() << 2, F::zero());
+    assert_eq!(lde, naive_coset_dft(&padded, width, shift));
+}
+
+macro_rules! dft_tests {
+    ($name:ident, $field:ty, $dft:ty) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn dft_matches_naive() {
+                test_dft_matches_naive::<$field, $dft>();
+            }
+
+            #[test]
+            fn idft_inverts_dft() {
+                test_idft_inverts_dft::<$field, $dft>();
+            }
+
+            #[test]
+            fn coset_dft() {
+                test_coset_dft::<$field, $dft>();
+            }
+
+            #[test]
+            fn coset_lde() {
+                test_coset_lde::<$field, $dft>();
+            }
+        }
+    };
+}
+
+dft_tests!(baby_bear_dit, BabyBear, Radix2Dit);
+dft_tests!(baby_bear_dif, BabyBear, Radix2Dif);
+dft_tests!(quartic_extension_dit, EF4, Radix2Dit);
+dft_tests!(quartic_extension_dif, EF4, Radix2Dif);
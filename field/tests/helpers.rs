@@ -0,0 +1,90 @@
+use p3_baby_bear::BabyBear;
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{
+    add_linear_combination_in_place, add_scaled_slice_in_place, add_scaled_slice_in_place_packed,
+    add_slices_in_place, dot_product_slices, mul_slices_in_place, scale_slice_in_place,
+    AbstractField, Field,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::{thread_rng, Rng};
+
+type EF4 = BinomialExtensionField<BabyBear, 4>;
+
+fn rand_vec<F: Field>(n: usize) -> Vec<F>
+where
+    Standard: Distribution<F>,
+{
+    let mut rng = thread_rng();
+    (0..n).map(|_| rng.gen()).collect()
+}
+
+fn test_slice_kernels<F: Field>()
+where
+    Standard: Distribution<F>,
+{
+    let mut rng = thread_rng();
+    // Lengths around multiples of every packing width in use, so both the packed body and the
+    // scalar tail are exercised.
+    for n in (0..20).chain([63, 64, 65, 1000]) {
+        let x = rand_vec::<F>(n);
+        let y = rand_vec::<F>(n);
+        let s = rng.gen::<F>();
+
+        let mut sum = x.clone();
+        add_slices_in_place(&mut sum, &y);
+        assert!(sum.iter().zip(&x).zip(&y).all(|((&r, &a), &b)| r == a + b));
+
+        let mut scaled = x.clone();
+        scale_slice_in_place(s, &mut scaled);
+        assert!(scaled.iter().zip(&x).all(|(&r, &a)| r == a * s));
+
+        let mut axpy = x.clone();
+        add_scaled_slice_in_place_packed(&mut axpy, &y, s);
+        assert!(axpy
+            .iter()
+            .zip(&x)
+            .zip(&y)
+            .all(|((&r, &a), &b)| r == a + b * s));
+        let mut axpy_iter = x.clone();
+        add_scaled_slice_in_place(&mut axpy_iter, y.iter().copied(), s);
+        assert_eq!(axpy_iter, axpy);
+
+        let mut prod = x.clone();
+        mul_slices_in_place(&mut prod, &y);
+        assert!(prod.iter().zip(&x).zip(&y).all(|((&r, &a), &b)| r == a * b));
+
+        let expected_dot: F = x.iter().zip(&y).map(|(&a, &b)| a * b).sum();
+        assert_eq!(dot_product_slices(&x, &y), expected_dot);
+
+        let ys: Vec<Vec<F>> = (0..5).map(|_| rand_vec(n)).collect();
+        let ys_refs: Vec<&[F]> = ys.iter().map(|y| y.as_slice()).collect();
+        let weights = rand_vec::<F>(5);
+        let mut combo = x.clone();
+        add_linear_combination_in_place(&mut combo, &ys_refs, &weights);
+        let mut expected = x.clone();
+        for (y, &w) in ys.iter().zip(&weights) {
+            for (e, &y_i) in expected.iter_mut().zip(y) {
+                *e += y_i * w;
+            }
+        }
+        assert_eq!(combo, expected);
+    }
+}
+
+#[test]
+fn slice_kernels_baby_bear() {
+    test_slice_kernels::<BabyBear>();
+}
+
+#[test]
+fn slice_kernels_quartic_extension() {
+    test_slice_kernels::<EF4>();
+}
+
+#[test]
+#[should_panic]
+fn add_scaled_slice_length_mismatch() {
+    let mut x = rand_vec::<BabyBear>(8);
+    let y = rand_vec::<BabyBear>(7);
+    add_scaled_slice_in_place_packed(&mut x, &y, BabyBear::two());
+}
@@ -0,0 +1,118 @@
+use p3_baby_bear::BabyBear;
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{
+    two_adic_coset_zerofier, AbstractExtensionField, AbstractField, Field, Polynomial, TwoAdicField,
+};
+use rand::distributions::Standard;
+use rand::{thread_rng, Rng};
+
+type F = BabyBear;
+type EF4 = BinomialExtensionField<BabyBear, 4>;
+
+fn random_poly(num_coeffs: usize) -> Polynomial<F> {
+    Polynomial::new(
+        thread_rng()
+            .sample_iter(Standard)
+            .take(num_coeffs)
+            .collect(),
+    )
+}
+
+#[test]
+fn arithmetic_agrees_with_evaluation() {
+    let mut rng = thread_rng();
+    let a = random_poly(17);
+    let b = random_poly(9);
+    let x = rng.gen::<F>();
+
+    assert_eq!((&a + &b).eval(x), a.eval(x) + b.eval(x));
+    assert_eq!((&a - &b).eval(x), a.eval(x) - b.eval(x));
+    assert_eq!((&a * &b).eval(x), a.eval(x) * b.eval(x));
+    assert_eq!((-a.clone()).eval(x), -a.eval(x));
+    assert_eq!((&a * &b).degree(), Some(24));
+    assert!((&a - &a).is_zero());
+}
+
+#[test]
+fn eval_ext_agrees_with_base() {
+    let a = random_poly(12);
+    let x = thread_rng().gen::<F>();
+    assert_eq!(a.eval_ext(EF4::from_base(x)), EF4::from_base(a.eval(x)));
+
+    let y = thread_rng().gen::<EF4>();
+    let expected = a
+        .coeffs()
+        .iter()
+        .zip(y.powers())
+        .map(|(&c, y_i)| y_i * c)
+        .sum::<EF4>();
+    assert_eq!(a.eval_ext(y), expected);
+}
+
+#[test]
+fn div_rem() {
+    let a = random_poly(30);
+    let b = random_poly(7);
+    let (q, r) = a.div_rem(&b);
+    assert!(r.degree() < b.degree());
+    assert_eq!(&(&q * &b) + &r, a);
+
+    let (q, r) = b.div_rem(&a);
+    assert!(q.is_zero());
+    assert_eq!(r, b);
+}
+
+#[test]
+fn div_rem_binomial_matches_div_rem() {
+    let a = random_poly(40);
+    let c = thread_rng().gen::<F>();
+    for n in [1, 5, 16, 39, 40, 41] {
+        assert_eq!(
+            a.div_rem_binomial(n, c),
+            a.div_rem(&Polynomial::binomial(n, c))
+        );
+    }
+}
+
+#[test]
+fn coset_zerofier_division() {
+    let log_n = 4;
+    let shift = F::generator();
+    let zerofier = Polynomial::two_adic_coset_zerofier(log_n, shift);
+
+    let x = thread_rng().gen::<F>();
+    assert_eq!(zerofier.eval(x), two_adic_coset_zerofier(log_n, shift, x));
+
+    // A multiple of the zerofier divides exactly.
+    let q = random_poly(20);
+    let (quotient, rem) = (&q * &zerofier).div_rem_two_adic_coset_zerofier(log_n, shift);
+    assert_eq!(quotient, q);
+    assert!(rem.is_zero());
+
+    // The zerofier vanishes on the coset.
+    assert!(zerofier
+        .evaluate_coset(5, shift)
+        .iter()
+        .step_by(2)
+        .all(Field::is_zero));
+}
+
+#[test]
+fn interpolation() {
+    let a = random_poly(16);
+    let points: Vec<(F, F)> = (0..16)
+        .map(|i| {
+            let x = F::from_canonical_usize(i * i + 3);
+            (x, a.eval(x))
+        })
+        .collect();
+    assert_eq!(Polynomial::interpolate(&points), a);
+
+    let shift = F::generator();
+    let evals = a.evaluate_coset(4, shift);
+    let g = F::two_adic_generator(4);
+    for (x, &y) in g.shifted_powers(shift).zip(&evals) {
+        assert_eq!(a.eval(x), y);
+    }
+    assert_eq!(Polynomial::interpolate_coset(&evals, shift), a);
+}
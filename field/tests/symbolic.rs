@@ -0,0 +1,335 @@
+use p3_baby_bear::BabyBear;
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{
+    AbstractExtensionField, AbstractField, Field, FieldArray, PackedField, SymbolicDag,
+    SymbolicField, SymbolicNode,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+type S = SymbolicField<BabyBear, usize>;
+type EF4 = BinomialExtensionField<BabyBear, 4>;
+
+const NUM_VARS: usize = 4;
+
+fn var(i: usize) -> S {
+    S::Variable(i)
+}
+
+fn as_constant(e: &S) -> Option<BabyBear> {
+    match e {
+        S::Constant(c) => Some(*c),
+        _ => None,
+    }
+}
+
+#[test]
+fn identities() {
+    let x = var(0);
+    let y = var(1);
+
+    assert_eq!(
+        as_constant(&(x.clone() * S::zero()).simplify()),
+        Some(BabyBear::zero())
+    );
+    assert!(matches!((x.clone() + S::zero()).simplify(), S::Variable(0)));
+    assert!(matches!((S::one() * x.clone()).simplify(), S::Variable(0)));
+    assert!(matches!((-(-x.clone())).simplify(), S::Variable(0)));
+    assert!(matches!((x.clone() - S::zero()).simplify(), S::Variable(0)));
+    assert_eq!(
+        as_constant(&(x.clone() - x.clone()).simplify()),
+        Some(BabyBear::zero())
+    );
+
+    // Commutative operands are ordered canonically, so these cancel.
+    let e = x.clone() * y.clone() - y.clone() * x.clone();
+    assert_eq!(as_constant(&e.simplify()), Some(BabyBear::zero()));
+    let e = (x.clone() + y.clone()) - (y + x);
+    assert_eq!(as_constant(&e.simplify()), Some(BabyBear::zero()));
+}
+
+#[test]
+fn constant_folding() {
+    let x = var(0);
+    let two = S::two();
+    let three = S::from_canonical_u32(3);
+
+    assert_eq!(
+        as_constant(&(two.clone() * three.clone() - S::one()).simplify()),
+        Some(BabyBear::from_canonical_u32(5))
+    );
+
+    // Constants are collected through nested products and sums.
+    let e = (two.clone() * (three.clone() * x.clone())).simplify();
+    match e {
+        S::Mul(c, v) => {
+            assert_eq!(as_constant(&c), Some(BabyBear::from_canonical_u32(6)));
+            assert!(matches!(*v, S::Variable(0)));
+        }
+        _ => panic!("expected 6 * x, got {e:?}"),
+    }
+    let e = ((x + two) + three).simplify();
+    match e {
+        S::Add(c, v) => {
+            assert_eq!(as_constant(&c), Some(BabyBear::from_canonical_u32(5)));
+            assert!(matches!(*v, S::Variable(0)));
+        }
+        _ => panic!("expected 5 + x, got {e:?}"),
+    }
+}
+
+#[test]
+fn common_subexpressions_are_shared() {
+    // Two structurally equal but separately allocated copies of `x * y + z`.
+    let make = || var(0) * var(1) + var(2);
+    let e = make() * make();
+
+    let stats = e.stats();
+    assert_eq!(stats.tree_nodes, 11);
+    assert_eq!(stats.allocated_nodes, 11);
+    assert_eq!(stats.unique_nodes, 6);
+
+    let simplified = e.simplify();
+    let stats = simplified.stats();
+    assert_eq!(stats.tree_nodes, 11);
+    assert_eq!(stats.allocated_nodes, 6);
+    assert_eq!(stats.unique_nodes, 6);
+}
+
+#[test]
+fn dag_hash_conses() {
+    let mut dag = SymbolicDag::<BabyBear, usize>::new();
+    let x = dag.variable(0);
+    let y = dag.variable(1);
+    let xy = dag.mul(x, y);
+    let yx = dag.mul(y, x);
+    assert_eq!(xy, yx);
+    assert_eq!(dag.len(), 3);
+    assert!(matches!(dag.node(xy), SymbolicNode::Mul(a, b) if (*a, *b) == (x, y)));
+
+    let sum = dag.add(xy, x);
+    assert_eq!(dag.reachable_count(sum), 4);
+    assert_eq!(dag.reachable_count(y), 1);
+}
+
+#[test]
+fn repeated_squaring_stays_small() {
+    let mut e = var(0) + S::one();
+    for _ in 0..100 {
+        e = e.clone() * e;
+    }
+    let stats = e.stats();
+    assert_eq!(stats.tree_nodes, usize::MAX);
+    // `e.clone() * e` allocates each operand separately, so memory doubles the unique count.
+    assert_eq!(stats.allocated_nodes, 203);
+    assert_eq!(stats.unique_nodes, 103);
+    assert_eq!(e.simplify().stats().allocated_nodes, 103);
+}
+
+#[test]
+fn deep_expressions_do_not_overflow_the_stack() {
+    let e: S = (0..10_000).map(|i| var(i % 7) * S::zero() + var(i)).sum();
+    let stats = e.stats();
+    assert_eq!(stats.tree_nodes, 10_000 * 6 - 1);
+
+    let simplified = e.simplify();
+    // Each term reduces to its variable, leaving 10_000 leaves and 9_999 additions.
+    assert_eq!(simplified.stats().unique_nodes, 2 * 10_000 - 1);
+}
+
+/// A random expression over `NUM_VARS` variables, built with the `AbstractField` operators, so
+/// the same code can be run directly on field elements to get the expected value.
+fn random_expr<AF: AbstractField<F = BabyBear>>(rng: &mut StdRng, vars: &[AF], depth: usize) -> AF {
+    if depth == 0 || rng.gen_ratio(1, 4) {
+        return if rng.gen() {
+            vars[rng.gen_range(0..vars.len())].clone()
+        } else {
+            // Small constants, including 0, 1 and -1, so that simplification kicks in.
+            AF::from_f(BabyBear::from_canonical_u32(rng.gen_range(0..3)) - BabyBear::one())
+        };
+    }
+    let a = random_expr(rng, vars, depth - 1);
+    let b = random_expr(rng, vars, depth - 1);
+    match rng.gen_range(0..5) {
+        0 => a + b,
+        1 => a - b,
+        2 => -a,
+        3 => a.square(),
+        _ => a * b,
+    }
+}
+
+#[test]
+fn eval_matches_direct_computation() {
+    let symbolic_vars: Vec<S> = (0..NUM_VARS).map(var).collect();
+    for seed in 0..50 {
+        let expr = random_expr(&mut StdRng::seed_from_u64(seed), &symbolic_vars, 6);
+        let simplified = expr.simplify();
+
+        let mut rng = StdRng::seed_from_u64(1000 + seed);
+        let values: Vec<BabyBear> = (0..NUM_VARS).map(|_| rng.gen()).collect();
+        let expected = random_expr(&mut StdRng::seed_from_u64(seed), &values, 6);
+        assert_eq!(expr.eval(|&v| values[v]), expected);
+        assert_eq!(simplified.eval(|&v| values[v]), expected);
+
+        let mut dag = SymbolicDag::new();
+        let root = dag.insert(&expr);
+        assert_eq!(dag.eval(&[root], |&v| values[v]), vec![expected]);
+        assert_eq!(dag.degree(root), simplified.degree());
+        assert!(simplified.degree() <= expr.degree());
+    }
+}
+
+#[test]
+fn eval_into_packed_array_and_extension() {
+    type P = <BabyBear as Field>::Packing;
+    let symbolic_vars: Vec<S> = (0..NUM_VARS).map(var).collect();
+    let mut rng = StdRng::seed_from_u64(0);
+    let expr = random_expr(&mut rng, &symbolic_vars, 6);
+
+    // Several rows of variable assignments, one per lane of the wider of the two packings.
+    let rows: Vec<Vec<BabyBear>> = (0..P::WIDTH.max(8))
+        .map(|_| (0..NUM_VARS).map(|_| rng.gen()).collect())
+        .collect();
+    let expected: Vec<BabyBear> = rows.iter().map(|row| expr.eval(|&v| row[v])).collect();
+
+    let packed = expr.eval(|&v| P::from_fn(|lane| rows[lane][v]));
+    assert_eq!(packed.as_slice(), &expected[..P::WIDTH]);
+
+    let array: FieldArray<BabyBear, 8> =
+        expr.eval(|&v| FieldArray(core::array::from_fn(|i| rows[i][v])));
+    assert_eq!(array.0, expected[..8]);
+
+    // Base field assignments embedded in the extension give the embedded base field result.
+    let ext: EF4 = expr.eval_ext(|&v| EF4::from_base(rows[0][v]));
+    assert_eq!(ext, EF4::from_base(expected[0]));
+
+    // Genuine extension assignments agree with the DAG evaluator.
+    let ext_values: Vec<EF4> = (0..NUM_VARS).map(|_| rng.gen()).collect();
+    let mut dag = SymbolicDag::new();
+    let root = dag.insert(&expr);
+    assert_eq!(
+        dag.eval_ext(&[root], |&v| ext_values[v]),
+        vec![expr.eval_ext(|&v| ext_values[v])]
+    );
+}
+
+#[test]
+fn dag_eval_multiple_roots() {
+    let x = var(0);
+    let y = var(1);
+    let shared = x.clone() * y.clone();
+    let mut dag = SymbolicDag::new();
+    let roots = [
+        dag.insert(&(shared.clone() + S::one())),
+        dag.insert(&(shared.clone() * x)),
+        dag.insert(&(y - S::two())),
+    ];
+    let values = [
+        BabyBear::from_canonical_u32(3),
+        BabyBear::from_canonical_u32(5),
+    ];
+    assert_eq!(
+        dag.eval(&roots, |&v| values[v]),
+        [16, 45, 3].map(BabyBear::from_canonical_u32).to_vec()
+    );
+    assert!(dag.eval::<BabyBear>(&[], |&v| values[v]).is_empty());
+}
+
+#[test]
+fn degree() {
+    let x = var(0);
+    let y = var(1);
+    let z = var(2);
+
+    assert_eq!(S::from_canonical_u32(7).degree(), 0);
+    assert_eq!(x.degree(), 1);
+    assert_eq!((x.clone() * y.clone() + z.clone()).degree(), 2);
+    assert_eq!((x.clone() * y.clone() * x.clone() - S::two()).degree(), 3);
+    assert_eq!(x.clone().exp_u64(5).degree(), 5);
+    assert_eq!((-(x.clone() * z) * S::from_canonical_u32(3)).degree(), 2);
+
+    // Cancellation is only visible after simplification.
+    let cancelling = x.clone() * y.clone() - y * x.clone();
+    assert_eq!(cancelling.degree(), 2);
+    assert_eq!(cancelling.simplify().degree(), 0);
+    assert_eq!((x * S::zero()).simplify().degree(), 0);
+}
+
+/// The expression used to test code generation: `(x + 1)^7 * (y + 1) - 3y - y^2 - 2`, written
+/// with redundancy that simplification and CSE should remove.
+fn codegen_example() -> S {
+    let x = var(0);
+    let y = var(1);
+    let s7 = (x + S::one()).exp_const_u64::<7>();
+    s7.clone() * y.clone() + s7 - y.clone() * S::from_canonical_u32(3)
+        + (y.clone() * y) * S::neg_one()
+        - S::two()
+}
+
+const CODEGEN_EXPECTED: &str = "\
+pub fn constraint<AF: p3_field::AbstractField>(vars: &[AF]) -> [AF; 1] {
+    let t0 = AF::square(&vars[1]);
+    let t1 = AF::from_canonical_u64(3) * vars[1].clone();
+    let t2 = AF::one() + vars[0].clone();
+    let t3 = AF::exp_const_u64::<7>(&t2);
+    let t4 = vars[1].clone() * t3.clone();
+    let t5 = t3 + t4;
+    let t6 = t5 - t1;
+    let t7 = t6 - t0;
+    let t8 = -AF::two() + t7;
+    [t8]
+}
+";
+
+// `CODEGEN_EXPECTED`, compiled.
+pub fn constraint<AF: p3_field::AbstractField>(vars: &[AF]) -> [AF; 1] {
+    let t0 = AF::square(&vars[1]);
+    let t1 = AF::from_canonical_u64(3) * vars[1].clone();
+    let t2 = AF::one() + vars[0].clone();
+    let t3 = AF::exp_const_u64::<7>(&t2);
+    let t4 = vars[1].clone() * t3.clone();
+    let t5 = t3 + t4;
+    let t6 = t5 - t1;
+    let t7 = t6 - t0;
+    let t8 = -AF::two() + t7;
+    [t8]
+}
+
+#[test]
+fn codegen() {
+    let expr = codegen_example();
+    assert_eq!(expr.to_rust("constraint", |&v| v), CODEGEN_EXPECTED);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let values: [BabyBear; 2] = rng.gen();
+        assert_eq!(constraint(&values), [expr.eval(|&v| values[v])]);
+    }
+}
+
+#[test]
+fn codegen_multiple_roots() {
+    let x = var(0);
+    let y = var(1);
+    let shared = x.clone() * y.clone();
+    let mut dag = SymbolicDag::new();
+    let roots = [
+        dag.insert(&(shared.clone() + S::one())),
+        dag.insert(&(shared * x.clone())),
+        dag.insert(&x),
+    ];
+    // Variables are numbered by `var_index`, here in reverse.
+    let code = dag.to_rust("f", &roots, |&v| 1 - v);
+    assert_eq!(
+        code,
+        "\
+pub fn f<AF: p3_field::AbstractField>(vars: &[AF]) -> [AF; 3] {
+    let t0 = vars[0].clone() * vars[1].clone();
+    let t1 = AF::one() + t0.clone();
+    let t2 = vars[1].clone() * t0;
+    [t1, t2, vars[1].clone()]
+}
+"
+    );
+}
@@ -0,0 +1,85 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use p3_baby_bear::{BabyBear, BabyBearCanonical};
+use p3_challenger::DuplexChallenger;
+use p3_field::dft::{Radix2Dit, TwoAdicSubgroupDft};
+use p3_field::extension::HasTwoAdicBionmialExtension;
+use p3_field::{AbstractField, PrimeField64, TwoAdicField};
+use p3_fri::{fold_even_odd, prove, verify, Ext4, FriConfig};
+use p3_merkle_tree::MerkleTreeMmcs;
+use p3_poseidon2::poseidon2_baby_bear_16_generic;
+use p3_symmetric::{PaddedSponge, TruncatedPermutation};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+const LOG_FOLD_LEN: usize = 20;
+const LOG_DEGREE: usize = 15;
+const LOG_BLOWUP: usize = 1;
+
+fn bench_field<F>(c: &mut Criterion, name: &str)
+where
+    F: TwoAdicField + PrimeField64 + HasTwoAdicBionmialExtension<4> + From<BabyBear>,
+    Standard: Distribution<Ext4<F>>,
+{
+    let mut rng = rand::thread_rng();
+
+    let codeword: Vec<Ext4<F>> = (0..1 << LOG_FOLD_LEN).map(|_| rng.gen()).collect();
+    let beta: Ext4<F> = rng.gen();
+    c.bench_function(&format!("{name} fold 2^{LOG_FOLD_LEN}"), |b| {
+        b.iter(|| fold_even_odd(black_box(&codeword), black_box(beta)))
+    });
+
+    let perm = poseidon2_baby_bear_16_generic::<F>();
+    let config = FriConfig {
+        log_blowup: LOG_BLOWUP,
+        log_arity: 3,
+        num_queries: 100,
+        log_final_poly_len: 3,
+        proof_of_work_bits: 16,
+        mmcs: MerkleTreeMmcs::<_, _, 8>::new(
+            PaddedSponge::<_, 16, 8, 8>::new(perm.clone()),
+            TruncatedPermutation::<_, 2, 8, 16>::new(perm.clone()),
+        ),
+    };
+    let challenger = DuplexChallenger::<F, _, 16, 8>::new(perm);
+
+    let mut codeword: Vec<Ext4<F>> = (0..1 << LOG_DEGREE).map(|_| rng.gen()).collect();
+    codeword.resize(1 << (LOG_DEGREE + LOG_BLOWUP), Ext4::zero());
+    Radix2Dit.dft_bit_reversed(&mut codeword);
+
+    let mut group = c.benchmark_group("fri");
+    group.sample_size(10);
+    let log_n = LOG_DEGREE + LOG_BLOWUP;
+    group.bench_function(format!("{name} prove 2^{log_n}"), |b| {
+        b.iter_batched(
+            || (codeword.clone(), challenger.clone()),
+            |(codeword, mut challenger)| prove(&config, codeword, &mut challenger),
+            BatchSize::LargeInput,
+        )
+    });
+    let proof = prove(&config, codeword, &mut challenger.clone());
+    group.bench_function(format!("{name} verify 2^{log_n}"), |b| {
+        b.iter(|| verify(&config, log_n, &proof, &mut challenger.clone()).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_montgomery(c: &mut Criterion) {
+    bench_field::<BabyBear>(c, "BabyBear");
+}
+
+fn bench_canonical(c: &mut Criterion) {
+    // `BabyBearCanonical` only has a SIMD packing with AVX2 and without AVX-512.
+    let name = if cfg!(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(target_feature = "avx512f")
+    )) {
+        "BabyBearCanonical (AVX2 packing)"
+    } else {
+        "BabyBearCanonical (FieldArray packing)"
+    };
+    bench_field::<BabyBearCanonical>(c, name);
+}
+
+criterion_group!(fri, bench_montgomery, bench_canonical);
+criterion_main!(fri);
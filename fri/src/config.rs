@@ -0,0 +1,50 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use p3_merkle_tree::MerkleTreeMmcs;
+
+#[derive(Clone, Debug)]
+pub struct FriConfig<H, C, const DIGEST_ELEMS: usize> {
+    /// `log2` of the ratio of the codeword length to the degree bound.
+    pub log_blowup: usize,
+    /// Each round folds the codeword by `2^log_arity`, except perhaps the last, which only folds
+    /// by what is left before the final polynomial.
+    pub log_arity: usize,
+    pub num_queries: usize,
+    /// The final polynomial has degree less than `2^log_final_poly_len`.
+    pub log_final_poly_len: usize,
+    pub proof_of_work_bits: usize,
+    pub mmcs: MerkleTreeMmcs<H, C, DIGEST_ELEMS>,
+}
+
+impl<H, C, const DIGEST_ELEMS: usize> FriConfig<H, C, DIGEST_ELEMS> {
+    /// `log2` of the length of the final codeword, which is the final polynomial evaluated over a
+    /// subgroup `2^log_blowup` times its degree bound.
+    pub fn log_final_len(&self) -> usize {
+        self.log_final_poly_len + self.log_blowup
+    }
+
+    /// `log2` of the arity of each round, for a codeword of length `2^log_n`.
+    ///
+    /// # Panics
+    /// Panics if the codeword is no longer than the final codeword, or if `log_arity` is zero.
+    pub fn fold_schedule(&self, log_n: usize) -> Vec<usize> {
+        assert!(self.log_arity > 0, "each round must fold");
+        assert!(
+            log_n > self.log_final_len(),
+            "the codeword must be longer than the final codeword"
+        );
+        let to_fold = log_n - self.log_final_len();
+        let mut schedule = vec![self.log_arity; to_fold / self.log_arity];
+        if !to_fold.is_multiple_of(self.log_arity) {
+            schedule.push(to_fold % self.log_arity);
+        }
+        schedule
+    }
+
+    /// The conjectured security of the test, in bits: each query catches a codeword far from low
+    /// degree with probability `1 - 2^-log_blowup`, and grinding adds `proof_of_work_bits`.
+    pub fn conjectured_soundness_bits(&self) -> usize {
+        self.num_queries * self.log_blowup + self.proof_of_work_bits
+    }
+}
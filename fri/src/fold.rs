@@ -0,0 +1,206 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use p3_field::extension::{BinomialExtensionField, HasTwoAdicBionmialExtension};
+use p3_field::{AbstractExtensionField, AbstractField, Field, PackedField, TwoAdicField};
+use p3_maybe_rayon::prelude::*;
+use p3_util::{reverse_bits_len, reverse_slice_index_bits};
+
+use crate::Ext4;
+
+/// Fold a codeword in bit-reversed order by `2^log_arity`, as `log_arity` folds by two with the
+/// challenges `beta, beta^2, beta^4, ...`.
+///
+/// Writing `f(x) = \sum_{i < 2^log_arity} x^i f_i(x^{2^log_arity})`, the result is the evaluation
+/// of `\sum_i beta^i f_i` over the subgroup of `2^log_arity`-th powers, again in bit-reversed
+/// order.
+pub fn fold<F>(mut codeword: Vec<Ext4<F>>, log_arity: usize, mut beta: Ext4<F>) -> Vec<Ext4<F>>
+where
+    F: TwoAdicField + HasTwoAdicBionmialExtension<4>,
+{
+    for _ in 0..log_arity {
+        codeword = fold_even_odd(&codeword, beta);
+        beta = beta.square();
+    }
+    codeword
+}
+
+/// Fold a codeword in bit-reversed order by two: with `f(x) = f_e(x^2) + x f_o(x^2)`, the result is
+/// `f_e + beta f_o` over the squares.
+///
+/// In bit-reversed order, `f(x)` and `f(-x)` are adjacent, and `f_e(x^2)` and `f_o(x^2)` are
+/// `(f(x) + f(-x)) / 2` and `(f(x) - f(-x)) / 2x`. The pairs are folded `F::Packing::WIDTH` at a
+/// time, with the extension elements transposed into `BinomialExtensionField<F::Packing, 4>`.
+pub fn fold_even_odd<F>(codeword: &[Ext4<F>], beta: Ext4<F>) -> Vec<Ext4<F>>
+where
+    F: TwoAdicField + HasTwoAdicBionmialExtension<4>,
+{
+    let log_n = p3_util::log2_strict_usize(codeword.len());
+    assert!(log_n > 0, "cannot fold a codeword of length 1");
+    let half_n = codeword.len() / 2;
+
+    // `1 / 2x` for the `x` of each pair, which is `g^rev(j)` for pair `j`.
+    let g_inv = F::two_adic_generator(log_n).inverse();
+    let mut inv_two_x: Vec<F> = g_inv
+        .shifted_powers(F::two().inverse())
+        .take(half_n)
+        .collect();
+    reverse_slice_index_bits(&mut inv_two_x);
+
+    let width = F::Packing::WIDTH;
+    let mut folded = vec![Ext4::<F>::zero(); half_n];
+    let packed_len = half_n - half_n % width;
+    let (packed, rest) = folded.split_at_mut(packed_len);
+
+    let half = F::two().inverse();
+    let packed_beta = pack_ext(|_| beta);
+    packed
+        .par_chunks_exact_mut(width)
+        .zip(inv_two_x.par_chunks_exact(width))
+        .enumerate()
+        .for_each(|(i, (out, inv_two_x))| {
+            let lo = pack_ext(|lane| codeword[2 * (i * width + lane)]);
+            let hi = pack_ext(|lane| codeword[2 * (i * width + lane) + 1]);
+            let result = fold_pair(
+                lo,
+                hi,
+                packed_beta,
+                F::Packing::from(half),
+                *F::Packing::from_slice(inv_two_x),
+            );
+            let coeffs = AbstractExtensionField::<F::Packing>::as_base_slice(&result);
+            for (lane, out) in out.iter_mut().enumerate() {
+                let lane_coeffs: [F; 4] = core::array::from_fn(|c| coeffs[c].as_slice()[lane]);
+                *out = Ext4::from_base_slice(&lane_coeffs);
+            }
+        });
+    for (j, out) in (packed_len..).zip(rest) {
+        *out = fold_pair(
+            codeword[2 * j],
+            codeword[2 * j + 1],
+            beta,
+            half,
+            inv_two_x[j],
+        );
+    }
+    folded
+}
+
+/// Fold one row of `2^log_arity` adjacent elements, row `row_index` of a codeword of length
+/// `2^log_n` in bit-reversed order, into the element at `row_index` of the folded codeword.
+///
+/// This is what `fold` computes for a single row, evaluated the slow way for the verifier.
+pub fn fold_row<F>(row: &[Ext4<F>], row_index: usize, log_n: usize, mut beta: Ext4<F>) -> Ext4<F>
+where
+    F: TwoAdicField + HasTwoAdicBionmialExtension<4>,
+{
+    let log_arity = p3_util::log2_strict_usize(row.len());
+    let half = F::two().inverse();
+    let mut values = row.to_vec();
+    for round in 0..log_arity {
+        let log_len = log_n - round;
+        let g = F::two_adic_generator(log_len);
+        let first_pair = row_index << (log_arity - round - 1);
+        values = values
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(j, pair)| {
+                let rev = reverse_bits_len(first_pair + j, log_len - 1);
+                let inv_two_x = (g.exp_u64(rev as u64) * F::two()).inverse();
+                fold_pair(pair[0], pair[1], beta, half, inv_two_x)
+            })
+            .collect();
+        beta = beta.square();
+    }
+    values[0]
+}
+
+/// `(lo + hi) / 2 + beta (lo - hi) / 2x`, given `f(x) = lo` and `f(-x) = hi`.
+#[inline]
+fn fold_pair<AF>(
+    lo: BinomialExtensionField<AF, 4>,
+    hi: BinomialExtensionField<AF, 4>,
+    beta: BinomialExtensionField<AF, 4>,
+    half: AF,
+    inv_two_x: AF,
+) -> BinomialExtensionField<AF, 4>
+where
+    AF: AbstractField,
+    AF::F: HasTwoAdicBionmialExtension<4>,
+{
+    (lo.clone() + hi.clone()) * half + beta * ((lo - hi) * inv_two_x)
+}
+
+/// Transpose `F::Packing::WIDTH` extension elements into one extension element over `F::Packing`.
+#[inline]
+fn pack_ext<F: Field + HasTwoAdicBionmialExtension<4>>(
+    lane: impl Fn(usize) -> Ext4<F>,
+) -> BinomialExtensionField<F::Packing, 4> {
+    let coeffs: [F::Packing; 4] = core::array::from_fn(|c| {
+        F::Packing::from_fn(|l| AbstractExtensionField::<F>::as_base_slice(&lane(l))[c])
+    });
+    BinomialExtensionField::from_base_slice(&coeffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_baby_bear::BabyBear;
+    use p3_field::dft::{Radix2Dit, TwoAdicSubgroupDft};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    type F = BabyBear;
+    type EF = Ext4<F>;
+
+    /// The evaluations over the subgroup of order `2^log_n`, in bit-reversed order, of the
+    /// polynomial with the given coefficients.
+    fn evaluate(coeffs: &[EF], log_n: usize) -> Vec<EF> {
+        let mut values = coeffs.to_vec();
+        values.resize(1 << log_n, EF::zero());
+        Radix2Dit.dft_bit_reversed(&mut values);
+        values
+    }
+
+    #[test]
+    fn fold_matches_definition() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let log_n = 8;
+        let coeffs: Vec<EF> = (0..1 << (log_n - 1)).map(|_| rng.gen()).collect();
+        let codeword = evaluate(&coeffs, log_n);
+
+        for log_arity in 1..=3 {
+            let beta: EF = rng.gen();
+            // The coefficients of `\sum_i beta^i f_i`, where `f_i` takes every `2^log_arity`-th
+            // coefficient starting from the `i`-th.
+            let folded_coeffs: Vec<EF> = coeffs
+                .chunks_exact(1 << log_arity)
+                .map(|chunk| chunk.iter().zip(beta.powers()).map(|(&c, b)| c * b).sum())
+                .collect();
+            assert_eq!(
+                fold(codeword.clone(), log_arity, beta),
+                evaluate(&folded_coeffs, log_n - log_arity)
+            );
+        }
+    }
+
+    #[test]
+    fn fold_row_matches_fold() {
+        let mut rng = SmallRng::seed_from_u64(2);
+        // Short enough that the scalar remainder of `fold_even_odd` is used, and long enough for
+        // the packed path.
+        for log_n in [3, 10] {
+            let codeword: Vec<EF> = (0..1 << log_n).map(|_| rng.gen()).collect();
+            let beta: EF = rng.gen();
+            for log_arity in 1..=3 {
+                let folded = fold(codeword.clone(), log_arity, beta);
+                for (r, row) in codeword.chunks_exact(1 << log_arity).enumerate() {
+                    assert_eq!(fold_row(row, r, log_n, beta), folded[r]);
+                }
+            }
+        }
+    }
+}
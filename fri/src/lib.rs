@@ -0,0 +1,27 @@
+//! The FRI low-degree test, over a two-adic field and its quartic binomial extension.
+//!
+//! The prover commits to a codeword of extension field elements in bit-reversed order, so that
+//! each coset folded together in a round is a contiguous row of the committed matrix. It then
+//! folds the codeword with a random challenge, commits to the result, and repeats until what is
+//! left is sent as the coefficients of the final polynomial. The verifier checks the folds at
+//! random query positions.
+
+#![no_std]
+
+extern crate alloc;
+
+mod config;
+mod fold;
+mod proof;
+mod prover;
+mod verifier;
+
+pub use config::*;
+pub use fold::*;
+use p3_field::extension::BinomialExtensionField;
+pub use proof::*;
+pub use prover::*;
+pub use verifier::*;
+
+/// The extension field that codewords and folding challenges live in.
+pub type Ext4<F> = BinomialExtensionField<F, 4>;
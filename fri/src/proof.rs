@@ -0,0 +1,38 @@
+use alloc::vec::Vec;
+
+use p3_merkle_tree::{MerkleTreeError, MultiProof};
+
+use crate::Ext4;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FriProof<F, const DIGEST_ELEMS: usize> {
+    /// The root of the commitment to the codeword before each round of folding.
+    pub commit_roots: Vec<[F; DIGEST_ELEMS]>,
+    /// The coefficients of the final polynomial, lowest degree first.
+    pub final_poly: Vec<Ext4<F>>,
+    pub pow_witness: F,
+    /// The openings of each commitment at the query positions.
+    pub query_openings: Vec<RoundOpening<F, DIGEST_ELEMS>>,
+}
+
+/// The rows of one committed codeword that the queries landed in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundOpening<F, const DIGEST_ELEMS: usize> {
+    /// The row opened for each query, in query order, with its extension field elements written
+    /// out in the base field.
+    pub rows: Vec<Vec<F>>,
+    pub proof: MultiProof<F, DIGEST_ELEMS>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FriError {
+    /// The proof has the wrong number of rounds, queries or final coefficients.
+    WrongShape,
+    InvalidPowWitness,
+    /// An opening does not match its commitment.
+    Merkle(MerkleTreeError),
+    /// A folded value differs from the opened value it should equal in the next round.
+    FoldMismatch,
+    /// The last folded value differs from the final polynomial at the query position.
+    FinalPolyMismatch,
+}
@@ -0,0 +1,100 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use p3_challenger::{CanObserve, FieldChallenger, GrindingChallenger};
+use p3_field::dft::{Radix2Dit, TwoAdicSubgroupDft};
+use p3_field::extension::HasTwoAdicBionmialExtension;
+use p3_field::{AbstractExtensionField, PrimeField64, TwoAdicField};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_symmetric::{CryptographicHasher, PseudoCompressionFunction};
+use p3_util::{log2_strict_usize, reverse_slice_index_bits};
+
+use crate::{fold, Ext4, FriConfig, FriProof, RoundOpening};
+
+/// Prove that `codeword`, the evaluations in bit-reversed order of a polynomial over the subgroup
+/// of its length, is close to a polynomial of degree less than `codeword.len() >> log_blowup`.
+///
+/// The challenger should already have observed a commitment to whatever the codeword was derived
+/// from.
+pub fn prove<F, H, C, Chal, const DIGEST_ELEMS: usize>(
+    config: &FriConfig<H, C, DIGEST_ELEMS>,
+    codeword: Vec<Ext4<F>>,
+    challenger: &mut Chal,
+) -> FriProof<F, DIGEST_ELEMS>
+where
+    F: TwoAdicField + PrimeField64 + HasTwoAdicBionmialExtension<4>,
+    H: CryptographicHasher<F, [F; DIGEST_ELEMS]>
+        + CryptographicHasher<F::Packing, [F::Packing; DIGEST_ELEMS]>
+        + Sync,
+    C: PseudoCompressionFunction<[F; DIGEST_ELEMS], 2>
+        + PseudoCompressionFunction<[F::Packing; DIGEST_ELEMS], 2>
+        + Sync,
+    Chal: FieldChallenger<F> + GrindingChallenger<Witness = F> + CanObserve<[F; DIGEST_ELEMS]>,
+{
+    let log_n = log2_strict_usize(codeword.len());
+    let schedule = config.fold_schedule(log_n);
+
+    // Commit phase: commit to each codeword, with the cosets to be folded together as rows, then
+    // fold it with a challenge that depends on the commitment.
+    let mut codeword = codeword;
+    let mut commit_roots = vec![];
+    let mut trees = vec![];
+    for &log_arity in &schedule {
+        // Written out in the base field straight from the codeword, which is still needed for the
+        // fold.
+        let values = codeword
+            .iter()
+            .flat_map(|x| {
+                AbstractExtensionField::<F>::as_base_slice(x)
+                    .iter()
+                    .copied()
+            })
+            .collect();
+        let matrix = RowMajorMatrix::new(values, 4 << log_arity);
+        let (root, tree) = config.mmcs.commit(vec![matrix]);
+        challenger.observe(root);
+        commit_roots.push(root);
+        trees.push(tree);
+
+        let beta: Ext4<F> = challenger.sample_ext_element();
+        codeword = fold(codeword, log_arity, beta);
+    }
+
+    // The final codeword is small enough to send as a polynomial. If the original codeword was
+    // far from low degree, the coefficients dropped here are not zero, and the queries catch the
+    // difference.
+    let mut final_poly = codeword;
+    reverse_slice_index_bits(&mut final_poly);
+    Radix2Dit.idft(&mut final_poly);
+    final_poly.truncate(1 << config.log_final_poly_len);
+    for &coeff in &final_poly {
+        challenger.observe_ext_element(coeff);
+    }
+
+    let pow_witness = challenger.grind(config.proof_of_work_bits);
+
+    // Query phase: open the row of each committed codeword that each query position folds into.
+    let indices: Vec<usize> = (0..config.num_queries)
+        .map(|_| challenger.sample_bits(log_n))
+        .collect();
+    let mut log_folded = 0;
+    let query_openings = trees
+        .iter()
+        .zip(&schedule)
+        .map(|(tree, &log_arity)| {
+            log_folded += log_arity;
+            let row_indices: Vec<usize> = indices.iter().map(|&i| i >> log_folded).collect();
+            let (opened, proof) = config.mmcs.open_batch(&row_indices, tree);
+            // Each commitment holds a single matrix.
+            let rows = opened.into_iter().map(|mut rows| rows.remove(0)).collect();
+            RoundOpening { rows, proof }
+        })
+        .collect();
+
+    FriProof {
+        commit_roots,
+        final_poly,
+        pow_witness,
+        query_openings,
+    }
+}
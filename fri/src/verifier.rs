@@ -0,0 +1,222 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use p3_challenger::{CanObserve, FieldChallenger, GrindingChallenger};
+use p3_field::extension::HasTwoAdicBionmialExtension;
+use p3_field::{AbstractExtensionField, AbstractField, PrimeField64, TwoAdicField};
+use p3_matrix::Dimensions;
+use p3_symmetric::{CryptographicHasher, PseudoCompressionFunction};
+use p3_util::reverse_bits_len;
+
+use crate::{fold_row, Ext4, FriConfig, FriError, FriProof};
+
+/// Verify a proof that the codeword of length `2^log_n` committed to in its first round is close
+/// to a polynomial of low degree.
+///
+/// The challenger must be in the state the prover's was in when it started.
+pub fn verify<F, H, C, Chal, const DIGEST_ELEMS: usize>(
+    config: &FriConfig<H, C, DIGEST_ELEMS>,
+    log_n: usize,
+    proof: &FriProof<F, DIGEST_ELEMS>,
+    challenger: &mut Chal,
+) -> Result<(), FriError>
+where
+    F: TwoAdicField + PrimeField64 + HasTwoAdicBionmialExtension<4>,
+    H: CryptographicHasher<F, [F; DIGEST_ELEMS]>,
+    C: PseudoCompressionFunction<[F; DIGEST_ELEMS], 2>,
+    Chal: FieldChallenger<F> + GrindingChallenger<Witness = F> + CanObserve<[F; DIGEST_ELEMS]>,
+{
+    let schedule = config.fold_schedule(log_n);
+    if proof.commit_roots.len() != schedule.len()
+        || proof.query_openings.len() != schedule.len()
+        || proof.final_poly.len() != 1 << config.log_final_poly_len
+    {
+        return Err(FriError::WrongShape);
+    }
+
+    // Replay the prover's transcript.
+    let betas: Vec<Ext4<F>> = proof
+        .commit_roots
+        .iter()
+        .map(|&root| {
+            challenger.observe(root);
+            challenger.sample_ext_element()
+        })
+        .collect();
+    for &coeff in &proof.final_poly {
+        challenger.observe_ext_element(coeff);
+    }
+    if !challenger.check_witness(config.proof_of_work_bits, proof.pow_witness) {
+        return Err(FriError::InvalidPowWitness);
+    }
+    let mut indices: Vec<usize> = (0..config.num_queries)
+        .map(|_| challenger.sample_bits(log_n))
+        .collect();
+
+    // For each query, the value that the previous round folded into the current codeword.
+    let mut folded: Vec<Option<Ext4<F>>> = vec![None; config.num_queries];
+    let mut log_len = log_n;
+    for (((root, opening), &log_arity), &beta) in proof
+        .commit_roots
+        .iter()
+        .zip(&proof.query_openings)
+        .zip(&schedule)
+        .zip(&betas)
+    {
+        if opening.rows.len() != config.num_queries {
+            return Err(FriError::WrongShape);
+        }
+        let row_indices: Vec<usize> = indices.iter().map(|&i| i >> log_arity).collect();
+        let dimensions = [Dimensions {
+            width: 4 << log_arity,
+            height: 1 << (log_len - log_arity),
+        }];
+        let opened: Vec<Vec<Vec<F>>> = opening.rows.iter().map(|row| vec![row.clone()]).collect();
+        config
+            .mmcs
+            .verify_batch(root, &dimensions, &row_indices, &opened, &opening.proof)
+            .map_err(FriError::Merkle)?;
+
+        for ((row, &index), folded) in opening.rows.iter().zip(&indices).zip(&mut folded) {
+            let row: Vec<Ext4<F>> = row.chunks_exact(4).map(Ext4::from_base_slice).collect();
+            let position = index & ((1 << log_arity) - 1);
+            if folded.is_some_and(|value| value != row[position]) {
+                return Err(FriError::FoldMismatch);
+            }
+            *folded = Some(fold_row(&row, index >> log_arity, log_len, beta));
+        }
+        indices = row_indices;
+        log_len -= log_arity;
+    }
+
+    // The final codeword is the final polynomial over the subgroup of order `2^log_len`, in
+    // bit-reversed order.
+    let g = F::two_adic_generator(log_len);
+    for (&index, folded) in indices.iter().zip(&folded) {
+        let x = g.exp_u64(reverse_bits_len(index, log_len) as u64);
+        let expected = proof
+            .final_poly
+            .iter()
+            .rev()
+            .fold(Ext4::<F>::zero(), |acc, &coeff| acc * x + coeff);
+        if *folded != Some(expected) {
+            return Err(FriError::FinalPolyMismatch);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_baby_bear::BabyBear;
+    use p3_challenger::DuplexChallenger;
+    use p3_field::dft::{Radix2Dit, TwoAdicSubgroupDft};
+    use p3_merkle_tree::{MerkleTreeError, MerkleTreeMmcs};
+    use p3_poseidon2::{poseidon2_baby_bear_16, Poseidon2BabyBear};
+    use p3_symmetric::{PaddedSponge, TruncatedPermutation};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::prove;
+
+    type F = BabyBear;
+    type EF = Ext4<F>;
+    type Perm = Poseidon2BabyBear<16>;
+    type Config = FriConfig<PaddedSponge<Perm, 16, 8, 8>, TruncatedPermutation<Perm, 2, 8, 16>, 8>;
+    type Challenger = DuplexChallenger<F, Perm, 16, 8>;
+
+    fn config(log_blowup: usize, log_arity: usize, log_final_poly_len: usize) -> Config {
+        let perm = poseidon2_baby_bear_16();
+        FriConfig {
+            log_blowup,
+            log_arity,
+            num_queries: 10,
+            log_final_poly_len,
+            proof_of_work_bits: 4,
+            mmcs: MerkleTreeMmcs::new(
+                PaddedSponge::new(perm.clone()),
+                TruncatedPermutation::new(perm),
+            ),
+        }
+    }
+
+    fn challenger() -> Challenger {
+        DuplexChallenger::new(poseidon2_baby_bear_16())
+    }
+
+    /// The codeword of a random polynomial of degree less than `2^log_degree`, blown up.
+    fn codeword(rng: &mut SmallRng, log_degree: usize, log_blowup: usize) -> Vec<EF> {
+        let mut values: Vec<EF> = (0..1 << log_degree).map(|_| rng.gen()).collect();
+        values.resize(1 << (log_degree + log_blowup), EF::zero());
+        Radix2Dit.dft_bit_reversed(&mut values);
+        values
+    }
+
+    fn prove_and_verify(config: &Config, codeword: Vec<EF>) -> Result<(), FriError> {
+        let log_n = p3_util::log2_strict_usize(codeword.len());
+        let proof = prove(config, codeword, &mut challenger());
+        verify(config, log_n, &proof, &mut challenger())
+    }
+
+    #[test]
+    fn honest_proofs_verify() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        // Arities that do and do not divide the number of folds, and final polynomials of degree
+        // zero and more.
+        for (log_blowup, log_arity, log_final_poly_len) in
+            [(1, 1, 0), (1, 3, 0), (2, 2, 2), (3, 4, 1)]
+        {
+            let config = config(log_blowup, log_arity, log_final_poly_len);
+            let codeword = codeword(&mut rng, 9, log_blowup);
+            assert_eq!(prove_and_verify(&config, codeword), Ok(()));
+        }
+    }
+
+    #[test]
+    fn rejects_corrupted_codeword() {
+        let mut rng = SmallRng::seed_from_u64(2);
+        let config = config(1, 2, 1);
+        let mut codeword = codeword(&mut rng, 9, 1);
+        // A quarter of the codeword replaced by noise is far from every low-degree codeword.
+        for x in codeword.iter_mut().step_by(4) {
+            *x = rng.gen();
+        }
+        assert_eq!(
+            prove_and_verify(&config, codeword),
+            Err(FriError::FinalPolyMismatch)
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_proofs() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let config = config(1, 2, 1);
+        let codeword = codeword(&mut rng, 9, 1);
+        let proof = prove(&config, codeword, &mut challenger());
+        let verify = |proof: &FriProof<F, 8>| verify(&config, 10, proof, &mut challenger());
+        assert_eq!(verify(&proof), Ok(()));
+
+        // Changing anything in the transcript changes the challenges, and with them the grinding.
+        let mut bad = proof.clone();
+        bad.final_poly[0] += EF::one();
+        assert_ne!(verify(&bad), Ok(()));
+
+        let mut bad = proof.clone();
+        bad.query_openings[1].rows[0][0] += F::one();
+        assert_eq!(
+            verify(&bad),
+            Err(FriError::Merkle(MerkleTreeError::RootMismatch))
+        );
+
+        let mut bad = proof.clone();
+        bad.query_openings.pop();
+        assert_eq!(verify(&bad), Err(FriError::WrongShape));
+
+        let mut bad = proof.clone();
+        bad.final_poly.push(EF::zero());
+        assert_eq!(verify(&bad), Err(FriError::WrongShape));
+    }
+}
@@ -0,0 +1,69 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use p3_baby_bear::BabyBear;
+use p3_field::dft::{Radix2Dit, TwoAdicSubgroupDft};
+use p3_field::{AbstractField, Field};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_maybe_rayon::prelude::*;
+
+type F = BabyBear;
+type P = <F as Field>::Packing;
+
+const LOG_HEIGHT: usize = 16;
+const WIDTH: usize = 64;
+
+fn trace() -> RowMajorMatrix<F> {
+    RowMajorMatrix::rand(&mut rand::thread_rng(), 1 << LOG_HEIGHT, WIDTH)
+}
+
+fn bench_layout(c: &mut Criterion) {
+    let name = format!("2^{LOG_HEIGHT}x{WIDTH}");
+
+    c.bench_function(&format!("{name} transpose"), |b| {
+        let m = trace();
+        b.iter(|| black_box(m.transpose()))
+    });
+
+    c.bench_function(&format!("{name} bit_reverse_rows"), |b| {
+        b.iter_batched(
+            trace,
+            |mut m| {
+                m.bit_reverse_rows();
+                m
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function(&format!("{name} par_rows sum"), |b| {
+        let m = trace();
+        b.iter(|| {
+            m.par_rows()
+                .map(|row| row.iter().copied().sum::<F>())
+                .collect::<Vec<_>>()
+        })
+    });
+
+    c.bench_function(&format!("{name} vertically_packed_row sum"), |b| {
+        let m = trace();
+        b.iter(|| {
+            (0..m.values.len() / WIDTH)
+                .step_by(<P as p3_field::PackedField>::WIDTH)
+                .map(|r| m.vertically_packed_row::<P>(r).sum::<P>())
+                .fold(P::zero(), |acc, x| acc + x)
+        })
+    });
+
+    c.bench_function(&format!("{name} dft_batch"), |b| {
+        b.iter_batched(
+            trace,
+            |mut m| {
+                Radix2Dit.dft_batch(&mut m.values, WIDTH);
+                m
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(matrix, bench_layout);
+criterion_main!(matrix);
@@ -0,0 +1,648 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::{Skip, StepBy};
+use core::slice;
+
+use p3_field::{ExtensionField, Field, PackedField};
+use p3_maybe_rayon::{
+    MaybeParChunks, MaybeParChunksExactIter, MaybeParChunksExactMutIter, MaybeParChunksMut,
+};
+use p3_util::reverse_matrix_index_bits;
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::Matrix;
+
+/// Rows are tiled into blocks of this many rows and columns when transposing, so that both the
+/// reads and the writes of a block stay in cache.
+const TRANSPOSE_BLOCK_SIZE: usize = 32;
+
+/// A dense matrix stored in row-major form.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RowMajorMatrix<T> {
+    /// All values, stored in row-major order.
+    pub values: Vec<T>,
+    width: usize,
+}
+
+/// A borrowed, immutable view of a `RowMajorMatrix`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RowMajorMatrixView<'a, T> {
+    pub values: &'a [T],
+    width: usize,
+}
+
+/// A borrowed, mutable view of a `RowMajorMatrix`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RowMajorMatrixViewMut<'a, T> {
+    pub values: &'a mut [T],
+    width: usize,
+}
+
+impl<T> RowMajorMatrix<T> {
+    /// # Panics
+    /// Panics if `values` does not consist of whole rows of `width` elements.
+    #[must_use]
+    pub fn new(values: Vec<T>, width: usize) -> Self {
+        check_shape(values.len(), width);
+        Self { values, width }
+    }
+
+    /// A matrix with a single column.
+    #[must_use]
+    pub fn new_col(values: Vec<T>) -> Self {
+        Self::new(values, 1)
+    }
+
+    /// A `height x width` matrix of random values.
+    #[must_use]
+    pub fn rand<R: Rng>(rng: &mut R, height: usize, width: usize) -> Self
+    where
+        Standard: Distribution<T>,
+    {
+        let values = rng.sample_iter(Standard).take(width * height).collect();
+        Self { values, width }
+    }
+
+    pub fn as_view(&self) -> RowMajorMatrixView<'_, T> {
+        RowMajorMatrixView {
+            values: &self.values,
+            width: self.width,
+        }
+    }
+
+    pub fn as_view_mut(&mut self) -> RowMajorMatrixViewMut<'_, T> {
+        RowMajorMatrixViewMut {
+            values: &mut self.values,
+            width: self.width,
+        }
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        self.as_view().row(r)
+    }
+
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        &mut self.values[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> slice::ChunksExact<'_, T> {
+        self.as_view().rows()
+    }
+
+    pub fn rows_mut(&mut self) -> slice::ChunksExactMut<'_, T> {
+        self.values.chunks_exact_mut(row_chunk_len(self.width))
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> &T {
+        self.as_view().get(r, c)
+    }
+
+    /// Iterate over column `c`, from the top row down.
+    pub fn col(&self, c: usize) -> StepBy<Skip<slice::Iter<'_, T>>> {
+        self.as_view().col(c)
+    }
+
+    /// Split into the first `r` rows and the rest.
+    pub fn split_rows(&self, r: usize) -> (RowMajorMatrixView<'_, T>, RowMajorMatrixView<'_, T>) {
+        self.as_view().split_rows(r)
+    }
+
+    /// Split into the first `r` rows and the rest.
+    pub fn split_rows_mut(
+        &mut self,
+        r: usize,
+    ) -> (RowMajorMatrixViewMut<'_, T>, RowMajorMatrixViewMut<'_, T>) {
+        self.as_view_mut().split_rows_mut(r)
+    }
+
+    /// Iterate over rows, in parallel if the `parallel` feature of `p3-maybe-rayon` is enabled.
+    pub fn par_rows(&self) -> MaybeParChunksExactIter<'_, T>
+    where
+        T: Sync,
+    {
+        self.as_view().par_rows()
+    }
+
+    /// Iterate over rows mutably, in parallel if the `parallel` feature of `p3-maybe-rayon` is
+    /// enabled.
+    pub fn par_rows_mut(&mut self) -> MaybeParChunksExactMutIter<'_, T>
+    where
+        T: Send,
+    {
+        self.values.par_chunks_exact_mut(row_chunk_len(self.width))
+    }
+
+    /// Iterate over chunks of `num_rows` rows, in parallel if the `parallel` feature of
+    /// `p3-maybe-rayon` is enabled. `num_rows` must divide the height.
+    pub fn par_row_chunks(&self, num_rows: usize) -> MaybeParChunksExactIter<'_, T>
+    where
+        T: Sync,
+    {
+        self.as_view().par_row_chunks(num_rows)
+    }
+
+    /// Mutable version of `par_row_chunks`.
+    pub fn par_row_chunks_mut(&mut self, num_rows: usize) -> MaybeParChunksExactMutIter<'_, T>
+    where
+        T: Send,
+    {
+        self.as_view_mut().par_row_chunks_mut(num_rows)
+    }
+
+    /// Permute the rows so that row `i` moves to row `rev(i)`, where `rev` reverses the low
+    /// `log2(height)` bits.
+    ///
+    /// # Panics
+    /// Panics if the height is not a power of two.
+    pub fn bit_reverse_rows(&mut self) {
+        self.as_view_mut().bit_reverse_rows();
+    }
+
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> RowMajorMatrix<U> {
+        RowMajorMatrix {
+            values: self.values.into_iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+}
+
+impl<T: Copy> RowMajorMatrix<T> {
+    pub fn transpose(&self) -> Self {
+        self.as_view().transpose()
+    }
+
+    /// Split into the first `c` columns and the rest. Columns are not contiguous in row-major
+    /// storage, so unlike `split_rows` this copies.
+    pub fn split_cols(&self, c: usize) -> (Self, Self) {
+        self.as_view().split_cols(c)
+    }
+}
+
+impl<T: Clone> RowMajorMatrix<T> {
+    /// Append rows of `fill` until the height is `new_height`.
+    pub fn expand_to_height(&mut self, new_height: usize, fill: T) {
+        let new_len = new_height * self.width;
+        assert!(new_len >= self.values.len(), "cannot shrink a matrix");
+        self.values.resize(new_len, fill);
+    }
+}
+
+impl<T> RowMajorMatrix<T> {
+    /// Replace each extension field element by its `T::D` coefficients over `F`, so row `r` of the
+    /// result is row `r` of `self` written out in the base field.
+    pub fn flatten_to_base<F: Field>(&self) -> RowMajorMatrix<F>
+    where
+        T: ExtensionField<F>,
+    {
+        RowMajorMatrix {
+            values: self
+                .values
+                .iter()
+                .flat_map(|x| x.as_base_slice().iter().copied())
+                .collect(),
+            width: self.width * T::D,
+        }
+    }
+}
+
+impl<T: Field> RowMajorMatrix<T> {
+    /// Pack row `r` horizontally. See `RowMajorMatrixView::horizontally_packed_row`.
+    pub fn horizontally_packed_row<P>(&self, r: usize) -> (&[P], &[T])
+    where
+        P: PackedField<Scalar = T>,
+    {
+        self.as_view().horizontally_packed_row(r)
+    }
+
+    /// Pack rows `r..r + P::WIDTH` vertically. See `RowMajorMatrixView::vertically_packed_row`.
+    pub fn vertically_packed_row<P>(&self, r: usize) -> impl Iterator<Item = P> + '_
+    where
+        P: PackedField<Scalar = T>,
+    {
+        self.as_view().vertically_packed_row(r)
+    }
+}
+
+impl<T> Matrix<T> for RowMajorMatrix<T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        height(self.values.len(), self.width)
+    }
+}
+
+impl<'a, T> RowMajorMatrixView<'a, T> {
+    /// # Panics
+    /// Panics if `values` does not consist of whole rows of `width` elements.
+    #[must_use]
+    pub fn new(values: &'a [T], width: usize) -> Self {
+        check_shape(values.len(), width);
+        Self { values, width }
+    }
+
+    pub fn row(&self, r: usize) -> &'a [T] {
+        &self.values[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> slice::ChunksExact<'a, T> {
+        self.values.chunks_exact(row_chunk_len(self.width))
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> &'a T {
+        assert!(c < self.width, "column {c} out of bounds");
+        &self.values[r * self.width + c]
+    }
+
+    /// Iterate over column `c`, from the top row down.
+    pub fn col(&self, c: usize) -> StepBy<Skip<slice::Iter<'a, T>>> {
+        assert!(c < self.width, "column {c} out of bounds");
+        self.values.iter().skip(c).step_by(self.width)
+    }
+
+    /// Split into the first `r` rows and the rest.
+    pub fn split_rows(&self, r: usize) -> (Self, Self) {
+        let (upper, lower) = self.values.split_at(r * self.width);
+        (
+            Self {
+                values: upper,
+                width: self.width,
+            },
+            Self {
+                values: lower,
+                width: self.width,
+            },
+        )
+    }
+
+    /// Iterate over rows, in parallel if the `parallel` feature of `p3-maybe-rayon` is enabled.
+    pub fn par_rows(&self) -> MaybeParChunksExactIter<'a, T>
+    where
+        T: Sync,
+    {
+        self.values.par_chunks_exact(row_chunk_len(self.width))
+    }
+
+    /// Iterate over chunks of `num_rows` rows, in parallel if the `parallel` feature of
+    /// `p3-maybe-rayon` is enabled. `num_rows` must divide the height.
+    pub fn par_row_chunks(&self, num_rows: usize) -> MaybeParChunksExactIter<'a, T>
+    where
+        T: Sync,
+    {
+        assert_eq!(self.height() % num_rows, 0, "chunk size must divide height");
+        self.values
+            .par_chunks_exact(row_chunk_len(num_rows * self.width))
+    }
+
+    pub fn to_row_major_matrix(&self) -> RowMajorMatrix<T>
+    where
+        T: Clone,
+    {
+        RowMajorMatrix {
+            values: self.values.to_vec(),
+            width: self.width,
+        }
+    }
+}
+
+impl<'a, T: Copy> RowMajorMatrixView<'a, T> {
+    pub fn transpose(&self) -> RowMajorMatrix<T> {
+        let (width, height) = (self.width, self.height());
+        if self.values.is_empty() {
+            return RowMajorMatrix {
+                values: Vec::new(),
+                width: height,
+            };
+        }
+
+        let mut values = vec![self.values[0]; self.values.len()];
+        for r0 in (0..height).step_by(TRANSPOSE_BLOCK_SIZE) {
+            for c0 in (0..width).step_by(TRANSPOSE_BLOCK_SIZE) {
+                for r in r0..(r0 + TRANSPOSE_BLOCK_SIZE).min(height) {
+                    for c in c0..(c0 + TRANSPOSE_BLOCK_SIZE).min(width) {
+                        values[c * height + r] = self.values[r * width + c];
+                    }
+                }
+            }
+        }
+        RowMajorMatrix {
+            values,
+            width: height,
+        }
+    }
+
+    /// Split into the first `c` columns and the rest. Columns are not contiguous in row-major
+    /// storage, so unlike `split_rows` this copies.
+    pub fn split_cols(&self, c: usize) -> (RowMajorMatrix<T>, RowMajorMatrix<T>) {
+        assert!(c <= self.width, "column {c} out of bounds");
+        let mut left = Vec::with_capacity(self.height() * c);
+        let mut right = Vec::with_capacity(self.height() * (self.width - c));
+        for row in self.rows() {
+            left.extend_from_slice(&row[..c]);
+            right.extend_from_slice(&row[c..]);
+        }
+        (
+            RowMajorMatrix {
+                values: left,
+                width: c,
+            },
+            RowMajorMatrix {
+                values: right,
+                width: self.width - c,
+            },
+        )
+    }
+}
+
+impl<'a, T: Field> RowMajorMatrixView<'a, T> {
+    /// Pack row `r` horizontally: consecutive elements of the row share a packed value. The
+    /// trailing `width % P::WIDTH` elements are returned unpacked.
+    pub fn horizontally_packed_row<P>(&self, r: usize) -> (&'a [P], &'a [T])
+    where
+        P: PackedField<Scalar = T>,
+    {
+        P::pack_slice_with_suffix(self.row(r))
+    }
+
+    /// Pack rows `r..r + P::WIDTH` vertically: the `c`-th packed value holds column `c` of each of
+    /// those rows, one per lane. Rows past the bottom wrap around to the top, which matches how
+    /// constraints read the "next" row of a trace.
+    pub fn vertically_packed_row<P>(&self, r: usize) -> impl Iterator<Item = P> + 'a
+    where
+        P: PackedField<Scalar = T>,
+    {
+        let values = self.values;
+        let width = self.width;
+        let height = self.height();
+        (0..width).map(move |c| P::from_fn(|i| values[((r + i) % height) * width + c]))
+    }
+}
+
+impl<T> Matrix<T> for RowMajorMatrixView<'_, T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        height(self.values.len(), self.width)
+    }
+}
+
+impl<'a, T> RowMajorMatrixViewMut<'a, T> {
+    /// # Panics
+    /// Panics if `values` does not consist of whole rows of `width` elements.
+    #[must_use]
+    pub fn new(values: &'a mut [T], width: usize) -> Self {
+        check_shape(values.len(), width);
+        Self { values, width }
+    }
+
+    pub fn as_view(&self) -> RowMajorMatrixView<'_, T> {
+        RowMajorMatrixView {
+            values: &*self.values,
+            width: self.width,
+        }
+    }
+
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        &mut self.values[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows_mut(&mut self) -> slice::ChunksExactMut<'_, T> {
+        self.values.chunks_exact_mut(row_chunk_len(self.width))
+    }
+
+    /// Split into the first `r` rows and the rest.
+    pub fn split_rows_mut(self, r: usize) -> (Self, Self) {
+        let (upper, lower) = self.values.split_at_mut(r * self.width);
+        (
+            Self {
+                values: upper,
+                width: self.width,
+            },
+            Self {
+                values: lower,
+                width: self.width,
+            },
+        )
+    }
+
+    /// Iterate over rows mutably, in parallel if the `parallel` feature of `p3-maybe-rayon` is
+    /// enabled.
+    pub fn par_rows_mut(self) -> MaybeParChunksExactMutIter<'a, T>
+    where
+        T: Send,
+    {
+        self.values.par_chunks_exact_mut(row_chunk_len(self.width))
+    }
+
+    /// Iterate over chunks of `num_rows` rows mutably, in parallel if the `parallel` feature of
+    /// `p3-maybe-rayon` is enabled. `num_rows` must divide the height.
+    pub fn par_row_chunks_mut(self, num_rows: usize) -> MaybeParChunksExactMutIter<'a, T>
+    where
+        T: Send,
+    {
+        assert_eq!(self.height() % num_rows, 0, "chunk size must divide height");
+        self.values
+            .par_chunks_exact_mut(row_chunk_len(num_rows * self.width))
+    }
+
+    /// Permute the rows so that row `i` moves to row `rev(i)`, where `rev` reverses the low
+    /// `log2(height)` bits.
+    ///
+    /// # Panics
+    /// Panics if the height is not a power of two.
+    pub fn bit_reverse_rows(&mut self) {
+        reverse_matrix_index_bits(self.values, self.width);
+    }
+}
+
+impl<T> Matrix<T> for RowMajorMatrixViewMut<'_, T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        height(self.values.len(), self.width)
+    }
+}
+
+fn check_shape(len: usize, width: usize) {
+    assert!(
+        width > 0 || len == 0,
+        "a matrix with no columns cannot hold values"
+    );
+    if width > 0 {
+        assert_eq!(
+            len % width,
+            0,
+            "length {len} is not a multiple of width {width}"
+        );
+    }
+}
+
+/// The chunk length with which to split the values into rows. A matrix with no columns has no
+/// values and so no rows, but `chunks_exact(0)` panics, so it gets any nonzero length instead.
+fn row_chunk_len(width: usize) -> usize {
+    width.max(1)
+}
+
+fn height(len: usize, width: usize) -> usize {
+    len.checked_div(width).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use p3_baby_bear::BabyBear;
+    use p3_field::{AbstractExtensionField, AbstractField, Field, PackedField};
+    use p3_maybe_rayon::prelude::*;
+
+    use super::*;
+
+    type F = BabyBear;
+    type P = <F as Field>::Packing;
+
+    fn counting_matrix(height: usize, width: usize) -> RowMajorMatrix<F> {
+        RowMajorMatrix::new(
+            (0..height * width).map(F::from_canonical_usize).collect(),
+            width,
+        )
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let m = counting_matrix(4, 3);
+        assert_eq!(
+            m.dimensions(),
+            crate::Dimensions {
+                width: 3,
+                height: 4
+            }
+        );
+        assert_eq!(m.row(2), &[6, 7, 8].map(F::from_canonical_u32));
+        assert_eq!(*m.get(3, 1), F::from_canonical_u32(10));
+        assert_eq!(
+            m.col(1).copied().collect::<Vec<_>>(),
+            vec![1, 4, 7, 10]
+                .into_iter()
+                .map(F::from_canonical_u32)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn transpose() {
+        for (height, width) in [(1, 1), (3, 70), (65, 33), (128, 4)] {
+            let m = counting_matrix(height, width);
+            let t = m.transpose();
+            assert_eq!(
+                t.dimensions(),
+                crate::Dimensions {
+                    width: height,
+                    height: width
+                }
+            );
+            for r in 0..height {
+                for c in 0..width {
+                    assert_eq!(m.get(r, c), t.get(c, r));
+                }
+            }
+            assert_eq!(t.transpose(), m);
+        }
+    }
+
+    #[test]
+    fn bit_reverse_rows() {
+        let mut m = counting_matrix(8, 2);
+        m.bit_reverse_rows();
+        assert_eq!(m.row(1), counting_matrix(8, 2).row(4));
+        assert_eq!(m.row(3), counting_matrix(8, 2).row(6));
+        m.bit_reverse_rows();
+        assert_eq!(m, counting_matrix(8, 2));
+    }
+
+    #[test]
+    fn split() {
+        let m = counting_matrix(5, 4);
+        let (top, bottom) = m.split_rows(2);
+        assert_eq!(top.height(), 2);
+        assert_eq!(bottom.height(), 3);
+        assert_eq!(bottom.row(0), m.row(2));
+
+        let (left, right) = m.split_cols(1);
+        assert_eq!(left.width(), 1);
+        assert_eq!(right.width(), 3);
+        assert_eq!(left.row(4), &m.row(4)[..1]);
+        assert_eq!(right.row(4), &m.row(4)[1..]);
+
+        let mut m = m;
+        let (_, mut bottom) = m.split_rows_mut(4);
+        bottom.row_mut(0)[0] = F::zero();
+        assert!(m.get(4, 0).is_zero());
+    }
+
+    #[test]
+    fn packed_rows() {
+        let height = 2 * P::WIDTH;
+        let m = counting_matrix(height, 5);
+
+        let (packed, suffix) = m.horizontally_packed_row::<P>(1);
+        assert_eq!(packed.len() * P::WIDTH + suffix.len(), 5);
+
+        let r = height - 1;
+        for (c, p) in m.vertically_packed_row::<P>(r).enumerate() {
+            for (i, &x) in p.as_slice().iter().enumerate() {
+                assert_eq!(x, *m.get((r + i) % height, c));
+            }
+        }
+    }
+
+    #[test]
+    fn flatten_to_base() {
+        type EF = p3_field::extension::BinomialExtensionField<F, 4>;
+        let m = RowMajorMatrix::new((0..6).map(EF::from_canonical_usize).collect(), 2);
+        let flat = m.flatten_to_base::<F>();
+        assert_eq!(
+            flat.dimensions(),
+            crate::Dimensions {
+                width: 8,
+                height: 3
+            }
+        );
+        for r in 0..3 {
+            for (c, x) in m.row(r).iter().enumerate() {
+                assert_eq!(&flat.row(r)[4 * c..4 * c + 4], x.as_base_slice());
+            }
+        }
+    }
+
+    #[test]
+    fn par_rows() {
+        let mut m = counting_matrix(64, 3);
+        m.par_rows_mut().for_each(|row| row[0] = row[1] + row[2]);
+        let sums: Vec<F> = m.par_rows().map(|row| row[0]).collect();
+        for (r, s) in sums.into_iter().enumerate() {
+            assert_eq!(s, F::from_canonical_usize(6 * r + 3));
+        }
+        assert_eq!(m.par_row_chunks(16).count(), 4);
+    }
+
+    #[test]
+    fn zero_width() {
+        let mut m = RowMajorMatrix::<F>::new(Vec::new(), 0);
+        assert_eq!(m.height(), 0);
+        assert_eq!(m.rows().count(), 0);
+        assert_eq!(m.rows_mut().count(), 0);
+        assert_eq!(m.par_rows().count(), 0);
+        assert_eq!(m.par_rows_mut().count(), 0);
+        assert_eq!(m.as_view().par_row_chunks(4).count(), 0);
+
+        let (left, _) = counting_matrix(4, 3).split_cols(0);
+        assert_eq!(left.rows().count(), 0);
+    }
+}
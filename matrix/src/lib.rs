@@ -0,0 +1,27 @@
+//! Matrices for execution traces and batched transforms.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod dense;
+
+/// The shape of a matrix.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Dimensions {
+    pub width: usize,
+    pub height: usize,
+}
+
+pub trait Matrix<T> {
+    fn width(&self) -> usize;
+
+    fn height(&self) -> usize;
+
+    fn dimensions(&self) -> Dimensions {
+        Dimensions {
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+}
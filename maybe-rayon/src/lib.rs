@@ -161,6 +161,22 @@ where
     }
 }
 
+/// The iterator returned by `MaybeParChunks::par_chunks_exact`.
+#[cfg(feature = "parallel")]
+pub type MaybeParChunksExactIter<'data, T> = ParChunksExact<'data, T>;
+
+/// The iterator returned by `MaybeParChunks::par_chunks_exact`.
+#[cfg(not(feature = "parallel"))]
+pub type MaybeParChunksExactIter<'data, T> = ChunksExact<'data, T>;
+
+/// The iterator returned by `MaybeParChunksMut::par_chunks_exact_mut`.
+#[cfg(feature = "parallel")]
+pub type MaybeParChunksExactMutIter<'data, T> = ParChunksExactMut<'data, T>;
+
+/// The iterator returned by `MaybeParChunksMut::par_chunks_exact_mut`.
+#[cfg(not(feature = "parallel"))]
+pub type MaybeParChunksExactMutIter<'data, T> = ChunksExactMut<'data, T>;
+
 #[cfg(feature = "parallel")]
 pub trait MaybeParChunks<T: Sync> {
     fn par_chunks(&self, chunk_size: usize) -> ParChunks<'_, T>;
@@ -304,3 +320,28 @@ where
 {
     (oper_a(), oper_b())
 }
+
+/// The number of threads `par_*` iterators will split work across; 1 without `parallel`.
+#[cfg(feature = "parallel")]
+pub fn current_num_threads() -> usize {
+    rayon::current_num_threads()
+}
+
+/// The number of threads `par_*` iterators will split work across; 1 without `parallel`.
+#[cfg(not(feature = "parallel"))]
+pub fn current_num_threads() -> usize {
+    1
+}
+
+/// Re-exports the traits needed to call the `par_*` helpers, whether or not the `parallel`
+/// feature is enabled.
+pub mod prelude {
+    #[cfg(not(feature = "parallel"))]
+    pub use crate::ParallelIteratorMock;
+    #[cfg(feature = "parallel")]
+    pub use crate::{IndexedParallelIterator, ParallelIterator};
+    pub use crate::{
+        MaybeIntoParIter, MaybeParChunks, MaybeParChunksMut, MaybeParIter, MaybeParIterMut,
+        MaybeParWindows,
+    };
+}
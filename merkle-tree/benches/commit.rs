@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use p3_baby_bear::{BabyBear, BabyBearCanonical};
+use p3_field::PrimeField64;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_merkle_tree::MerkleTreeMmcs;
+use p3_poseidon2::poseidon2_baby_bear_16_generic;
+use p3_symmetric::{PaddedSponge, TruncatedPermutation};
+use r0_baby_bear::baby_bear_montgomery;
+use rand::distributions::{Distribution, Standard};
+
+const LOG_HEIGHT: usize = 20;
+/// One sponge block per leaf, so each leaf costs two permutations with the padding block.
+const LEAF_WIDTH: usize = 8;
+
+fn bench_commit<F>(c: &mut Criterion, name: &str)
+where
+    F: PrimeField64 + From<BabyBear>,
+    Standard: Distribution<F>,
+{
+    // The same permutation in every representation, so they all commit to the same root.
+    let perm = poseidon2_baby_bear_16_generic::<F>();
+    let mmcs = MerkleTreeMmcs::<_, _, 8>::new(
+        PaddedSponge::<_, 16, 8, 8>::new(perm.clone()),
+        TruncatedPermutation::<_, 2, 8, 16>::new(perm),
+    );
+    let matrix = RowMajorMatrix::<F>::rand(&mut rand::thread_rng(), 1 << LOG_HEIGHT, LEAF_WIDTH);
+
+    let mut group = c.benchmark_group("merkle tree commit");
+    group.sample_size(10);
+    group.bench_function(format!("{name} 2^{LOG_HEIGHT} leaves"), |b| {
+        b.iter_batched(
+            || vec![matrix.clone()],
+            |leaves| mmcs.commit(leaves),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_p3(c: &mut Criterion) {
+    bench_commit::<BabyBear>(c, "p3 BabyBear");
+    // `BabyBearCanonical` only has a SIMD packing with AVX2 and without AVX-512.
+    let name = if cfg!(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(target_feature = "avx512f")
+    )) {
+        "p3 BabyBearCanonical (AVX2 packing)"
+    } else {
+        "p3 BabyBearCanonical (FieldArray packing)"
+    };
+    bench_commit::<BabyBearCanonical>(c, name);
+}
+
+fn bench_risc0(c: &mut Criterion) {
+    bench_commit::<baby_bear_montgomery::Elem>(c, "risc0 Elem");
+}
+
+criterion_group!(commit, bench_p3, bench_risc0);
+criterion_main!(commit);
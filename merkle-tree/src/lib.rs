@@ -0,0 +1,18 @@
+//! Merkle trees committing to the rows of matrices.
+//!
+//! The leaf and compression functions are pluggable: any `CryptographicHasher` and
+//! `PseudoCompressionFunction` that also accept `F::Packing` can be used, and leaves and layers
+//! are then hashed `F::Packing::WIDTH` at a time, in parallel through `p3_maybe_rayon`. Matrices
+//! over an extension field are committed to through `RowMajorMatrix::flatten_to_base`.
+
+#![no_std]
+
+extern crate alloc;
+
+mod mmcs;
+mod proof;
+mod tree;
+
+pub use mmcs::*;
+pub use proof::*;
+pub use tree::*;
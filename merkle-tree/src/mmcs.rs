@@ -0,0 +1,278 @@
+use alloc::vec::Vec;
+
+use p3_field::Field;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Dimensions;
+use p3_symmetric::{CryptographicHasher, PseudoCompressionFunction};
+
+use crate::{verify_batch, MerkleTree, MerkleTreeError, MultiProof};
+
+/// A commitment scheme for matrices, committing to their rows with a Merkle tree built from a
+/// leaf hash `H` and a compression function `C`.
+#[derive(Copy, Clone, Debug)]
+pub struct MerkleTreeMmcs<H, C, const DIGEST_ELEMS: usize> {
+    hash: H,
+    compress: C,
+}
+
+impl<H, C, const DIGEST_ELEMS: usize> MerkleTreeMmcs<H, C, DIGEST_ELEMS> {
+    pub fn new(hash: H, compress: C) -> Self {
+        Self { hash, compress }
+    }
+
+    pub fn commit<F>(
+        &self,
+        inputs: Vec<RowMajorMatrix<F>>,
+    ) -> ([F; DIGEST_ELEMS], MerkleTree<F, DIGEST_ELEMS>)
+    where
+        F: Field,
+        H: CryptographicHasher<F, [F; DIGEST_ELEMS]>
+            + CryptographicHasher<F::Packing, [F::Packing; DIGEST_ELEMS]>
+            + Sync,
+        C: PseudoCompressionFunction<[F; DIGEST_ELEMS], 2>
+            + PseudoCompressionFunction<[F::Packing; DIGEST_ELEMS], 2>
+            + Sync,
+    {
+        let tree = MerkleTree::new(&self.hash, &self.compress, inputs);
+        (tree.root(), tree)
+    }
+
+    /// The rows at each of `indices` (row `indices[j]` of matrix `i` is `opened[j][i]`), and a
+    /// single proof for all of them.
+    pub fn open_batch<F: Field>(
+        &self,
+        indices: &[usize],
+        tree: &MerkleTree<F, DIGEST_ELEMS>,
+    ) -> (Vec<Vec<Vec<F>>>, MultiProof<F, DIGEST_ELEMS>) {
+        let opened = indices.iter().map(|&i| tree.opened_rows(i)).collect();
+        (opened, tree.prove_batch(indices))
+    }
+
+    pub fn verify_batch<F>(
+        &self,
+        root: &[F; DIGEST_ELEMS],
+        dimensions: &[Dimensions],
+        indices: &[usize],
+        opened: &[Vec<Vec<F>>],
+        proof: &MultiProof<F, DIGEST_ELEMS>,
+    ) -> Result<(), MerkleTreeError>
+    where
+        F: Field,
+        H: CryptographicHasher<F, [F; DIGEST_ELEMS]>,
+        C: PseudoCompressionFunction<[F; DIGEST_ELEMS], 2>,
+    {
+        verify_batch(
+            &self.hash,
+            &self.compress,
+            root,
+            dimensions,
+            indices,
+            opened,
+            proof,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use p3_baby_bear::BabyBear;
+    use p3_field::extension::BinomialExtensionField;
+    use p3_field::{AbstractField, Field, PackedField, PrimeField32};
+    use p3_matrix::Matrix;
+    use p3_poseidon2::{poseidon2_baby_bear_16, Poseidon2BabyBear};
+    use p3_symmetric::{PaddedSponge, TruncatedPermutation};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    type F = BabyBear;
+    type EF = BinomialExtensionField<F, 4>;
+    type Perm = Poseidon2BabyBear<16>;
+    type Hash = PaddedSponge<Perm, 16, 8, 8>;
+    type Compress = TruncatedPermutation<Perm, 2, 8, 16>;
+    type Mmcs = MerkleTreeMmcs<Hash, Compress, 8>;
+
+    fn mmcs() -> Mmcs {
+        let perm = poseidon2_baby_bear_16();
+        MerkleTreeMmcs::new(Hash::new(perm.clone()), Compress::new(perm))
+    }
+
+    fn matrices(rng: &mut SmallRng, height: usize) -> Vec<RowMajorMatrix<F>> {
+        vec![
+            RowMajorMatrix::rand(rng, height, 3),
+            RowMajorMatrix::<EF>::rand(rng, height, 2).flatten_to_base(),
+        ]
+    }
+
+    /// The root computed one leaf and one node at a time, with no packing.
+    fn naive_root(mmcs: &Mmcs, leaves: &[RowMajorMatrix<F>]) -> [F; 8] {
+        let mut layer: Vec<[F; 8]> = (0..leaves[0].height())
+            .map(|r| {
+                let row: Vec<F> = leaves.iter().flat_map(|m| m.row(r).to_vec()).collect();
+                mmcs.hash.hash_slice(&row)
+            })
+            .collect();
+        while layer.len() > 1 {
+            layer = layer
+                .chunks_exact(2)
+                .map(|pair| mmcs.compress.compress([pair[0], pair[1]]))
+                .collect();
+        }
+        layer[0]
+    }
+
+    #[test]
+    fn packed_root_matches_naive() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mmcs = mmcs();
+        // Heights below, at and above the packing width.
+        let width = <F as Field>::Packing::WIDTH;
+        for height in [1, 2, width, 4 * width, 64] {
+            let leaves = matrices(&mut rng, height);
+            let expected = naive_root(&mmcs, &leaves);
+            let (root, tree) = mmcs.commit(leaves);
+            assert_eq!(root, expected, "height {height}");
+            assert_eq!(tree.height(), height);
+        }
+    }
+
+    #[test]
+    fn open_and_verify() {
+        let mut rng = SmallRng::seed_from_u64(2);
+        let mmcs = mmcs();
+        let height = 1 << 10;
+        let leaves = matrices(&mut rng, height);
+        let dims: Vec<Dimensions> = leaves.iter().map(|m| m.dimensions()).collect();
+        let (root, tree) = mmcs.commit(leaves);
+
+        for num_queries in [1, 2, 16, 100] {
+            let mut indices: Vec<usize> =
+                (0..num_queries).map(|_| rng.gen_range(0..height)).collect();
+            // A repeated index is allowed.
+            indices.push(indices[0]);
+            let (opened, proof) = mmcs.open_batch(&indices, &tree);
+            assert_eq!(
+                mmcs.verify_batch(&root, &dims, &indices, &opened, &proof),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn siblings_are_shared() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let mmcs = mmcs();
+        let (_, tree) = mmcs.commit(matrices(&mut rng, 8));
+
+        // Leaves 0 and 1 share all but the first layer of their paths.
+        assert_eq!(tree.prove_batch(&[0, 1]).siblings.len(), 2);
+        assert_eq!(tree.prove_batch(&[1, 0, 1]).siblings.len(), 2);
+        assert_eq!(tree.prove_batch(&[0, 7]).siblings.len(), 4);
+        assert!(tree
+            .prove_batch(&(0..8).collect::<Vec<_>>())
+            .siblings
+            .is_empty());
+
+        // Many queries into a big tree need far fewer siblings than their separate paths.
+        let height = 1 << 12;
+        let (_, tree) = mmcs.commit(matrices(&mut rng, height));
+        let indices: Vec<usize> = (0..64).map(|_| rng.gen_range(0..height)).collect();
+        assert!(tree.prove_batch(&indices).siblings.len() < 64 * 12 * 3 / 4);
+    }
+
+    #[test]
+    fn rejects_tampering() {
+        let mut rng = SmallRng::seed_from_u64(4);
+        let mmcs = mmcs();
+        let leaves = matrices(&mut rng, 64);
+        let dims: Vec<Dimensions> = leaves.iter().map(|m| m.dimensions()).collect();
+        let (root, tree) = mmcs.commit(leaves);
+        let indices = [3, 17, 40];
+        let (opened, proof) = mmcs.open_batch(&indices, &tree);
+        let verify = |indices: &[usize], opened: &[Vec<Vec<F>>], proof: &MultiProof<F, 8>| {
+            mmcs.verify_batch(&root, &dims, indices, opened, proof)
+        };
+        assert_eq!(verify(&indices, &opened, &proof), Ok(()));
+
+        let mut bad_opened = opened.clone();
+        bad_opened[1][1][5] += F::one();
+        assert_eq!(
+            verify(&indices, &bad_opened, &proof),
+            Err(MerkleTreeError::RootMismatch)
+        );
+
+        let mut bad_proof = proof.clone();
+        bad_proof.siblings[4][0] += F::one();
+        assert_eq!(
+            verify(&indices, &opened, &bad_proof),
+            Err(MerkleTreeError::RootMismatch)
+        );
+
+        assert_eq!(
+            verify(&[3, 17, 41], &opened, &proof),
+            Err(MerkleTreeError::RootMismatch)
+        );
+        assert_eq!(
+            verify(&[3, 17, 64], &opened, &proof),
+            Err(MerkleTreeError::IndexOutOfBounds)
+        );
+        assert_eq!(
+            verify(&indices[..2], &opened, &proof),
+            Err(MerkleTreeError::WrongBatchSize)
+        );
+
+        let mut short_proof = proof.clone();
+        short_proof.siblings.pop();
+        assert_eq!(
+            verify(&indices, &opened, &short_proof),
+            Err(MerkleTreeError::WrongProofLength)
+        );
+        let mut long_proof = proof.clone();
+        long_proof.siblings.push([F::zero(); 8]);
+        assert_eq!(
+            verify(&indices, &opened, &long_proof),
+            Err(MerkleTreeError::WrongProofLength)
+        );
+
+        let mut wide = opened.clone();
+        wide[0][0].push(F::zero());
+        assert_eq!(
+            verify(&indices, &wide, &proof),
+            Err(MerkleTreeError::WrongDimensions)
+        );
+
+        let mut repeated = opened.clone();
+        repeated.push(bad_opened[1].clone());
+        assert_eq!(
+            verify(&[3, 17, 40, 17], &repeated, &proof),
+            Err(MerkleTreeError::InconsistentOpenings)
+        );
+    }
+
+    #[test]
+    fn encoding_round_trip() {
+        let mut rng = SmallRng::seed_from_u64(5);
+        let mmcs = mmcs();
+        let (_, tree) = mmcs.commit(matrices(&mut rng, 256));
+        let proof = tree.prove_batch(&[5, 6, 100, 255]);
+
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), proof.siblings.len() * 8 * 4);
+        assert_eq!(MultiProof::from_bytes(&bytes), Ok(proof));
+
+        assert_eq!(
+            MultiProof::<F, 8>::from_bytes(&bytes[1..]),
+            Err(MerkleTreeError::MalformedEncoding)
+        );
+        let mut non_canonical = bytes.clone();
+        non_canonical[..4].copy_from_slice(&F::ORDER_U32.to_le_bytes());
+        assert_eq!(
+            MultiProof::<F, 8>::from_bytes(&non_canonical),
+            Err(MerkleTreeError::MalformedEncoding)
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::vec::Vec;
+
+use p3_field::{Field, PrimeField32};
+use p3_matrix::Dimensions;
+use p3_symmetric::{CryptographicHasher, PseudoCompressionFunction};
+
+/// A proof that several leaves of a Merkle tree are consistent with its root.
+///
+/// Rather than one authentication path per leaf, this holds each sibling digest once however many
+/// of the paths it lies on, and omits those that the verifier recomputes from the opened leaves.
+/// The siblings are ordered layer by layer from the leaves, and by index within a layer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiProof<F, const DIGEST_ELEMS: usize> {
+    pub siblings: Vec<[F; DIGEST_ELEMS]>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MerkleTreeError {
+    /// The number of opened leaves differs from the number of indices.
+    WrongBatchSize,
+    /// An opened leaf has the wrong number of rows, or a row has the wrong width.
+    WrongDimensions,
+    IndexOutOfBounds,
+    /// The same index was opened twice with different values.
+    InconsistentOpenings,
+    /// The proof has too few or too many siblings.
+    WrongProofLength,
+    RootMismatch,
+    /// The bytes are not a whole number of digests of canonical field elements.
+    MalformedEncoding,
+}
+
+impl<F: PrimeField32, const DIGEST_ELEMS: usize> MultiProof<F, DIGEST_ELEMS> {
+    /// Each element as its canonical value in 4 little-endian bytes. The number of siblings is
+    /// implied by the length, so there is no header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.siblings
+            .iter()
+            .flatten()
+            .flat_map(|x| x.as_canonical_u32().to_le_bytes())
+            .collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleTreeError> {
+        if !bytes.len().is_multiple_of(4 * DIGEST_ELEMS) {
+            return Err(MerkleTreeError::MalformedEncoding);
+        }
+        let elems = bytes
+            .chunks_exact(4)
+            .map(|b| {
+                let x = u32::from_le_bytes(b.try_into().unwrap());
+                (x < F::ORDER_U32)
+                    .then(|| F::from_canonical_u32(x))
+                    .ok_or(MerkleTreeError::MalformedEncoding)
+            })
+            .collect::<Result<Vec<F>, _>>()?;
+        let siblings = elems
+            .chunks_exact(DIGEST_ELEMS)
+            .map(|digest| digest.try_into().unwrap())
+            .collect();
+        Ok(Self { siblings })
+    }
+}
+
+/// Check that `opened_rows[j]`, the rows at `indices[j]` of matrices with the given dimensions,
+/// are leaves of the tree with the given root.
+///
+/// Only scalar hashing is used, as a verifier opens few leaves.
+pub fn verify_batch<F, H, C, const DIGEST_ELEMS: usize>(
+    hash: &H,
+    compress: &C,
+    root: &[F; DIGEST_ELEMS],
+    dimensions: &[Dimensions],
+    indices: &[usize],
+    opened_rows: &[Vec<Vec<F>>],
+    proof: &MultiProof<F, DIGEST_ELEMS>,
+) -> Result<(), MerkleTreeError>
+where
+    F: Field,
+    H: CryptographicHasher<F, [F; DIGEST_ELEMS]>,
+    C: PseudoCompressionFunction<[F; DIGEST_ELEMS], 2>,
+{
+    let height = dimensions.first().map_or(0, |d| d.height);
+    if !height.is_power_of_two() || dimensions.iter().any(|d| d.height != height) {
+        return Err(MerkleTreeError::WrongDimensions);
+    }
+    if indices.len() != opened_rows.len() {
+        return Err(MerkleTreeError::WrongBatchSize);
+    }
+
+    let mut nodes = Vec::with_capacity(indices.len());
+    for (&index, rows) in indices.iter().zip(opened_rows) {
+        if index >= height {
+            return Err(MerkleTreeError::IndexOutOfBounds);
+        }
+        if rows.len() != dimensions.len()
+            || rows
+                .iter()
+                .zip(dimensions)
+                .any(|(row, d)| row.len() != d.width)
+        {
+            return Err(MerkleTreeError::WrongDimensions);
+        }
+        nodes.push((index, hash.hash_iter(rows.iter().flatten().copied())));
+    }
+    nodes.sort_unstable_by_key(|&(index, _)| index);
+    for pair in nodes.windows(2) {
+        if pair[0].0 == pair[1].0 && pair[0].1 != pair[1].1 {
+            return Err(MerkleTreeError::InconsistentOpenings);
+        }
+    }
+    nodes.dedup_by_key(|&mut (index, _)| index);
+
+    let mut siblings = proof.siblings.iter();
+    for _ in 0..height.ilog2() {
+        // Pair up nodes exactly as `MerkleTree::prove_batch` does, taking each sibling that is not
+        // known from the proof.
+        let mut next = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let (index, digest) = nodes[i];
+            let sibling = if index.is_multiple_of(2)
+                && nodes.get(i + 1).map(|&(j, _)| j) == Some(index + 1)
+            {
+                i += 1;
+                nodes[i].1
+            } else {
+                *siblings.next().ok_or(MerkleTreeError::WrongProofLength)?
+            };
+            let children = if index.is_multiple_of(2) {
+                [digest, sibling]
+            } else {
+                [sibling, digest]
+            };
+            next.push((index >> 1, compress.compress(children)));
+            i += 1;
+        }
+        nodes = next;
+    }
+
+    if siblings.next().is_some() {
+        return Err(MerkleTreeError::WrongProofLength);
+    }
+    match nodes.as_slice() {
+        [(0, digest)] if digest == root => Ok(()),
+        _ => Err(MerkleTreeError::RootMismatch),
+    }
+}
@@ -0,0 +1,200 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use p3_field::{Field, PackedField};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_maybe_rayon::prelude::*;
+use p3_symmetric::{CryptographicHasher, PseudoCompressionFunction};
+
+use crate::MultiProof;
+
+/// A Merkle tree whose leaves are the rows of one or more matrices of the same height.
+///
+/// Leaf `i` is the hash of row `i` of every matrix, concatenated in order, and each node above is
+/// the compression of its two children. The height must be a power of two.
+#[derive(Clone, Debug)]
+pub struct MerkleTree<F, const DIGEST_ELEMS: usize> {
+    leaves: Vec<RowMajorMatrix<F>>,
+    /// The leaf digests first, then each layer of nodes up to the one holding the root.
+    digest_layers: Vec<Vec<[F; DIGEST_ELEMS]>>,
+}
+
+impl<F: Field, const DIGEST_ELEMS: usize> MerkleTree<F, DIGEST_ELEMS> {
+    /// # Panics
+    /// Panics if there are no matrices, or if their heights differ or are not a power of two.
+    pub fn new<H, C>(hash: &H, compress: &C, leaves: Vec<RowMajorMatrix<F>>) -> Self
+    where
+        H: CryptographicHasher<F, [F; DIGEST_ELEMS]>
+            + CryptographicHasher<F::Packing, [F::Packing; DIGEST_ELEMS]>
+            + Sync,
+        C: PseudoCompressionFunction<[F; DIGEST_ELEMS], 2>
+            + PseudoCompressionFunction<[F::Packing; DIGEST_ELEMS], 2>
+            + Sync,
+    {
+        assert!(
+            !leaves.is_empty(),
+            "a Merkle tree needs at least one matrix"
+        );
+        let height = leaves[0].height();
+        assert!(
+            height.is_power_of_two(),
+            "the height must be a power of two"
+        );
+        assert!(
+            leaves.iter().all(|m| m.height() == height),
+            "all matrices must have the same height"
+        );
+
+        let mut digest_layers = vec![hash_leaves(hash, &leaves)];
+        while let Some(layer) = digest_layers.last().filter(|layer| layer.len() > 1) {
+            let next = compress_layer(compress, layer);
+            digest_layers.push(next);
+        }
+        Self {
+            leaves,
+            digest_layers,
+        }
+    }
+
+    pub fn root(&self) -> [F; DIGEST_ELEMS] {
+        self.digest_layers.last().unwrap()[0]
+    }
+
+    /// The number of leaves.
+    pub fn height(&self) -> usize {
+        self.digest_layers[0].len()
+    }
+
+    pub fn leaves(&self) -> &[RowMajorMatrix<F>] {
+        &self.leaves
+    }
+
+    /// Row `index` of each matrix.
+    pub fn opened_rows(&self, index: usize) -> Vec<Vec<F>> {
+        self.leaves.iter().map(|m| m.row(index).to_vec()).collect()
+    }
+
+    /// The sibling digests needed to recompute the root from the leaves at `indices`, which may be
+    /// in any order and contain duplicates.
+    ///
+    /// # Panics
+    /// Panics if an index is not less than the height.
+    pub fn prove_batch(&self, indices: &[usize]) -> MultiProof<F, DIGEST_ELEMS> {
+        assert!(
+            indices.iter().all(|&i| i < self.height()),
+            "index out of bounds"
+        );
+        let mut known = indices.to_vec();
+        known.sort_unstable();
+        known.dedup();
+
+        let mut siblings = Vec::new();
+        for layer in &self.digest_layers[..self.digest_layers.len() - 1] {
+            for_each_missing_sibling(&known, |sibling| siblings.push(layer[sibling]));
+            known = parents(&known);
+        }
+        MultiProof { siblings }
+    }
+}
+
+/// Calls `f` with the index of each sibling of a `known` node (sorted and deduplicated) that is
+/// not itself known, in increasing order.
+fn for_each_missing_sibling(known: &[usize], mut f: impl FnMut(usize)) {
+    let mut i = 0;
+    while i < known.len() {
+        let node = known[i];
+        if node.is_multiple_of(2) && known.get(i + 1) == Some(&(node + 1)) {
+            i += 2;
+        } else {
+            f(node ^ 1);
+            i += 1;
+        }
+    }
+}
+
+/// The parents of sorted, deduplicated nodes, again sorted and deduplicated.
+fn parents(known: &[usize]) -> Vec<usize> {
+    let mut parents: Vec<usize> = known.iter().map(|node| node >> 1).collect();
+    parents.dedup();
+    parents
+}
+
+fn hash_leaves<F, H, const DIGEST_ELEMS: usize>(
+    hash: &H,
+    leaves: &[RowMajorMatrix<F>],
+) -> Vec<[F; DIGEST_ELEMS]>
+where
+    F: Field,
+    H: CryptographicHasher<F, [F; DIGEST_ELEMS]>
+        + CryptographicHasher<F::Packing, [F::Packing; DIGEST_ELEMS]>
+        + Sync,
+{
+    let height = leaves[0].height();
+    let width = F::Packing::WIDTH;
+    let mut digests = vec![[F::zero(); DIGEST_ELEMS]; height];
+
+    // Rows that do not fill a whole packed vector are hashed one at a time.
+    let packed_len = height - height % width;
+    let (packed, rest) = digests.split_at_mut(packed_len);
+    packed
+        .par_chunks_exact_mut(width)
+        .enumerate()
+        .for_each(|(i, digests)| {
+            let rows = leaves
+                .iter()
+                .flat_map(|m| m.vertically_packed_row::<F::Packing>(i * width));
+            unpack_digests(hash.hash_iter(rows), digests);
+        });
+    for (i, digest) in rest.iter_mut().enumerate() {
+        let rows = leaves
+            .iter()
+            .flat_map(|m| m.row(packed_len + i).iter().copied());
+        *digest = hash.hash_iter(rows);
+    }
+    digests
+}
+
+fn compress_layer<F, C, const DIGEST_ELEMS: usize>(
+    compress: &C,
+    prev: &[[F; DIGEST_ELEMS]],
+) -> Vec<[F; DIGEST_ELEMS]>
+where
+    F: Field,
+    C: PseudoCompressionFunction<[F; DIGEST_ELEMS], 2>
+        + PseudoCompressionFunction<[F::Packing; DIGEST_ELEMS], 2>
+        + Sync,
+{
+    let width = F::Packing::WIDTH;
+    let next_len = prev.len() / 2;
+    let mut next = vec![[F::zero(); DIGEST_ELEMS]; next_len];
+
+    let packed_len = next_len - next_len % width;
+    let (packed, rest) = next.split_at_mut(packed_len);
+    packed
+        .par_chunks_exact_mut(width)
+        .enumerate()
+        .for_each(|(i, digests)| {
+            let child = |side: usize| -> [F::Packing; DIGEST_ELEMS] {
+                core::array::from_fn(|k| {
+                    F::Packing::from_fn(|lane| prev[2 * (i * width + lane) + side][k])
+                })
+            };
+            unpack_digests(compress.compress([child(0), child(1)]), digests);
+        });
+    for (i, digest) in rest.iter_mut().enumerate() {
+        let node = packed_len + i;
+        *digest = compress.compress([prev[2 * node], prev[2 * node + 1]]);
+    }
+    next
+}
+
+/// Write lane `l` of `packed` to `digests[l]`.
+fn unpack_digests<P: PackedField, const DIGEST_ELEMS: usize>(
+    packed: [P; DIGEST_ELEMS],
+    digests: &mut [[P::Scalar; DIGEST_ELEMS]],
+) {
+    for (lane, digest) in digests.iter_mut().enumerate() {
+        *digest = packed.map(|p| p.as_slice()[lane]);
+    }
+}
@@ -1,6 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
-use p3_baby_bear::BabyBear;
+use p3_baby_bear::{BabyBear, BabyBearCanonical, KoalaBear};
 use p3_field::{extension::BinomialExtensionField, Field};
+use p3_goldilocks::Goldilocks;
+use p3_mersenne_31::{Mersenne31, Mersenne31Complex};
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
 use rand::Rng;
@@ -8,6 +10,8 @@ use rand::Rng;
 type Base = BabyBear;
 type EF4 = BinomialExtensionField<BabyBear, 4>;
 type EF5 = BinomialExtensionField<BabyBear, 5>;
+type CanonicalEF4 = BinomialExtensionField<BabyBearCanonical, 4>;
+type Goldilocks2 = BinomialExtensionField<Goldilocks, 2>;
 
 fn bench_field<F: Field>(c: &mut Criterion, name: &str)
 where
@@ -217,6 +221,21 @@ fn bench_babybear(c: &mut Criterion) {
     bench_field::<Base>(c, name);
 }
 
+fn bench_babybear_canonical(c: &mut Criterion) {
+    let name = "BabyBearCanonical";
+    bench_field::<BabyBearCanonical>(c, name);
+}
+
+fn bench_canonical_quartic_extension(c: &mut Criterion) {
+    let name = "BinomialExtensionField<BabyBearCanonical, 4>";
+    bench_field::<CanonicalEF4>(c, name);
+}
+
+fn bench_koalabear(c: &mut Criterion) {
+    let name = "KoalaBear";
+    bench_field::<KoalaBear>(c, name);
+}
+
 fn bench_quartic_extension(c: &mut Criterion) {
     let name = "BinomialExtensionField<BabyBear, 4>";
     bench_field::<EF4>(c, name);
@@ -227,11 +246,38 @@ fn bench_qunitic_extension(c: &mut Criterion) {
     bench_field::<EF5>(c, name);
 }
 
+fn bench_goldilocks(c: &mut Criterion) {
+    let name = "Goldilocks";
+    bench_field::<Goldilocks>(c, name);
+}
+
+fn bench_goldilocks_quadratic_extension(c: &mut Criterion) {
+    let name = "BinomialExtensionField<Goldilocks, 2>";
+    bench_field::<Goldilocks2>(c, name);
+}
+
+fn bench_mersenne31(c: &mut Criterion) {
+    let name = "Mersenne31";
+    bench_field::<Mersenne31>(c, name);
+}
+
+fn bench_mersenne31_complex(c: &mut Criterion) {
+    let name = "Mersenne31Complex";
+    bench_field::<Mersenne31Complex>(c, name);
+}
+
 criterion_group!(
     arithmetic,
     bench_babybear,
+    bench_koalabear,
     bench_quartic_extension,
     bench_qunitic_extension,
+    bench_babybear_canonical,
+    bench_canonical_quartic_extension,
+    bench_goldilocks,
+    bench_goldilocks_quadratic_extension,
+    bench_mersenne31,
+    bench_mersenne31_complex,
 );
 
 criterion_main!(arithmetic);
@@ -0,0 +1,82 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use p3_baby_bear::BabyBear;
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{
+    batch_multiplicative_inverse, batch_multiplicative_inverse_allowing_zero,
+    batch_multiplicative_inverse_packed, par_batch_multiplicative_inverse, Field,
+};
+use r0_baby_bear::{baby_bear_canonical, baby_bear_montgomery};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+type Base = BabyBear;
+type EF4 = BinomialExtensionField<BabyBear, 4>;
+
+const LEN: usize = 1 << 16;
+
+fn bench_batch_inverse<F: Field>(c: &mut Criterion, name: &str)
+where
+    Standard: Distribution<F>,
+{
+    let mut rng = rand::thread_rng();
+    let mut input = || -> Vec<F> { (0..LEN).map(|_| rng.gen::<F>()).collect() };
+
+    c.bench_function(&format!("{} batch_inverse 2^16", name), |b| {
+        b.iter_batched(
+            &mut input,
+            |x| batch_multiplicative_inverse(&x),
+            BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function(&format!("{} batch_inverse_packed 2^16", name), |b| {
+        b.iter_batched(
+            &mut input,
+            |x| batch_multiplicative_inverse_packed(&x),
+            BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function(&format!("{} par_batch_inverse 2^16", name), |b| {
+        b.iter_batched(
+            &mut input,
+            |x| par_batch_multiplicative_inverse(&x),
+            BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function(&format!("{} batch_inverse_allowing_zero 2^16", name), |b| {
+        b.iter_batched(
+            &mut input,
+            |x| batch_multiplicative_inverse_allowing_zero(&x),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn bench_babybear(c: &mut Criterion) {
+    bench_batch_inverse::<Base>(c, "BabyBear");
+}
+
+fn bench_quartic_extension(c: &mut Criterion) {
+    bench_batch_inverse::<EF4>(c, "BinomialExtensionField<BabyBear, 4>");
+}
+
+fn bench_risc0_montgomery(c: &mut Criterion) {
+    bench_batch_inverse::<baby_bear_montgomery::Elem>(c, "risc0 Montgomery Elem");
+    bench_batch_inverse::<baby_bear_montgomery::ExtElem>(c, "risc0 Montgomery ExtElem");
+}
+
+fn bench_risc0_canonical(c: &mut Criterion) {
+    bench_batch_inverse::<baby_bear_canonical::Elem>(c, "risc0 canonical Elem");
+    bench_batch_inverse::<baby_bear_canonical::ExtElem>(c, "risc0 canonical ExtElem");
+}
+
+criterion_group!(
+    batch_inverse,
+    bench_babybear,
+    bench_quartic_extension,
+    bench_risc0_montgomery,
+    bench_risc0_canonical
+);
+criterion_main!(batch_inverse);
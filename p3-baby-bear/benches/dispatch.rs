@@ -0,0 +1,124 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use p3_baby_bear::{BabyBear, BabyBearKernels, SimdBackend};
+use p3_field::{
+    add_scaled_slice_in_place_packed, add_slices_in_place, batch_multiplicative_inverse_packed,
+    mul_slices_in_place,
+};
+use rand::Rng;
+
+type Base = BabyBear;
+
+const LEN: usize = 1 << 16;
+
+type BinaryOp = Box<dyn Fn(&mut [Base], &[Base])>;
+type ScaledOp = Box<dyn Fn(&mut [Base], &[Base], Base)>;
+type ButterflyOp = Box<dyn Fn(&mut [Base], &mut [Base], Base)>;
+type InverseOp = Box<dyn Fn(&[Base]) -> Vec<Base>>;
+
+/// One implementation of every kernel: either the statically selected `p3_field` functions, or a
+/// runtime-dispatched `BabyBearKernels`.
+struct Kernels {
+    name: String,
+    add: BinaryOp,
+    mul: BinaryOp,
+    axpy: ScaledOp,
+    dit_butterfly: ButterflyOp,
+    batch_inverse: InverseOp,
+}
+
+fn static_kernels() -> Kernels {
+    Kernels {
+        name: "static".into(),
+        add: Box::new(add_slices_in_place),
+        mul: Box::new(mul_slices_in_place),
+        axpy: Box::new(add_scaled_slice_in_place_packed),
+        dit_butterfly: Box::new(
+            p3_field::kernels::dit_butterfly::<<Base as p3_field::Field>::Packing>,
+        ),
+        batch_inverse: Box::new(batch_multiplicative_inverse_packed),
+    }
+}
+
+fn dispatched_kernels(kernels: &'static BabyBearKernels, name: String) -> Kernels {
+    Kernels {
+        name,
+        add: Box::new(|x, y| kernels.add_slices_in_place(x, y)),
+        mul: Box::new(|x, y| kernels.mul_slices_in_place(x, y)),
+        axpy: Box::new(|x, y, s| kernels.add_scaled_slice_in_place(x, y, s)),
+        dit_butterfly: Box::new(|lo, hi, t| kernels.dit_butterfly(lo, hi, t)),
+        batch_inverse: Box::new(|x| kernels.batch_multiplicative_inverse(x)),
+    }
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let x: Vec<Base> = (0..LEN).map(|_| rng.gen()).collect();
+    let y: Vec<Base> = (0..LEN).map(|_| rng.gen()).collect();
+    let s = rng.gen::<Base>();
+
+    let mut implementations = vec![
+        static_kernels(),
+        dispatched_kernels(BabyBearKernels::detected(), "dispatch detected".into()),
+    ];
+    for backend in [SimdBackend::Static, SimdBackend::Avx2, SimdBackend::Avx512] {
+        if let Some(kernels) = BabyBearKernels::for_backend(backend) {
+            implementations.push(dispatched_kernels(kernels, format!("dispatch {backend:?}")));
+        }
+    }
+
+    for imp in &implementations {
+        c.bench_function(&format!("{} add_slices_in_place 2^16", imp.name), |b| {
+            b.iter_batched(
+                || x.clone(),
+                |mut x| {
+                    (imp.add)(&mut x, &y);
+                    x
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        c.bench_function(&format!("{} mul_slices_in_place 2^16", imp.name), |b| {
+            b.iter_batched(
+                || x.clone(),
+                |mut x| {
+                    (imp.mul)(&mut x, &y);
+                    x
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        c.bench_function(
+            &format!("{} add_scaled_slice_in_place 2^16", imp.name),
+            |b| {
+                b.iter_batched(
+                    || x.clone(),
+                    |mut x| {
+                        (imp.axpy)(&mut x, &y, s);
+                        x
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+
+        c.bench_function(&format!("{} dit_butterfly 2^16", imp.name), |b| {
+            b.iter_batched(
+                || (x.clone(), y.clone()),
+                |(mut lo, mut hi)| {
+                    (imp.dit_butterfly)(&mut lo, &mut hi, s);
+                    (lo, hi)
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        c.bench_function(&format!("{} batch_inverse 2^16", imp.name), |b| {
+            b.iter(|| (imp.batch_inverse)(&x))
+        });
+    }
+}
+
+criterion_group!(dispatch, bench_dispatch);
+criterion_main!(dispatch);
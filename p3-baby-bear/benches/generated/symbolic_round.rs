@@ -0,0 +1,125 @@
+// @generated by benches/symbolic_codegen.rs with P3_REGENERATE_CODEGEN=1. Do not edit.
+
+pub fn symbolic_round<AF: p3_field::AbstractField>(vars: &[AF]) -> [AF; 8] {
+    let t0 = AF::from_canonical_u64(295875520) + vars[7].clone();
+    let t1 = AF::exp_const_u64::<7>(&t0);
+    let t2 = AF::from_canonical_u64(5) * t1.clone();
+    let t3 = -AF::from_canonical_u64(949068473) + vars[6].clone();
+    let t4 = AF::exp_const_u64::<7>(&t3);
+    let t5 = AF::from_canonical_u64(7) * t4.clone();
+    let t6 = AF::from_canonical_u64(758777552) + vars[5].clone();
+    let t7 = AF::exp_const_u64::<7>(&t6);
+    let t8 = AF::from_canonical_u64(453357656) + vars[4].clone();
+    let t9 = AF::exp_const_u64::<7>(&t8);
+    let t10 = AF::from_canonical_u64(4) * t9.clone();
+    let t11 = AF::from_canonical_u64(147937760) + vars[3].clone();
+    let t12 = AF::exp_const_u64::<7>(&t11);
+    let t13 = AF::from_canonical_u64(916259688) + vars[2].clone();
+    let t14 = AF::exp_const_u64::<7>(&t13);
+    let t15 = AF::from_canonical_u64(610839792) + vars[1].clone();
+    let t16 = AF::exp_const_u64::<7>(&t15);
+    let t17 = AF::from_canonical_u64(3) * t16.clone();
+    let t18 = AF::from_canonical_u64(305419896) + vars[0].clone();
+    let t19 = AF::exp_const_u64::<7>(&t18);
+    let t20 = AF::two() * t19.clone();
+    let t21 = t17 + t20;
+    let t22 = t14.clone() + t21;
+    let t23 = t12.clone() + t22;
+    let t24 = t10 + t23;
+    let t25 = t7.clone() + t24;
+    let t26 = t5 + t25;
+    let t27 = t2 + t26;
+    let t28 = t27 - vars[8].clone();
+    let t29 = AF::from_canonical_u64(7) * t1.clone();
+    let t30 = AF::from_canonical_u64(4) * t7.clone();
+    let t31 = AF::from_canonical_u64(3) * t14.clone();
+    let t32 = AF::two() * t16.clone();
+    let t33 = AF::from_canonical_u64(5) * t19.clone();
+    let t34 = t32 + t33;
+    let t35 = t31 + t34;
+    let t36 = t12.clone() + t35;
+    let t37 = t9.clone() + t36;
+    let t38 = t30 + t37;
+    let t39 = t4.clone() + t38;
+    let t40 = t29 + t39;
+    let t41 = t40 - vars[9].clone();
+    let t42 = AF::from_canonical_u64(4) * t4.clone();
+    let t43 = AF::from_canonical_u64(3) * t12.clone();
+    let t44 = AF::two() * t14.clone();
+    let t45 = AF::from_canonical_u64(5) * t16.clone();
+    let t46 = AF::from_canonical_u64(7) * t19.clone();
+    let t47 = t45 + t46;
+    let t48 = t44 + t47;
+    let t49 = t43 + t48;
+    let t50 = t9.clone() + t49;
+    let t51 = t7.clone() + t50;
+    let t52 = t42 + t51;
+    let t53 = t1.clone() + t52;
+    let t54 = t53 - vars[10].clone();
+    let t55 = AF::from_canonical_u64(4) * t1.clone();
+    let t56 = AF::from_canonical_u64(3) * t9.clone();
+    let t57 = AF::two() * t12.clone();
+    let t58 = AF::from_canonical_u64(5) * t14.clone();
+    let t59 = AF::from_canonical_u64(7) * t16.clone();
+    let t60 = t19.clone() + t59;
+    let t61 = t58 + t60;
+    let t62 = t57 + t61;
+    let t63 = t56 + t62;
+    let t64 = t7.clone() + t63;
+    let t65 = t4.clone() + t64;
+    let t66 = t55 + t65;
+    let t67 = t66 - vars[11].clone();
+    let t68 = AF::from_canonical_u64(3) * t7.clone();
+    let t69 = AF::two() * t9.clone();
+    let t70 = AF::from_canonical_u64(5) * t12.clone();
+    let t71 = AF::from_canonical_u64(7) * t14.clone();
+    let t72 = AF::from_canonical_u64(4) * t19.clone();
+    let t73 = t16.clone() + t72;
+    let t74 = t71 + t73;
+    let t75 = t70 + t74;
+    let t76 = t69 + t75;
+    let t77 = t68 + t76;
+    let t78 = t4.clone() + t77;
+    let t79 = t1.clone() + t78;
+    let t80 = t79 - vars[12].clone();
+    let t81 = AF::from_canonical_u64(3) * t4.clone();
+    let t82 = AF::two() * t7.clone();
+    let t83 = AF::from_canonical_u64(5) * t9.clone();
+    let t84 = AF::from_canonical_u64(7) * t12.clone();
+    let t85 = AF::from_canonical_u64(4) * t16.clone();
+    let t86 = t19.clone() + t85;
+    let t87 = t14.clone() + t86;
+    let t88 = t84 + t87;
+    let t89 = t83 + t88;
+    let t90 = t82 + t89;
+    let t91 = t81 + t90;
+    let t92 = t1.clone() + t91;
+    let t93 = t92 - vars[13].clone();
+    let t94 = AF::from_canonical_u64(3) * t1.clone();
+    let t95 = AF::two() * t4.clone();
+    let t96 = AF::from_canonical_u64(5) * t7.clone();
+    let t97 = AF::from_canonical_u64(7) * t9.clone();
+    let t98 = AF::from_canonical_u64(4) * t14.clone();
+    let t99 = t16.clone() + t19.clone();
+    let t100 = t98 + t99;
+    let t101 = t12.clone() + t100;
+    let t102 = t97 + t101;
+    let t103 = t96 + t102;
+    let t104 = t95 + t103;
+    let t105 = t94 + t104;
+    let t106 = t105 - vars[14].clone();
+    let t107 = AF::two() * t1;
+    let t108 = AF::from_canonical_u64(5) * t4;
+    let t109 = AF::from_canonical_u64(7) * t7;
+    let t110 = AF::from_canonical_u64(4) * t12;
+    let t111 = AF::from_canonical_u64(3) * t19;
+    let t112 = t16 + t111;
+    let t113 = t14 + t112;
+    let t114 = t110 + t113;
+    let t115 = t9 + t114;
+    let t116 = t109 + t115;
+    let t117 = t108 + t116;
+    let t118 = t107 + t117;
+    let t119 = t118 - vars[15].clone();
+    [t28, t41, t54, t67, t80, t93, t106, t119]
+}
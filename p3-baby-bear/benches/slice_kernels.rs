@@ -0,0 +1,89 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use p3_baby_bear::BabyBear;
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{
+    add_linear_combination_in_place, add_scaled_slice_in_place_packed, dot_product_slices,
+    mul_slices_in_place, Field,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+type Base = BabyBear;
+type EF4 = BinomialExtensionField<BabyBear, 4>;
+
+const LEN: usize = 1 << 16;
+const NUM_SLICES: usize = 16;
+
+fn rand_vec<F: Field>(rng: &mut impl Rng, n: usize) -> Vec<F>
+where
+    Standard: Distribution<F>,
+{
+    (0..n).map(|_| rng.gen()).collect()
+}
+
+fn bench_slice_kernels<F: Field>(c: &mut Criterion, name: &str)
+where
+    Standard: Distribution<F>,
+{
+    let mut rng = rand::thread_rng();
+    let x = rand_vec::<F>(&mut rng, LEN);
+    let y = rand_vec::<F>(&mut rng, LEN);
+    let s = rng.gen::<F>();
+
+    c.bench_function(
+        &format!("{} add_scaled_slice_in_place_packed 2^16", name),
+        |b| {
+            b.iter_batched(
+                || x.clone(),
+                |mut x| {
+                    add_scaled_slice_in_place_packed(&mut x, &y, s);
+                    x
+                },
+                BatchSize::LargeInput,
+            )
+        },
+    );
+
+    c.bench_function(&format!("{} mul_slices_in_place 2^16", name), |b| {
+        b.iter_batched(
+            || x.clone(),
+            |mut x| {
+                mul_slices_in_place(&mut x, &y);
+                x
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function(&format!("{} dot_product_slices 2^16", name), |b| {
+        b.iter(|| dot_product_slices(black_box(&x), black_box(&y)))
+    });
+
+    let ys: Vec<Vec<F>> = (0..NUM_SLICES).map(|_| rand_vec(&mut rng, LEN)).collect();
+    let ys_refs: Vec<&[F]> = ys.iter().map(|y| y.as_slice()).collect();
+    let weights = rand_vec::<F>(&mut rng, NUM_SLICES);
+    c.bench_function(
+        &format!("{} add_linear_combination_in_place 16 x 2^16", name),
+        |b| {
+            b.iter_batched(
+                || x.clone(),
+                |mut x| {
+                    add_linear_combination_in_place(&mut x, &ys_refs, &weights);
+                    x
+                },
+                BatchSize::LargeInput,
+            )
+        },
+    );
+}
+
+fn bench_babybear(c: &mut Criterion) {
+    bench_slice_kernels::<Base>(c, "BabyBear");
+}
+
+fn bench_quartic_extension(c: &mut Criterion) {
+    bench_slice_kernels::<EF4>(c, "BinomialExtensionField<BabyBear, 4>");
+}
+
+criterion_group!(slice_kernels, bench_babybear, bench_quartic_extension);
+criterion_main!(slice_kernels);
@@ -0,0 +1,116 @@
+//! Compares interpreting a `SymbolicField` constraint system against the straight-line Rust
+//! generated from it by `SymbolicDag::to_rust`.
+//!
+//! The generated code is checked in at `benches/generated/symbolic_round.rs`. If the constraints
+//! or the code generator change, rerun this benchmark with `P3_REGENERATE_CODEGEN=1` set to
+//! rewrite it.
+
+use std::path::Path;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, Field, NodeId, PackedField, SymbolicDag, SymbolicField};
+use rand::Rng;
+
+include!("generated/symbolic_round.rs");
+
+type F = BabyBear;
+type S = SymbolicField<F, usize>;
+
+const WIDTH: usize = 8;
+const GENERATED_PATH: &str = "benches/generated/symbolic_round.rs";
+const GENERATED_HEADER: &str =
+    "// @generated by benches/symbolic_codegen.rs with P3_REGENERATE_CODEGEN=1. Do not edit.\n\n";
+
+/// Transition constraints for one Poseidon-style full round over `WIDTH` elements: variables
+/// `0..WIDTH` are the current state and `WIDTH..2 * WIDTH` the next state, which must equal a
+/// circulant mix of `(state[i] + c[i])^7`.
+fn round_constraints() -> Vec<S> {
+    let state: Vec<S> = (0..WIDTH).map(S::Variable).collect();
+    let next: Vec<S> = (WIDTH..2 * WIDTH).map(S::Variable).collect();
+    let sboxed: Vec<S> = state
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let c = S::from_canonical_u32(0x1234_5678u32.wrapping_mul(i as u32 + 1) % (1 << 30));
+            (s.clone() + c).exp_const_u64::<7>()
+        })
+        .collect();
+    const CIRCULANT: [u32; WIDTH] = [2, 3, 1, 1, 4, 1, 7, 5];
+    (0..WIDTH)
+        .map(|j| {
+            let mixed: S = (0..WIDTH)
+                .map(|i| {
+                    sboxed[i].clone() * S::from_canonical_u32(CIRCULANT[(WIDTH + i - j) % WIDTH])
+                })
+                .sum();
+            mixed - next[j].clone()
+        })
+        .collect()
+}
+
+fn check_generated_code(dag: &SymbolicDag<F, usize>, roots: &[NodeId]) {
+    let expected = format!(
+        "{GENERATED_HEADER}{}",
+        dag.to_rust("symbolic_round", roots, |&v| v)
+    );
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(GENERATED_PATH);
+    if std::env::var_os("P3_REGENERATE_CODEGEN").is_some() {
+        std::fs::write(&path, expected).unwrap();
+        panic!("regenerated {GENERATED_PATH}; rerun the benchmark to use it");
+    }
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        expected,
+        "{GENERATED_PATH} is stale; rerun with P3_REGENERATE_CODEGEN=1"
+    );
+}
+
+fn bench_codegen<AF: AbstractField<F = F> + PartialEq>(
+    c: &mut Criterion,
+    name: &str,
+    vars: Vec<AF>,
+) {
+    let constraints = round_constraints();
+    let mut dag = SymbolicDag::new();
+    let roots: Vec<NodeId> = constraints.iter().map(|e| dag.insert(e)).collect();
+    check_generated_code(&dag, &roots);
+
+    let interpreted: Vec<AF> = constraints
+        .iter()
+        .map(|e| e.eval(|&v| vars[v].clone()))
+        .collect();
+    assert_eq!(symbolic_round(&vars).to_vec(), interpreted);
+    assert_eq!(dag.eval(&roots, |&v| vars[v].clone()), interpreted);
+
+    c.bench_function(&format!("{name} symbolic round, interpreted tree"), |b| {
+        b.iter(|| {
+            constraints
+                .iter()
+                .map(|e| e.eval(|&v| black_box(&vars)[v].clone()))
+                .collect::<Vec<_>>()
+        })
+    });
+    c.bench_function(&format!("{name} symbolic round, interpreted DAG"), |b| {
+        b.iter(|| dag.eval(&roots, |&v| black_box(&vars)[v].clone()))
+    });
+    c.bench_function(&format!("{name} symbolic round, generated"), |b| {
+        b.iter(|| symbolic_round(black_box(&vars)))
+    });
+}
+
+fn bench_scalar(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let vars: Vec<F> = (0..2 * WIDTH).map(|_| rng.gen()).collect();
+    bench_codegen(c, "BabyBear", vars);
+}
+
+fn bench_packed(c: &mut Criterion) {
+    type P = <F as Field>::Packing;
+    let mut rng = rand::thread_rng();
+    let vars: Vec<P> = (0..2 * WIDTH).map(|_| P::from_fn(|_| rng.gen())).collect();
+    bench_codegen(c, "BabyBear packed", vars);
+}
+
+criterion_group!(symbolic_codegen, bench_scalar, bench_packed);
+criterion_main!(symbolic_codegen);
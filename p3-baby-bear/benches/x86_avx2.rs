@@ -0,0 +1,283 @@
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+mod bench {
+    use criterion::{black_box, criterion_group, BatchSize, Criterion};
+    use p3_baby_bear::{BabyBear, PackedMontyField31AVX2};
+    use p3_field::AbstractField;
+    use rand::Rng;
+
+    type Base = BabyBear;
+
+    fn field_operations(c: &mut Criterion) {
+        let mut rng = rand::thread_rng();
+
+        c.bench_function("scalar add", |b| {
+            let x = [rng.gen::<Base>(); 8];
+            let y = [rng.gen::<Base>(); 8];
+            let mut res = [Base::zero(); 8];
+            b.iter(|| {
+                for i in 0..8 {
+                    res[i] = black_box(black_box(x[i]) + black_box(y[i]));
+                }
+            })
+        });
+
+        c.bench_function("scalar mul", |b| {
+            let x = [rng.gen::<Base>(); 8];
+            let y = [rng.gen::<Base>(); 8];
+            let mut res = [Base::zero(); 8];
+            b.iter(|| {
+                for i in 0..8 {
+                    res[i] = black_box(black_box(x[i]) * black_box(y[i]));
+                }
+            })
+        });
+
+        c.bench_function("scalar sub", |b| {
+            let x = [rng.gen::<Base>(); 8];
+            let y = [rng.gen::<Base>(); 8];
+            let mut res = [Base::zero(); 8];
+            b.iter(|| {
+                for i in 0..8 {
+                    res[i] = black_box(black_box(x[i]) - black_box(y[i]));
+                }
+            })
+        });
+
+        c.bench_function("scalar square", |b| {
+            let x = [rng.gen::<Base>(); 8];
+            let mut res = [Base::zero(); 8];
+            b.iter(|| {
+                for i in 0..8 {
+                    res[i] = black_box(black_box(x[i]).square());
+                }
+            })
+        });
+
+        c.bench_function("avx2 add", |b| {
+            let x = [rng.gen::<Base>(); 8];
+            let y = [rng.gen::<Base>(); 8];
+            let mut res = PackedMontyField31AVX2([Base::zero(); 8]);
+            b.iter(|| {
+                res = black_box(
+                    PackedMontyField31AVX2(black_box(x)) + PackedMontyField31AVX2(black_box(y)),
+                )
+            })
+        });
+
+        c.bench_function("avx2 mul", |b| {
+            let x = [rng.gen::<Base>(); 8];
+            let y = [rng.gen::<Base>(); 8];
+            let mut res = PackedMontyField31AVX2([Base::zero(); 8]);
+            b.iter(|| {
+                res = black_box(
+                    PackedMontyField31AVX2(black_box(x)) * PackedMontyField31AVX2(black_box(y)),
+                )
+            })
+        });
+
+        c.bench_function("avx2 sub", |b| {
+            let x = [rng.gen::<Base>(); 8];
+            let y = [rng.gen::<Base>(); 8];
+            let mut res = PackedMontyField31AVX2([Base::zero(); 8]);
+            b.iter(|| {
+                res = black_box(
+                    PackedMontyField31AVX2(black_box(x)) - PackedMontyField31AVX2(black_box(y)),
+                )
+            })
+        });
+
+        c.bench_function("avx2 square", |b| {
+            let x = [rng.gen::<Base>(); 8];
+            let mut res = PackedMontyField31AVX2([Base::zero(); 8]);
+            b.iter(|| res = black_box(PackedMontyField31AVX2(black_box(x)).square()))
+        });
+    }
+
+    fn bench_latency_throughputs(c: &mut Criterion, iteration: u32) {
+        let mut rng = rand::thread_rng();
+
+        c.bench_function(&format!("scalar add-latency {}k", iteration / 1000), |b| {
+            b.iter_batched(
+                || {
+                    let mut vec = Vec::new();
+                    for _ in 0..iteration {
+                        vec.push(rng.gen::<Base>())
+                    }
+                    vec
+                },
+                |x| x.iter().fold(Base::zero(), |x, y| x + *y),
+                BatchSize::SmallInput,
+            )
+        });
+
+        c.bench_function(
+            &format!("scalar add-throughput {}k", iteration / 1000),
+            |b| {
+                let (mut w, mut x, mut y, mut z) =
+                    (Base::zero(), Base::zero(), Base::zero(), Base::zero());
+                b.iter_batched(
+                    || {
+                        (
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                        )
+                    },
+                    |(a, b, c, d, e, f, g, h)| {
+                        for _ in 0..iteration {
+                            (w, x, y, z) = (a + e, b + f, c + g, d + h);
+                        }
+                        (w, x, y, z)
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+
+        c.bench_function(&format!("scalar mul-latency {}k", iteration / 1000), |b| {
+            b.iter_batched(
+                || {
+                    let mut vec = Vec::new();
+                    for _ in 0..iteration {
+                        vec.push(rng.gen::<Base>())
+                    }
+                    vec
+                },
+                |x| x.iter().fold(Base::one(), |x, y| x * *y),
+                BatchSize::SmallInput,
+            )
+        });
+
+        c.bench_function(
+            &format!("scalar mul-throughput {}k", iteration / 1000),
+            |b| {
+                let (mut w, mut x, mut y, mut z) =
+                    (Base::zero(), Base::zero(), Base::zero(), Base::zero());
+                b.iter_batched(
+                    || {
+                        (
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                            rng.gen::<Base>(),
+                        )
+                    },
+                    |(a, b, c, d, e, f, g, h)| {
+                        for _ in 0..iteration {
+                            (w, x, y, z) = (a * e, b * f, c * g, d * h);
+                        }
+                        (w, x, y, z)
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+
+        c.bench_function(&format!("avx2 add-latency {}k", iteration / 1000), |b| {
+            let mut res = Base::zero();
+            b.iter_batched(
+                || {
+                    let mut vec = Vec::new();
+                    for _ in 0..iteration / 8 {
+                        vec.push(PackedMontyField31AVX2([rng.gen::<Base>(); 8]))
+                    }
+                    vec
+                },
+                |x| {
+                    let m = x
+                        .iter()
+                        .fold(PackedMontyField31AVX2([Base::zero(); 8]), |x, y| x + *y);
+                    for i in 0..8 {
+                        res += m.0[i];
+                    }
+                    res
+                },
+                BatchSize::SmallInput,
+            )
+        });
+
+        c.bench_function(&format!("avx2 add-throughput {}k", iteration / 1000), |b| {
+            let mut res = PackedMontyField31AVX2([Base::zero(); 8]);
+            b.iter_batched(
+                || {
+                    (
+                        PackedMontyField31AVX2([rng.gen::<Base>(); 8]),
+                        PackedMontyField31AVX2([rng.gen::<Base>(); 8]),
+                    )
+                },
+                |(a, b)| {
+                    for _ in 0..iteration / 8 {
+                        res = black_box(a) + black_box(b);
+                    }
+                    res
+                },
+                BatchSize::SmallInput,
+            )
+        });
+
+        c.bench_function(&format!("avx2 mul-latency {}k", iteration / 1000), |b| {
+            let mut res = Base::one();
+            b.iter_batched(
+                || {
+                    let mut vec = Vec::new();
+                    for _ in 0..iteration / 8 {
+                        vec.push(PackedMontyField31AVX2([rng.gen::<Base>(); 8]))
+                    }
+                    vec
+                },
+                |x| {
+                    let m = x
+                        .iter()
+                        .fold(PackedMontyField31AVX2([Base::one(); 8]), |x, y| x * *y);
+                    for i in 0..8 {
+                        res *= m.0[i];
+                    }
+                    res
+                },
+                BatchSize::SmallInput,
+            )
+        });
+
+        c.bench_function(&format!("avx2 mul-throughput {}k", iteration / 1000), |b| {
+            let mut res = PackedMontyField31AVX2([Base::zero(); 8]);
+            b.iter_batched(
+                || {
+                    (
+                        PackedMontyField31AVX2([rng.gen::<Base>(); 8]),
+                        PackedMontyField31AVX2([rng.gen::<Base>(); 8]),
+                    )
+                },
+                |(a, b)| {
+                    for _ in 0..iteration / 8 {
+                        res = black_box(a) * black_box(b);
+                    }
+                    res
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    fn avx2_vs_scalar(c: &mut Criterion) {
+        field_operations(c);
+        bench_latency_throughputs(c, 10000);
+        bench_latency_throughputs(c, 100000);
+    }
+
+    criterion_group!(avx2, avx2_vs_scalar,);
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+criterion::criterion_main!(bench::avx2);
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "avx2")))]
+fn main() {}
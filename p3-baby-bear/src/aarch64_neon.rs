@@ -9,29 +9,41 @@ use p3_field::{AbstractField, Field, PackedField};
 use rand::distributions::{Distribution, Standard};
 use rand::Rng;
 
-use crate::BabyBear;
+use crate::{BabyBearParameters, KoalaBearParameters, MontyField31, MontyParameters};
 
 const WIDTH: usize = 4;
-const P: uint32x4_t = unsafe { transmute::<[u32; WIDTH], _>([0x78000001; WIDTH]) };
-const MU: uint32x4_t = unsafe { transmute::<[u32; WIDTH], _>([0x08000001; WIDTH]) };
 const TOP_BIT: uint32x4_t = unsafe { transmute::<[u32; WIDTH], _>([0x80000000; WIDTH]) };
 
-/// Vectorized NEON implementation of `BabyBear` arithmetic.
+/// Vectorized NEON implementation of `MontyField31` arithmetic.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(transparent)] // This needed to make `transmute`s safe.
-pub struct PackedBabyBearNeon(pub [BabyBear; WIDTH]);
+pub struct PackedMontyField31Neon<MP: MontyParameters>(pub [MontyField31<MP>; WIDTH]);
 
-impl PackedBabyBearNeon {
+pub type PackedBabyBearNeon = PackedMontyField31Neon<BabyBearParameters>;
+pub type PackedKoalaBearNeon = PackedMontyField31Neon<KoalaBearParameters>;
+
+/// The constants of a `MontyParameters` field, broadcast to every lane.
+trait MontyParametersNeon {
+    const PACKED_P: uint32x4_t;
+    const PACKED_MU: uint32x4_t;
+}
+
+impl<MP: MontyParameters> MontyParametersNeon for MP {
+    const PACKED_P: uint32x4_t = unsafe { transmute::<[u32; WIDTH], _>([MP::PRIME; WIDTH]) };
+    const PACKED_MU: uint32x4_t = unsafe { transmute::<[u32; WIDTH], _>([MP::MONTY_MU; WIDTH]) };
+}
+
+impl<MP: MontyParameters> PackedMontyField31Neon<MP> {
     #[inline]
     #[must_use]
     /// Get an arch-specific vector representing the packed values.
     fn to_vector(self) -> uint32x4_t {
         unsafe {
-            // Safety: `BabyBear` is `repr(transparent)` so it can be transmuted to `u32`. It
-            // follows that `[BabyBear; WIDTH]` can be transmuted to `[u32; WIDTH]`, which can be
+            // Safety: `MontyField31` is `repr(transparent)` so it can be transmuted to `u32`. It
+            // follows that `[MontyField31<MP>; WIDTH]` can be transmuted to `[u32; WIDTH]`, which can be
             // transmuted to `uint32x4_t`, since arrays are guaranteed to be contiguous in memory.
-            // Finally `PackedBabyBearNeon` is `repr(transparent)` so it can be transmuted to
-            // `[BabyBear; WIDTH]`.
+            // Finally `PackedMontyField31Neon` is `repr(transparent)` so it can be transmuted to
+            // `[MontyField31<MP>; WIDTH]`.
             transmute(self)
         }
     }
@@ -40,34 +52,34 @@ impl PackedBabyBearNeon {
     #[must_use]
     /// Make a packed field vector from an arch-specific vector.
     ///
-    /// SAFETY: The caller must ensure that each element of `vector` represents a valid `BabyBear`.
+    /// SAFETY: The caller must ensure that each element of `vector` represents a valid `MontyField31`.
     /// In particular, each element of vector must be in `0..P` (canonical form).
     unsafe fn from_vector(vector: uint32x4_t) -> Self {
         // Safety: It is up to the user to ensure that elements of `vector` represent valid
-        // `BabyBear` values. We must only reason about memory representations. `uint32x4_t` can be
+        // `MontyField31` values. We must only reason about memory representations. `uint32x4_t` can be
         // transmuted to `[u32; WIDTH]` (since arrays elements are contiguous in memory), which can
-        // be transmuted to `[BabyBear; WIDTH]` (since `BabyBear` is `repr(transparent)`), which in
-        // turn can be transmuted to `PackedBabyBearNeon` (since `PackedBabyBearNeon` is also
+        // be transmuted to `[MontyField31<MP>; WIDTH]` (since `MontyField31` is `repr(transparent)`), which in
+        // turn can be transmuted to `PackedMontyField31Neon` (since `PackedMontyField31Neon` is also
         // `repr(transparent)`).
         transmute(vector)
     }
 
     /// Copy `value` to all positions in a packed vector. This is the same as
-    /// `From<BabyBear>::from`, but `const`.
+    /// `From<MontyField31<MP>>::from`, but `const`.
     #[inline]
     #[must_use]
-    const fn broadcast(value: BabyBear) -> Self {
+    const fn broadcast(value: MontyField31<MP>) -> Self {
         Self([value; WIDTH])
     }
 }
 
-impl Add for PackedBabyBearNeon {
+impl<MP: MontyParameters> Add for PackedMontyField31Neon<MP> {
     type Output = Self;
     #[inline]
     fn add(self, rhs: Self) -> Self {
         let lhs = self.to_vector();
         let rhs = rhs.to_vector();
-        let res = add(lhs, rhs);
+        let res = add::<MP>(lhs, rhs);
         unsafe {
             // Safety: `add` returns values in canonical form when given values in canonical form.
             Self::from_vector(res)
@@ -75,13 +87,13 @@ impl Add for PackedBabyBearNeon {
     }
 }
 
-impl Mul for PackedBabyBearNeon {
+impl<MP: MontyParameters> Mul for PackedMontyField31Neon<MP> {
     type Output = Self;
     #[inline]
     fn mul(self, rhs: Self) -> Self {
         let lhs = self.to_vector();
         let rhs = rhs.to_vector();
-        let res = mul(lhs, rhs);
+        let res = mul::<MP>(lhs, rhs);
         unsafe {
             // Safety: `mul` returns values in canonical form when given values in canonical form.
             Self::from_vector(res)
@@ -89,12 +101,12 @@ impl Mul for PackedBabyBearNeon {
     }
 }
 
-impl Neg for PackedBabyBearNeon {
+impl<MP: MontyParameters> Neg for PackedMontyField31Neon<MP> {
     type Output = Self;
     #[inline]
     fn neg(self) -> Self {
         let val = self.to_vector();
-        let res = neg(val);
+        let res = neg::<MP>(val);
         unsafe {
             // Safety: `neg` returns values in canonical form when given values in canonical form.
             Self::from_vector(res)
@@ -102,13 +114,13 @@ impl Neg for PackedBabyBearNeon {
     }
 }
 
-impl Sub for PackedBabyBearNeon {
+impl<MP: MontyParameters> Sub for PackedMontyField31Neon<MP> {
     type Output = Self;
     #[inline]
     fn sub(self, rhs: Self) -> Self {
         let lhs = self.to_vector();
         let rhs = rhs.to_vector();
-        let res = sub(lhs, rhs);
+        let res = sub::<MP>(lhs, rhs);
         unsafe {
             // Safety: `sub` returns values in canonical form when given values in canonical form.
             Self::from_vector(res)
@@ -135,18 +147,18 @@ fn confuse_compiler(x: uint32x4_t) -> uint32x4_t {
         // You may ask, doesn't it defeat the point of the inline asm block to tell the compiler
         // what it does? The answer is that we still inhibit the transform we want to avoid, so
         // apparently not. Idk, LLVM works in mysterious ways.
-        if transmute::<_, [u32; 4]>(x) != transmute::<_, [u32; 4]>(y) {
+        if transmute::<uint32x4_t, [u32; 4]>(x) != transmute::<uint32x4_t, [u32; 4]>(y) {
             unreachable_unchecked();
         }
     }
     y
 }
 
-/// Add two vectors of Baby Bear field elements in canonical form.
+/// Add two vectors of field elements in canonical form.
 /// If the inputs are not in canonical form, the result is undefined.
 #[inline]
 #[must_use]
-fn add(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+fn add<MP: MontyParametersNeon>(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
     // We want this to compile to:
     //      add   t.4s, lhs.4s, rhs.4s
     //      sub   u.4s, t.4s, P.4s
@@ -165,7 +177,7 @@ fn add(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
     unsafe {
         // Safety: If this code got compiled then NEON intrinsics are available.
         let t = aarch64::vaddq_u32(lhs, rhs);
-        let u = aarch64::vsubq_u32(t, P);
+        let u = aarch64::vsubq_u32(t, MP::PACKED_P);
         aarch64::vminq_u32(t, u)
     }
 }
@@ -189,7 +201,7 @@ fn mul_31x31_to_hi_31(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
 // MONTGOMERY MULTIPLICATION
 //   This implementation is based on [1] but with minor changes. The reduction is as follows:
 //
-// Constants: P = 2^31 - 2^27 + 1
+// Constants: P < 2^31 prime
 //            B = 2^31
 //            mu = P^-1 mod B
 // Input: 0 <= C < P B
@@ -231,7 +243,7 @@ fn monty_mul_hi(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
 #[allow(non_snake_case)]
 #[inline]
 #[must_use]
-fn monty_mul_lo(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+fn monty_mul_lo<MP: MontyParametersNeon>(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
     // We want this to compile to:
     //      mul      rhs_mu_mod_2pow32.rs, rhs.4s, MU.4s
     //      mul      mu_C_mod_2pow32.rs, lhs.4s, rhs_mu_mod_2pow32.4s
@@ -242,31 +254,31 @@ fn monty_mul_lo(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
     //          (2->1) 11 cyc
     unsafe {
         // Safety: If this code got compiled then NEON intrinsics are available.
-        let rhs_mu_mod_2pow32 = aarch64::vmulq_u32(rhs, MU);
+        let rhs_mu_mod_2pow32 = aarch64::vmulq_u32(rhs, MP::PACKED_MU);
         let mu_C_mod_2pow32 = aarch64::vmulq_u32(lhs, rhs_mu_mod_2pow32);
         let mu_C_mod_2pow31 = aarch64::vbicq_u32(mu_C_mod_2pow32, TOP_BIT);
-        mul_31x31_to_hi_31(mu_C_mod_2pow31, P)
+        mul_31x31_to_hi_31(mu_C_mod_2pow31, MP::PACKED_P)
     }
 }
 
-/// Multiply vectors of Baby Bear field elements in canonical form.
+/// Multiply vectors of field elements in canonical form.
 /// If the inputs are not in canonical form, the result is undefined.
 #[inline]
 #[must_use]
-fn mul(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+fn mul<MP: MontyParametersNeon>(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
     // throughput: 2 cyc/vec (2 els/cyc)
     // latency: (1->1) 13 cyc
     //          (2->1) 16 cyc
     let hi = monty_mul_hi(lhs, rhs);
-    let lo = monty_mul_lo(lhs, rhs);
-    sub(hi, lo)
+    let lo = monty_mul_lo::<MP>(lhs, rhs);
+    sub::<MP>(hi, lo)
 }
 
-/// Negate a vector of Baby Bear field elements in canonical form.
+/// Negate a vector of field elements in canonical form.
 /// If the inputs are not in canonical form, the result is undefined.
 #[inline]
 #[must_use]
-fn neg(val: uint32x4_t) -> uint32x4_t {
+fn neg<MP: MontyParametersNeon>(val: uint32x4_t) -> uint32x4_t {
     // We want this to compile to:
     //      sub   t.4s, P.4s, val.4s
     //      cmeq  is_zero.4s, val.4s, #0
@@ -282,17 +294,17 @@ fn neg(val: uint32x4_t) -> uint32x4_t {
     //   We return `r := t & ~is_zero`, which is `t` if `val > 0` and `0` otherwise, as desired.
     unsafe {
         // Safety: If this code got compiled then NEON intrinsics are available.
-        let t = aarch64::vsubq_u32(P, val);
+        let t = aarch64::vsubq_u32(MP::PACKED_P, val);
         let is_zero = aarch64::vceqzq_u32(val);
         aarch64::vbicq_u32(t, is_zero)
     }
 }
 
-/// Subtract vectors of Baby Bear field elements in canonical form.
+/// Subtract vectors of field elements in canonical form.
 /// If the inputs are not in canonical form, the result is undefined.
 #[inline]
 #[must_use]
-fn sub(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+fn sub<MP: MontyParametersNeon>(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
     // We want this to compile to:
     //      sub   res.4s, lhs.4s, rhs.4s
     //      cmhi  underflow.4s, rhs.4s, lhs.4s
@@ -315,46 +327,46 @@ fn sub(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
         // We really want to emit a `mls` instruction here. The compiler knows that `underflow` is
         // either 0 or -1 and will try to do an `and` and `add` instead, which is slower on the M1.
         // The `confuse_compiler` prevents this "optimization".
-        aarch64::vmlsq_u32(diff, confuse_compiler(underflow), P)
+        aarch64::vmlsq_u32(diff, confuse_compiler(underflow), MP::PACKED_P)
     }
 }
 
-impl From<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> From<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     #[inline]
-    fn from(value: BabyBear) -> Self {
+    fn from(value: MontyField31<MP>) -> Self {
         Self::broadcast(value)
     }
 }
 
-impl Default for PackedBabyBearNeon {
+impl<MP: MontyParameters> Default for PackedMontyField31Neon<MP> {
     #[inline]
     fn default() -> Self {
-        BabyBear::default().into()
+        MontyField31::<MP>::default().into()
     }
 }
 
-impl AddAssign for PackedBabyBearNeon {
+impl<MP: MontyParameters> AddAssign for PackedMontyField31Neon<MP> {
     #[inline]
     fn add_assign(&mut self, rhs: Self) {
         *self = *self + rhs;
     }
 }
 
-impl MulAssign for PackedBabyBearNeon {
+impl<MP: MontyParameters> MulAssign for PackedMontyField31Neon<MP> {
     #[inline]
     fn mul_assign(&mut self, rhs: Self) {
         *self = *self * rhs;
     }
 }
 
-impl SubAssign for PackedBabyBearNeon {
+impl<MP: MontyParameters> SubAssign for PackedMontyField31Neon<MP> {
     #[inline]
     fn sub_assign(&mut self, rhs: Self) {
         *self = *self - rhs;
     }
 }
 
-impl Sum for PackedBabyBearNeon {
+impl<MP: MontyParameters> Sum for PackedMontyField31Neon<MP> {
     #[inline]
     fn sum<I>(iter: I) -> Self
     where
@@ -364,7 +376,7 @@ impl Sum for PackedBabyBearNeon {
     }
 }
 
-impl Product for PackedBabyBearNeon {
+impl<MP: MontyParameters> Product for PackedMontyField31Neon<MP> {
     #[inline]
     fn product<I>(iter: I) -> Self
     where
@@ -374,20 +386,20 @@ impl Product for PackedBabyBearNeon {
     }
 }
 
-impl AbstractField for PackedBabyBearNeon {
-    type F = BabyBear;
+impl<MP: MontyParameters> AbstractField for PackedMontyField31Neon<MP> {
+    type F = MontyField31<MP>;
 
     fn zero() -> Self {
-        Self::broadcast(BabyBear::zero())
+        Self::broadcast(MontyField31::<MP>::zero())
     }
     fn one() -> Self {
-        Self::broadcast(BabyBear::one())
+        Self::broadcast(MontyField31::<MP>::one())
     }
     fn two() -> Self {
-        Self::broadcast(BabyBear::two())
+        Self::broadcast(MontyField31::<MP>::two())
     }
     fn neg_one() -> Self {
-        Self::broadcast(BabyBear::neg_one())
+        Self::broadcast(MontyField31::<MP>::neg_one())
     }
 
     #[inline]
@@ -397,146 +409,146 @@ impl AbstractField for PackedBabyBearNeon {
 
     #[inline]
     fn from_bool(b: bool) -> Self {
-        BabyBear::from_bool(b).into()
+        MontyField31::<MP>::from_bool(b).into()
     }
     #[inline]
     fn from_canonical_u8(n: u8) -> Self {
-        BabyBear::from_canonical_u8(n).into()
+        MontyField31::<MP>::from_canonical_u8(n).into()
     }
     #[inline]
     fn from_canonical_u16(n: u16) -> Self {
-        BabyBear::from_canonical_u16(n).into()
+        MontyField31::<MP>::from_canonical_u16(n).into()
     }
     #[inline]
     fn from_canonical_u32(n: u32) -> Self {
-        BabyBear::from_canonical_u32(n).into()
+        MontyField31::<MP>::from_canonical_u32(n).into()
     }
     #[inline]
     fn from_canonical_u64(n: u64) -> Self {
-        BabyBear::from_canonical_u64(n).into()
+        MontyField31::<MP>::from_canonical_u64(n).into()
     }
     #[inline]
     fn from_canonical_usize(n: usize) -> Self {
-        BabyBear::from_canonical_usize(n).into()
+        MontyField31::<MP>::from_canonical_usize(n).into()
     }
 
     #[inline]
     fn from_wrapped_u32(n: u32) -> Self {
-        BabyBear::from_wrapped_u32(n).into()
+        MontyField31::<MP>::from_wrapped_u32(n).into()
     }
     #[inline]
     fn from_wrapped_u64(n: u64) -> Self {
-        BabyBear::from_wrapped_u64(n).into()
+        MontyField31::<MP>::from_wrapped_u64(n).into()
     }
 
     #[inline]
     fn generator() -> Self {
-        BabyBear::generator().into()
+        MontyField31::<MP>::generator().into()
     }
 }
 
-impl Add<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> Add<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     type Output = Self;
     #[inline]
-    fn add(self, rhs: BabyBear) -> Self {
+    fn add(self, rhs: MontyField31<MP>) -> Self {
         self + Self::from(rhs)
     }
 }
 
-impl Mul<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> Mul<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     type Output = Self;
     #[inline]
-    fn mul(self, rhs: BabyBear) -> Self {
+    fn mul(self, rhs: MontyField31<MP>) -> Self {
         self * Self::from(rhs)
     }
 }
 
-impl Sub<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> Sub<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     type Output = Self;
     #[inline]
-    fn sub(self, rhs: BabyBear) -> Self {
+    fn sub(self, rhs: MontyField31<MP>) -> Self {
         self - Self::from(rhs)
     }
 }
 
-impl AddAssign<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> AddAssign<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     #[inline]
-    fn add_assign(&mut self, rhs: BabyBear) {
+    fn add_assign(&mut self, rhs: MontyField31<MP>) {
         *self += Self::from(rhs)
     }
 }
 
-impl MulAssign<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> MulAssign<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     #[inline]
-    fn mul_assign(&mut self, rhs: BabyBear) {
+    fn mul_assign(&mut self, rhs: MontyField31<MP>) {
         *self *= Self::from(rhs)
     }
 }
 
-impl SubAssign<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> SubAssign<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     #[inline]
-    fn sub_assign(&mut self, rhs: BabyBear) {
+    fn sub_assign(&mut self, rhs: MontyField31<MP>) {
         *self -= Self::from(rhs)
     }
 }
 
-impl Sum<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> Sum<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     #[inline]
     fn sum<I>(iter: I) -> Self
     where
-        I: Iterator<Item = BabyBear>,
+        I: Iterator<Item = MontyField31<MP>>,
     {
-        iter.sum::<BabyBear>().into()
+        iter.sum::<MontyField31<MP>>().into()
     }
 }
 
-impl Product<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> Product<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     #[inline]
     fn product<I>(iter: I) -> Self
     where
-        I: Iterator<Item = BabyBear>,
+        I: Iterator<Item = MontyField31<MP>>,
     {
-        iter.product::<BabyBear>().into()
+        iter.product::<MontyField31<MP>>().into()
     }
 }
 
-impl Div<BabyBear> for PackedBabyBearNeon {
+impl<MP: MontyParameters> Div<MontyField31<MP>> for PackedMontyField31Neon<MP> {
     type Output = Self;
     #[allow(clippy::suspicious_arithmetic_impl)]
     #[inline]
-    fn div(self, rhs: BabyBear) -> Self {
+    fn div(self, rhs: MontyField31<MP>) -> Self {
         self * rhs.inverse()
     }
 }
 
-impl Add<PackedBabyBearNeon> for BabyBear {
-    type Output = PackedBabyBearNeon;
+impl<MP: MontyParameters> Add<PackedMontyField31Neon<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31Neon<MP>;
     #[inline]
-    fn add(self, rhs: PackedBabyBearNeon) -> PackedBabyBearNeon {
-        PackedBabyBearNeon::from(self) + rhs
+    fn add(self, rhs: PackedMontyField31Neon<MP>) -> PackedMontyField31Neon<MP> {
+        PackedMontyField31Neon::from(self) + rhs
     }
 }
 
-impl Mul<PackedBabyBearNeon> for BabyBear {
-    type Output = PackedBabyBearNeon;
+impl<MP: MontyParameters> Mul<PackedMontyField31Neon<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31Neon<MP>;
     #[inline]
-    fn mul(self, rhs: PackedBabyBearNeon) -> PackedBabyBearNeon {
-        PackedBabyBearNeon::from(self) * rhs
+    fn mul(self, rhs: PackedMontyField31Neon<MP>) -> PackedMontyField31Neon<MP> {
+        PackedMontyField31Neon::from(self) * rhs
     }
 }
 
-impl Sub<PackedBabyBearNeon> for BabyBear {
-    type Output = PackedBabyBearNeon;
+impl<MP: MontyParameters> Sub<PackedMontyField31Neon<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31Neon<MP>;
     #[inline]
-    fn sub(self, rhs: PackedBabyBearNeon) -> PackedBabyBearNeon {
-        PackedBabyBearNeon::from(self) - rhs
+    fn sub(self, rhs: PackedMontyField31Neon<MP>) -> PackedMontyField31Neon<MP> {
+        PackedMontyField31Neon::from(self) - rhs
     }
 }
 
-impl Distribution<PackedBabyBearNeon> for Standard {
+impl<MP: MontyParameters> Distribution<PackedMontyField31Neon<MP>> for Standard {
     #[inline]
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> PackedBabyBearNeon {
-        PackedBabyBearNeon(rng.gen())
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> PackedMontyField31Neon<MP> {
+        PackedMontyField31Neon(rng.gen())
     }
 }
 
@@ -575,26 +587,26 @@ fn interleave2(v0: uint32x4_t, v1: uint32x4_t) -> (uint32x4_t, uint32x4_t) {
     }
 }
 
-unsafe impl PackedField for PackedBabyBearNeon {
-    type Scalar = BabyBear;
+unsafe impl<MP: MontyParameters> PackedField for PackedMontyField31Neon<MP> {
+    type Scalar = MontyField31<MP>;
 
     const WIDTH: usize = WIDTH;
 
     #[inline]
-    fn from_slice(slice: &[BabyBear]) -> &Self {
+    fn from_slice(slice: &[MontyField31<MP>]) -> &Self {
         assert_eq!(slice.len(), Self::WIDTH);
         unsafe {
-            // Safety: `[BabyBear; WIDTH]` can be transmuted to `PackedBabyBearNeon` since the
+            // Safety: `[MontyField31<MP>; WIDTH]` can be transmuted to `PackedMontyField31Neon` since the
             // latter is `repr(transparent)`. They have the same alignment, so the reference cast is
             // safe too.
             &*slice.as_ptr().cast()
         }
     }
     #[inline]
-    fn from_slice_mut(slice: &mut [BabyBear]) -> &mut Self {
+    fn from_slice_mut(slice: &mut [MontyField31<MP>]) -> &mut Self {
         assert_eq!(slice.len(), Self::WIDTH);
         unsafe {
-            // Safety: `[BabyBear; WIDTH]` can be transmuted to `PackedBabyBearNeon` since the
+            // Safety: `[MontyField31<MP>; WIDTH]` can be transmuted to `PackedMontyField31Neon` since the
             // latter is `repr(transparent)`. They have the same alignment, so the reference cast is
             // safe too.
             &mut *slice.as_mut_ptr().cast()
@@ -603,17 +615,17 @@ unsafe impl PackedField for PackedBabyBearNeon {
 
     /// Similar to `core:array::from_fn`.
     #[inline]
-    fn from_fn<F: FnMut(usize) -> BabyBear>(f: F) -> Self {
+    fn from_fn<F: FnMut(usize) -> MontyField31<MP>>(f: F) -> Self {
         let vals_arr: [_; WIDTH] = core::array::from_fn(f);
         Self(vals_arr)
     }
 
     #[inline]
-    fn as_slice(&self) -> &[BabyBear] {
+    fn as_slice(&self) -> &[MontyField31<MP>] {
         &self.0[..]
     }
     #[inline]
-    fn as_slice_mut(&mut self) -> &mut [BabyBear] {
+    fn as_slice_mut(&mut self) -> &mut [MontyField31<MP>] {
         &mut self.0[..]
     }
 
@@ -636,6 +648,7 @@ unsafe impl PackedField for PackedBabyBearNeon {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{BabyBear, KoalaBear};
     use p3_field::PrimeField32;
 
     type F = BabyBear;
@@ -646,7 +659,7 @@ mod tests {
     }
 
     fn packed_from_canonical(vals: [u32; WIDTH]) -> P {
-        PackedBabyBearNeon(array_from_canonical(vals))
+        PackedMontyField31Neon(array_from_canonical(vals))
     }
 
     #[test]
@@ -1007,8 +1020,8 @@ mod tests {
         let arr0 = array_from_canonical([0x496d8163, 0x68125590, 0x191cd03b, 0x65b9abef]);
         let arr1 = array_from_canonical([0x6db594e1, 0x5b1f6289, 0x74f15e13, 0x546936a8]);
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 + vec1;
 
         for i in 0..WIDTH {
@@ -1021,8 +1034,8 @@ mod tests {
         let arr0 = [F::zero(), F::one(), F::two(), F::neg_one()];
         let arr1 = array_from_canonical([0x4205a2f6, 0x6f4715f1, 0x29ed7f70, 0x70915992]);
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 + vec1;
 
         for i in 0..WIDTH {
@@ -1035,8 +1048,8 @@ mod tests {
         let arr0 = array_from_canonical([0x5f8329a7, 0x0f1166bb, 0x657bcb14, 0x0185c34a]);
         let arr1 = [F::zero(), F::one(), F::two(), F::neg_one()];
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 + vec1;
 
         for i in 0..WIDTH {
@@ -1049,8 +1062,8 @@ mod tests {
         let arr0 = array_from_canonical([0x6daef778, 0x0e868440, 0x54e7ca64, 0x01a9acab]);
         let arr1 = array_from_canonical([0x45609584, 0x67b63536, 0x0f72a573, 0x234a312e]);
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 - vec1;
 
         for i in 0..WIDTH {
@@ -1063,8 +1076,8 @@ mod tests {
         let arr0 = [F::zero(), F::one(), F::two(), F::neg_one()];
         let arr1 = array_from_canonical([0x4205a2f6, 0x6f4715f1, 0x29ed7f70, 0x70915992]);
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 - vec1;
 
         for i in 0..WIDTH {
@@ -1077,8 +1090,8 @@ mod tests {
         let arr0 = array_from_canonical([0x5f8329a7, 0x0f1166bb, 0x657bcb14, 0x0185c34a]);
         let arr1 = [F::zero(), F::one(), F::two(), F::neg_one()];
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 - vec1;
 
         for i in 0..WIDTH {
@@ -1091,8 +1104,8 @@ mod tests {
         let arr0 = array_from_canonical([0x13655880, 0x5223ea02, 0x5d7f4f90, 0x1494b624]);
         let arr1 = array_from_canonical([0x0ad5743c, 0x44956741, 0x533bc885, 0x7723a25b]);
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 * vec1;
 
         for i in 0..WIDTH {
@@ -1105,8 +1118,8 @@ mod tests {
         let arr0 = [F::zero(), F::one(), F::two(), F::neg_one()];
         let arr1 = array_from_canonical([0x4205a2f6, 0x6f4715f1, 0x29ed7f70, 0x70915992]);
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 * vec1;
 
         for i in 0..WIDTH {
@@ -1119,8 +1132,8 @@ mod tests {
         let arr0 = array_from_canonical([0x5f8329a7, 0x0f1166bb, 0x657bcb14, 0x0185c34a]);
         let arr1 = [F::zero(), F::one(), F::two(), F::neg_one()];
 
-        let vec0 = PackedBabyBearNeon(arr0);
-        let vec1 = PackedBabyBearNeon(arr1);
+        let vec0 = PackedMontyField31Neon(arr0);
+        let vec1 = PackedMontyField31Neon(arr1);
         let vec_res = vec0 * vec1;
 
         for i in 0..WIDTH {
@@ -1132,7 +1145,7 @@ mod tests {
     fn test_neg_vs_scalar() {
         let arr = array_from_canonical([0x1971a7b5, 0x00305be1, 0x52c08410, 0x39cb2586]);
 
-        let vec = PackedBabyBearNeon(arr);
+        let vec = PackedMontyField31Neon(arr);
         let vec_res = -vec;
 
         #[allow(clippy::needless_range_loop)]
@@ -1145,7 +1158,7 @@ mod tests {
     fn test_neg_vs_scalar_special_vals() {
         let arr = [F::zero(), F::one(), F::two(), F::neg_one()];
 
-        let vec = PackedBabyBearNeon(arr);
+        let vec = PackedMontyField31Neon(arr);
         let vec_res = -vec;
 
         #[allow(clippy::needless_range_loop)]
@@ -1153,4 +1166,83 @@ mod tests {
             assert_eq!(vec_res.0[i], -arr[i]);
         }
     }
+
+    #[test]
+    fn test_matches_emulation() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        use crate::neon_emulation;
+
+        let to_array = |v: uint32x4_t| unsafe { transmute::<_, [u32; WIDTH]>(v) };
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            let lhs: P = rng.gen();
+            let rhs: P = rng.gen();
+            let (l, r) = (lhs.to_vector(), rhs.to_vector());
+            let (la, ra) = (to_array(l), to_array(r));
+            assert_eq!(
+                to_array(add::<BabyBearParameters>(l, r)),
+                neon_emulation::add(la, ra)
+            );
+            assert_eq!(
+                to_array(sub::<BabyBearParameters>(l, r)),
+                neon_emulation::sub(la, ra)
+            );
+            assert_eq!(
+                to_array(neg::<BabyBearParameters>(l)),
+                neon_emulation::neg(la)
+            );
+            assert_eq!(
+                to_array(monty_mul_hi(l, r)),
+                neon_emulation::monty_mul_hi(la, ra)
+            );
+            assert_eq!(
+                to_array(monty_mul_lo::<BabyBearParameters>(l, r)),
+                neon_emulation::monty_mul_lo(la, ra)
+            );
+            assert_eq!(
+                to_array(mul::<BabyBearParameters>(l, r)),
+                neon_emulation::mul(la, ra)
+            );
+
+            // `vqdmulhq_s32` on arbitrary 32-bit inputs, including the saturating case.
+            let (la, ra): ([u32; WIDTH], [u32; WIDTH]) = (rng.gen(), rng.gen());
+            let (la, ra) = (
+                [la[0], la[1], la[2], 1 << 31],
+                [ra[0], ra[1], ra[2], 1 << 31],
+            );
+            let (l, r) = unsafe {
+                (
+                    transmute::<[u32; WIDTH], uint32x4_t>(la),
+                    transmute::<[u32; WIDTH], uint32x4_t>(ra),
+                )
+            };
+            assert_eq!(
+                to_array(mul_31x31_to_hi_31(l, r)),
+                neon_emulation::vqdmulhq_s32(la, ra)
+            );
+        }
+    }
+
+    #[test]
+    fn test_koala_bear_vs_scalar() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..1000 {
+            let (lhs, rhs): (PackedKoalaBearNeon, PackedKoalaBearNeon) = (rng.gen(), rng.gen());
+            let (sum, diff, prod, neg) = (lhs + rhs, lhs - rhs, lhs * rhs, -lhs);
+            for i in 0..WIDTH {
+                assert_eq!(sum.0[i], lhs.0[i] + rhs.0[i]);
+                assert_eq!(diff.0[i], lhs.0[i] - rhs.0[i]);
+                assert_eq!(prod.0[i], lhs.0[i] * rhs.0[i]);
+                assert_eq!(neg.0[i], -lhs.0[i]);
+                let (a, b) = (lhs.0[i].as_canonical_u32(), rhs.0[i].as_canonical_u32());
+                let expected = a as u64 * b as u64 % KoalaBear::ORDER_U32 as u64;
+                assert_eq!(prod.0[i].as_canonical_u32() as u64, expected);
+            }
+        }
+    }
 }
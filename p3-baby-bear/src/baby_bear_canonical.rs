@@ -0,0 +1,457 @@
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use p3_field::extension::{BinomiallyExtendable, HasTwoAdicBionmialExtension};
+use p3_field::{
+    exp_1725656503, exp_2013265919, exp_u64_by_squaring, AbstractField, Field, FieldArray,
+    PrimeField, PrimeField32, PrimeField64, TwoAdicField,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::{BabyBear, BabyBearParameters, MontyParameters};
+
+const P: u32 = BabyBearParameters::PRIME;
+
+/// The Baby Bear field, with elements stored in canonical form rather than Montgomery form.
+///
+/// This is the same field as `BabyBear`, and the two agree on every operation; only the
+/// representation, and thus the cost of each operation, differs. Multiplication reduces the
+/// 62-bit product with a division by the constant `P` instead of a Montgomery reduction.
+#[derive(Copy, Clone, Default, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[repr(transparent)]
+pub struct BabyBearCanonical {
+    /// Always in `0..P`.
+    value: u32,
+}
+
+/// The packed counterpart of `BabyBearCanonical`: `PackedBabyBearCanonicalAVX2` when AVX2 is
+/// enabled but AVX-512 is not.
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f")
+))]
+pub type PackedBabyBearCanonical = crate::PackedBabyBearCanonicalAVX2;
+
+/// The packed counterpart of `BabyBearCanonical`, with as many lanes as the `BabyBear` packing.
+///
+/// This is a `FieldArray`, not a SIMD type: each lane is multiplied with the scalar canonical
+/// arithmetic. Where `BabyBear` has an AVX-512 or NEON packing, its packed code is therefore
+/// vectorized and the canonical code is not.
+#[cfg(not(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f")
+)))]
+pub type PackedBabyBearCanonical = FieldArray<
+    BabyBearCanonical,
+    { <<BabyBear as Field>::Packing as p3_field::PackedField>::WIDTH },
+>;
+
+impl BabyBearCanonical {
+    /// create a new `BabyBearCanonical` from a canonical `u32`.
+    #[inline]
+    pub(crate) const fn new(n: u32) -> Self {
+        debug_assert!(n < P);
+        Self { value: n }
+    }
+}
+
+impl From<BabyBear> for BabyBearCanonical {
+    #[inline]
+    fn from(x: BabyBear) -> Self {
+        Self::new(x.as_canonical_u32())
+    }
+}
+
+impl From<BabyBearCanonical> for BabyBear {
+    #[inline]
+    fn from(x: BabyBearCanonical) -> Self {
+        Self::from_canonical_u32(x.value)
+    }
+}
+
+impl Display for BabyBearCanonical {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.value, f)
+    }
+}
+
+impl Debug for BabyBearCanonical {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.value, f)
+    }
+}
+
+impl Distribution<BabyBearCanonical> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> BabyBearCanonical {
+        loop {
+            let next_u31 = rng.next_u32() >> 1;
+            if next_u31 < P {
+                return BabyBearCanonical::new(next_u31);
+            }
+        }
+    }
+}
+
+impl AbstractField for BabyBearCanonical {
+    type F = Self;
+
+    fn zero() -> Self {
+        Self::new(0)
+    }
+    fn one() -> Self {
+        Self::new(1)
+    }
+    fn two() -> Self {
+        Self::new(2)
+    }
+    fn neg_one() -> Self {
+        Self::new(P - 1)
+    }
+
+    #[inline]
+    fn from_f(f: Self::F) -> Self {
+        f
+    }
+
+    #[inline]
+    fn from_bool(b: bool) -> Self {
+        Self::new(b as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u8(n: u8) -> Self {
+        Self::new(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u16(n: u16) -> Self {
+        Self::new(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u32(n: u32) -> Self {
+        Self::new(n)
+    }
+
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        debug_assert!(n < P as u64);
+        Self::new(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_usize(n: usize) -> Self {
+        debug_assert!(n < P as usize);
+        Self::new(n as u32)
+    }
+
+    #[inline]
+    fn from_wrapped_u32(n: u32) -> Self {
+        Self::new(n % P)
+    }
+
+    #[inline]
+    fn from_wrapped_u64(n: u64) -> Self {
+        Self::new((n % P as u64) as u32)
+    }
+
+    #[inline]
+    fn generator() -> Self {
+        Self::new(BabyBearParameters::GENERATOR)
+    }
+}
+
+impl Field for BabyBearCanonical {
+    type Packing = PackedBabyBearCanonical;
+
+    #[inline]
+    fn mul_2exp_u64(&self, exp: u64) -> Self {
+        let product = (self.value as u64) << exp;
+        Self::new((product % P as u64) as u32)
+    }
+
+    #[inline]
+    fn exp_u64_generic<AF: AbstractField<F = Self>>(val: AF, power: u64) -> AF {
+        match power {
+            1725656503 => exp_1725656503(val), // used to compute x^{1/7}
+            _ => exp_u64_by_squaring(val, power),
+        }
+    }
+
+    fn try_inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // From Fermat's little theorem, in a prime field `F_p`, the inverse of `a` is `a^(p-2)`.
+        Some(exp_2013265919(*self))
+    }
+}
+
+impl PrimeField for BabyBearCanonical {}
+
+impl PrimeField64 for BabyBearCanonical {
+    const ORDER_U64: u64 = P as u64;
+
+    #[inline]
+    fn as_canonical_u64(&self) -> u64 {
+        u64::from(self.value)
+    }
+
+    #[inline]
+    fn linear_combination_u64<const N: usize>(u: [u64; N], v: &[Self; N]) -> Self {
+        // In order not to overflow a u64, we must have sum(u) <= 2^32.
+        debug_assert!(u.iter().sum::<u64>() <= (1u64 << 32));
+
+        let mut dot = u[0] * v[0].value as u64;
+        for i in 1..N {
+            dot += u[i] * v[i].value as u64;
+        }
+        Self::new((dot % P as u64) as u32)
+    }
+}
+
+impl PrimeField32 for BabyBearCanonical {
+    const ORDER_U32: u32 = P;
+
+    #[inline]
+    fn as_canonical_u32(&self) -> u32 {
+        self.value
+    }
+}
+
+impl TwoAdicField for BabyBearCanonical {
+    const TWO_ADICITY: usize = BabyBearParameters::TWO_ADICITY;
+
+    fn two_adic_generator(bits: usize) -> Self {
+        assert!(bits <= Self::TWO_ADICITY);
+        let base = Self::new(BabyBearParameters::TWO_ADIC_GENERATOR);
+        base.exp_power_of_2(Self::TWO_ADICITY - bits)
+    }
+}
+
+impl BinomiallyExtendable<4> for BabyBearCanonical {
+    // The same as for `BabyBear`: `x^4 - 11` is irreducible.
+    fn w() -> Self {
+        Self::new(11)
+    }
+
+    // DTH_ROOT = W^((p - 1)/4)
+    fn dth_root() -> Self {
+        Self::new(1728404513)
+    }
+
+    fn ext_generator() -> [Self; 4] {
+        [Self::new(8), Self::one(), Self::zero(), Self::zero()]
+    }
+}
+
+impl HasTwoAdicBionmialExtension<4> for BabyBearCanonical {
+    const EXT_TWO_ADICITY: usize = 29;
+
+    fn ext_two_adic_generator(bits: usize) -> [Self; 4] {
+        <BabyBear as HasTwoAdicBionmialExtension<4>>::ext_two_adic_generator(bits).map(Self::from)
+    }
+}
+
+impl Add for BabyBearCanonical {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        let mut sum = self.value + rhs.value;
+        let (corr_sum, over) = sum.overflowing_sub(P);
+        if !over {
+            sum = corr_sum;
+        }
+        Self::new(sum)
+    }
+}
+
+impl AddAssign for BabyBearCanonical {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for BabyBearCanonical {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x + y).unwrap_or(Self::zero())
+    }
+}
+
+impl Sub for BabyBearCanonical {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        let (mut diff, over) = self.value.overflowing_sub(rhs.value);
+        let corr = if over { P } else { 0 };
+        diff = diff.wrapping_add(corr);
+        Self::new(diff)
+    }
+}
+
+impl SubAssign for BabyBearCanonical {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for BabyBearCanonical {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+impl Mul for BabyBearCanonical {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let long_prod = self.value as u64 * rhs.value as u64;
+        Self::new((long_prod % P as u64) as u32)
+    }
+}
+
+impl MulAssign for BabyBearCanonical {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Product for BabyBearCanonical {
+    #[inline]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x * y).unwrap_or(Self::one())
+    }
+}
+
+impl Div for BabyBearCanonical {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl<const N: usize> Add<FieldArray<Self, N>> for BabyBearCanonical {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn add(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) + rhs
+    }
+}
+
+impl<const N: usize> Mul<FieldArray<Self, N>> for BabyBearCanonical {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn mul(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) * rhs
+    }
+}
+
+impl<const N: usize> Sub<FieldArray<Self, N>> for BabyBearCanonical {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn sub(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) - rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::extension::BinomialExtensionField;
+    use p3_field::AbstractExtensionField;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    type F = BabyBearCanonical;
+    type EF = BinomialExtensionField<F, 4>;
+
+    #[test]
+    fn test_baby_bear_canonical() {
+        let f = F::from_canonical_u32(100);
+        assert_eq!(f.as_canonical_u64(), 100);
+
+        let f = F::from_wrapped_u32(F::ORDER_U32);
+        assert!(f.is_zero());
+
+        let f_1 = F::one();
+        let f_2 = F::from_canonical_u32(2);
+        assert_eq!(f_1 + f_1, F::two());
+        assert_eq!(f_1 + f_2 * f_2, F::from_canonical_u32(5));
+
+        let f_p_minus_1 = F::from_canonical_u32(F::ORDER_U32 - 1);
+        assert_eq!(f_p_minus_1, F::neg_one());
+        assert_eq!(f_1 + f_p_minus_1, F::zero());
+        assert_eq!(f_1 - f_p_minus_1, f_2);
+
+        let m1 = F::from_canonical_u32(0x34167c58);
+        let m2 = F::from_canonical_u32(0x61f3207b);
+        let expected_prod = F::from_canonical_u32(0x1b5c8046);
+        assert_eq!(m1 * m2, expected_prod);
+
+        assert_eq!(m1.exp_u64(1725656503).exp_const_u64::<7>(), m1);
+        assert_eq!(m1 * m1.inverse(), F::one());
+        assert_eq!(F::zero().try_inverse(), None);
+    }
+
+    #[test]
+    fn matches_montgomery() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let (x, y): (BabyBear, BabyBear) = (rng.gen(), rng.gen());
+            let (a, b) = (F::from(x), F::from(y));
+            assert_eq!(F::from(x + y), a + b);
+            assert_eq!(F::from(x - y), a - b);
+            assert_eq!(F::from(x * y), a * b);
+            assert_eq!(F::from(-x), -a);
+            assert_eq!(F::from(x.inverse()), a.inverse());
+            assert_eq!(BabyBear::from(a), x);
+        }
+        assert_eq!(F::from(BabyBear::generator()), F::generator());
+        assert_eq!(
+            F::from(BabyBear::two_adic_generator(27)),
+            F::two_adic_generator(27)
+        );
+    }
+
+    #[test]
+    fn quartic_extension() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let w = <F as BinomiallyExtendable<4>>::w();
+        assert_eq!(w.exp_u64((F::ORDER_U64 - 1) / 4), F::dth_root());
+
+        let x = <EF as AbstractExtensionField<F>>::monomial(1);
+        assert_eq!(x.exp_u64(4), EF::from_base(w));
+
+        for _ in 0..100 {
+            let a: EF = rng.gen();
+            assert_eq!(a * a.inverse(), EF::one());
+        }
+
+        let g = EF::from_base_slice(&F::ext_two_adic_generator(F::EXT_TWO_ADICITY));
+        assert_eq!(g.exp_power_of_2(F::EXT_TWO_ADICITY), EF::one());
+        assert_ne!(g.exp_power_of_2(F::EXT_TWO_ADICITY - 1), EF::one());
+    }
+}
@@ -0,0 +1,333 @@
+//! Slice kernels over `BabyBear` whose SIMD implementation is chosen at runtime.
+//!
+//! `<BabyBear as Field>::Packing` is fixed by the target features a binary is compiled with, so
+//! a binary built for generic x86_64 never uses AVX2 or AVX-512. `BabyBearKernels` instead
+//! detects the best instruction set the CPU supports, once, and routes each kernel to the
+//! matching packing from `p3_field::kernels`.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use p3_field::{kernels, Field};
+
+use crate::BabyBear;
+
+/// An implementation strategy for `BabyBearKernels`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SimdBackend {
+    /// `<BabyBear as Field>::Packing`, as selected at compile time.
+    Static,
+    /// `PackedBabyBearAVX2`, 8 lanes.
+    Avx2,
+    /// `PackedBabyBearAVX512`, 16 lanes.
+    Avx512,
+}
+
+impl SimdBackend {
+    /// The fastest backend supported by this CPU. The CPU is only queried on the first call.
+    pub fn detect() -> Self {
+        // 0 means not yet detected; otherwise `backend as u8 + 1`.
+        static DETECTED: AtomicU8 = AtomicU8::new(0);
+        match DETECTED.load(Ordering::Relaxed) {
+            0 => {
+                let backend = [Self::Avx512, Self::Avx2]
+                    .into_iter()
+                    .find(|b| b.is_supported())
+                    .unwrap_or(Self::Static);
+                DETECTED.store(backend as u8 + 1, Ordering::Relaxed);
+                backend
+            }
+            1 => Self::Static,
+            2 => Self::Avx2,
+            _ => Self::Avx512,
+        }
+    }
+
+    /// Whether this CPU can run the backend. `Static` is always supported.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Static => true,
+            #[cfg(target_arch = "x86_64")]
+            Self::Avx2 => cpu_features().avx2,
+            #[cfg(target_arch = "x86_64")]
+            Self::Avx512 => cpu_features().avx512f,
+            #[cfg(not(target_arch = "x86_64"))]
+            Self::Avx2 | Self::Avx512 => false,
+        }
+    }
+}
+
+/// `BabyBear` slice kernels bound to one `SimdBackend`.
+///
+/// Each method behaves exactly like the `p3_field` function of the same name; only the speed
+/// depends on the backend.
+#[derive(Clone, Copy, Debug)]
+pub struct BabyBearKernels {
+    backend: SimdBackend,
+    add_slices_in_place: unsafe fn(&mut [BabyBear], &[BabyBear]),
+    mul_slices_in_place: unsafe fn(&mut [BabyBear], &[BabyBear]),
+    add_scaled_slice_in_place: unsafe fn(&mut [BabyBear], &[BabyBear], BabyBear),
+    dit_butterfly: unsafe fn(&mut [BabyBear], &mut [BabyBear], BabyBear),
+    dif_butterfly: unsafe fn(&mut [BabyBear], &mut [BabyBear], BabyBear),
+    batch_multiplicative_inverse: unsafe fn(&[BabyBear]) -> Vec<BabyBear>,
+}
+
+/// Instantiates the `p3_field::kernels` functions with one packing, optionally inside functions
+/// compiled with extra target features, and collects them into a `BabyBearKernels`.
+macro_rules! kernel_table {
+    ($backend:expr, $packing:ty $(, $feature:literal)?) => {{
+        $(#[target_feature(enable = $feature)])?
+        unsafe fn add_slices_in_place(x: &mut [BabyBear], y: &[BabyBear]) {
+            kernels::add_slices_in_place::<$packing>(x, y)
+        }
+        $(#[target_feature(enable = $feature)])?
+        unsafe fn mul_slices_in_place(x: &mut [BabyBear], y: &[BabyBear]) {
+            kernels::mul_slices_in_place::<$packing>(x, y)
+        }
+        $(#[target_feature(enable = $feature)])?
+        unsafe fn add_scaled_slice_in_place(x: &mut [BabyBear], y: &[BabyBear], s: BabyBear) {
+            kernels::add_scaled_slice_in_place::<$packing>(x, y, s)
+        }
+        $(#[target_feature(enable = $feature)])?
+        unsafe fn dit_butterfly(lo: &mut [BabyBear], hi: &mut [BabyBear], twiddle: BabyBear) {
+            kernels::dit_butterfly::<$packing>(lo, hi, twiddle)
+        }
+        $(#[target_feature(enable = $feature)])?
+        unsafe fn dif_butterfly(lo: &mut [BabyBear], hi: &mut [BabyBear], twiddle: BabyBear) {
+            kernels::dif_butterfly::<$packing>(lo, hi, twiddle)
+        }
+        $(#[target_feature(enable = $feature)])?
+        unsafe fn batch_multiplicative_inverse(x: &[BabyBear]) -> Vec<BabyBear> {
+            kernels::batch_multiplicative_inverse_packed::<$packing>(x)
+        }
+        BabyBearKernels {
+            backend: $backend,
+            add_slices_in_place,
+            mul_slices_in_place,
+            add_scaled_slice_in_place,
+            dit_butterfly,
+            dif_butterfly,
+            batch_multiplicative_inverse,
+        }
+    }};
+}
+
+static STATIC_KERNELS: BabyBearKernels =
+    kernel_table!(SimdBackend::Static, <BabyBear as Field>::Packing);
+#[cfg(target_arch = "x86_64")]
+static AVX2_KERNELS: BabyBearKernels = kernel_table!(
+    SimdBackend::Avx2,
+    crate::x86_64_avx2::PackedBabyBearAVX2,
+    "avx2"
+);
+#[cfg(target_arch = "x86_64")]
+static AVX512_KERNELS: BabyBearKernels = kernel_table!(
+    SimdBackend::Avx512,
+    crate::x86_64_avx512::PackedBabyBearAVX512,
+    "avx512f"
+);
+
+impl BabyBearKernels {
+    /// The kernels for `SimdBackend::detect()`.
+    pub fn detected() -> &'static Self {
+        Self::table(SimdBackend::detect())
+    }
+
+    /// The kernels for `backend`, or `None` if this CPU does not support it.
+    pub fn for_backend(backend: SimdBackend) -> Option<&'static Self> {
+        backend.is_supported().then(|| Self::table(backend))
+    }
+
+    fn table(backend: SimdBackend) -> &'static Self {
+        match backend {
+            SimdBackend::Static => &STATIC_KERNELS,
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx2 => &AVX2_KERNELS,
+            #[cfg(target_arch = "x86_64")]
+            SimdBackend::Avx512 => &AVX512_KERNELS,
+            #[cfg(not(target_arch = "x86_64"))]
+            SimdBackend::Avx2 | SimdBackend::Avx512 => unreachable!("unsupported backend"),
+        }
+    }
+
+    pub fn backend(&self) -> SimdBackend {
+        self.backend
+    }
+
+    // Safety, for every method below: `detected` and `for_backend` only hand out tables whose
+    // target features this CPU supports, and tables cannot be constructed outside this module.
+
+    /// `x += y`, elementwise.
+    pub fn add_slices_in_place(&self, x: &mut [BabyBear], y: &[BabyBear]) {
+        unsafe { (self.add_slices_in_place)(x, y) }
+    }
+
+    /// `x *= y`, elementwise.
+    pub fn mul_slices_in_place(&self, x: &mut [BabyBear], y: &[BabyBear]) {
+        unsafe { (self.mul_slices_in_place)(x, y) }
+    }
+
+    /// `x += y * s`, where `s` is a scalar.
+    pub fn add_scaled_slice_in_place(&self, x: &mut [BabyBear], y: &[BabyBear], s: BabyBear) {
+        unsafe { (self.add_scaled_slice_in_place)(x, y, s) }
+    }
+
+    /// The decimation-in-time butterfly `(x, y) <- (x + y t, x - y t)` over the rows `lo`, `hi`.
+    pub fn dit_butterfly(&self, lo: &mut [BabyBear], hi: &mut [BabyBear], twiddle: BabyBear) {
+        unsafe { (self.dit_butterfly)(lo, hi, twiddle) }
+    }
+
+    /// The decimation-in-frequency butterfly `(x, y) <- (x + y, (x - y) t)` over the rows `lo`,
+    /// `hi`.
+    pub fn dif_butterfly(&self, lo: &mut [BabyBear], hi: &mut [BabyBear], twiddle: BabyBear) {
+        unsafe { (self.dif_butterfly)(lo, hi, twiddle) }
+    }
+
+    /// The inverse of every element of `x`.
+    ///
+    /// # Panics
+    /// Panics if any element of `x` is zero.
+    pub fn batch_multiplicative_inverse(&self, x: &[BabyBear]) -> Vec<BabyBear> {
+        unsafe { (self.batch_multiplicative_inverse)(x) }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[derive(Clone, Copy)]
+struct CpuFeatures {
+    avx2: bool,
+    avx512f: bool,
+}
+
+/// Queries `cpuid`, and `xgetbv` for whether the OS saves the wider registers on context switch.
+#[cfg(target_arch = "x86_64")]
+fn cpu_features() -> CpuFeatures {
+    use core::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv, CpuidResult};
+
+    const OSXSAVE: u32 = 1 << 27; // cpuid(1).ecx
+    const AVX2: u32 = 1 << 5; // cpuid(7, 0).ebx
+    const AVX512F: u32 = 1 << 16; // cpuid(7, 0).ebx
+    const XCR0_AVX: u64 = 0b110; // SSE and AVX state
+    const XCR0_AVX512: u64 = 0b1110_0110; // ... plus opmask and upper ZMM state
+
+    #[target_feature(enable = "xsave")]
+    unsafe fn xcr0() -> u64 {
+        _xgetbv(0)
+    }
+
+    let none = CpuFeatures {
+        avx2: false,
+        avx512f: false,
+    };
+    let CpuidResult { eax: max_leaf, .. } = __cpuid(0);
+    if max_leaf < 7 || __cpuid(1).ecx & OSXSAVE == 0 {
+        return none;
+    }
+    // Safety: OSXSAVE means the OS has enabled `xgetbv`.
+    let xcr0 = unsafe { xcr0() };
+    let ebx = __cpuid_count(7, 0).ebx;
+    CpuFeatures {
+        avx2: ebx & AVX2 != 0 && xcr0 & XCR0_AVX == XCR0_AVX,
+        avx512f: ebx & AVX512F != 0 && xcr0 & XCR0_AVX512 == XCR0_AVX512,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_field::AbstractField;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    type F = BabyBear;
+
+    fn supported_kernels() -> Vec<&'static BabyBearKernels> {
+        [SimdBackend::Static, SimdBackend::Avx2, SimdBackend::Avx512]
+            .into_iter()
+            .filter_map(BabyBearKernels::for_backend)
+            .collect()
+    }
+
+    #[test]
+    fn detect_is_supported_and_stable() {
+        let backend = SimdBackend::detect();
+        assert!(backend.is_supported());
+        assert_eq!(SimdBackend::detect(), backend);
+        assert_eq!(BabyBearKernels::detected().backend(), backend);
+    }
+
+    #[test]
+    fn static_is_always_available() {
+        let kernels = BabyBearKernels::for_backend(SimdBackend::Static).unwrap();
+        assert_eq!(kernels.backend(), SimdBackend::Static);
+    }
+
+    #[test]
+    fn kernels_match_scalar() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Lengths around multiples of every width, so both the packed body and the scalar tail
+        // are exercised.
+        for n in (0..40).chain([255, 256, 257, 1000]) {
+            let x: Vec<F> = (0..n).map(|_| rng.gen()).collect();
+            let y: Vec<F> = (0..n).map(|_| rng.gen()).collect();
+            let s: F = rng.gen();
+            let nonzero: Vec<F> = x
+                .iter()
+                .map(|&x_i| if x_i.is_zero() { F::one() } else { x_i })
+                .collect();
+
+            for kernels in supported_kernels() {
+                let backend = kernels.backend();
+
+                let mut sum = x.clone();
+                kernels.add_slices_in_place(&mut sum, &y);
+                assert!(sum.iter().zip(&x).zip(&y).all(|((&r, &a), &b)| r == a + b));
+
+                let mut product = x.clone();
+                kernels.mul_slices_in_place(&mut product, &y);
+                assert!(product
+                    .iter()
+                    .zip(&x)
+                    .zip(&y)
+                    .all(|((&r, &a), &b)| r == a * b));
+
+                let mut axpy = x.clone();
+                kernels.add_scaled_slice_in_place(&mut axpy, &y, s);
+                assert!(axpy
+                    .iter()
+                    .zip(&x)
+                    .zip(&y)
+                    .all(|((&r, &a), &b)| r == a + b * s));
+
+                let (mut lo, mut hi) = (x.clone(), y.clone());
+                kernels.dit_butterfly(&mut lo, &mut hi, s);
+                for i in 0..n {
+                    assert_eq!(
+                        (lo[i], hi[i]),
+                        (x[i] + y[i] * s, x[i] - y[i] * s),
+                        "{backend:?}"
+                    );
+                }
+
+                let (mut lo, mut hi) = (x.clone(), y.clone());
+                kernels.dif_butterfly(&mut lo, &mut hi, s);
+                for i in 0..n {
+                    assert_eq!(
+                        (lo[i], hi[i]),
+                        (x[i] + y[i], (x[i] - y[i]) * s),
+                        "{backend:?}"
+                    );
+                }
+
+                let inverses = kernels.batch_multiplicative_inverse(&nonzero);
+                assert!(inverses
+                    .iter()
+                    .zip(&nonzero)
+                    .all(|(&r, &a)| r * a == F::one()));
+            }
+        }
+    }
+}
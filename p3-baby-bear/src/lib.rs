@@ -3,11 +3,37 @@
 extern crate alloc;
 
 mod baby_bear;
+mod baby_bear_canonical;
+mod dispatch;
 mod extension;
+mod koala_bear;
+mod monty_31;
 
 pub use baby_bear::*;
+pub use baby_bear_canonical::*;
+pub use dispatch::*;
+pub use koala_bear::*;
+pub use monty_31::*;
 
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 mod aarch64_neon;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 pub use aarch64_neon::*;
+
+#[cfg(test)]
+mod neon_emulation;
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64_avx2;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+pub use x86_64_avx2::*;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+mod x86_64_avx2_canonical;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+pub use x86_64_avx2_canonical::*;
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64_avx512;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+pub use x86_64_avx512::*;
@@ -0,0 +1,436 @@
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::Hash;
+use core::iter::{Product, Sum};
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use p3_field::{
+    exp_u64_by_squaring, AbstractField, Field, FieldArray, PrimeField, PrimeField32, PrimeField64,
+    TwoAdicField,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+/// The Montgomery radix is `2^MONTY_BITS`. It is the same for every prime, since the packed
+/// implementations rely on elements fitting in 31 bits.
+pub(crate) const MONTY_BITS: u32 = 31;
+pub(crate) const MONTY_MASK: u32 = (1 << MONTY_BITS) - 1;
+
+/// The constants that determine a 31-bit prime field in Montgomery form.
+pub trait MontyParameters:
+    Copy + Clone + Default + Debug + Eq + PartialEq + Hash + Send + Sync + 'static
+{
+    /// The prime `P`, which must be below `2^31`.
+    const PRIME: u32;
+
+    /// `P^-1 mod 2^MONTY_BITS`.
+    const MONTY_MU: u32;
+
+    /// A generator of the whole multiplicative group, in canonical form.
+    const GENERATOR: u32;
+
+    /// The number of factors of two in `P - 1`.
+    const TWO_ADICITY: usize;
+
+    /// A generator of the multiplicative subgroup of order `2^TWO_ADICITY`, in canonical form.
+    const TWO_ADIC_GENERATOR: u32;
+
+    /// Exponentiation by `power`. Fields can override this to use addition chains for the powers
+    /// they care about, such as the inverse of an S-box exponent.
+    #[must_use]
+    #[inline]
+    fn exp_u64_generic<AF: AbstractField<F = MontyField31<Self>>>(val: AF, power: u64) -> AF {
+        exp_u64_by_squaring(val, power)
+    }
+
+    /// `val^(P - 2)`, which is the inverse of any non-zero `val`. Fields can override this with an
+    /// addition chain.
+    #[must_use]
+    #[inline]
+    fn exp_p_minus_2(val: MontyField31<Self>) -> MontyField31<Self> {
+        exp_u64_by_squaring(val, Self::PRIME as u64 - 2)
+    }
+}
+
+/// An element of the prime field defined by `MP`, stored in Montgomery form.
+#[derive(Copy, Clone, Default, Eq, Hash, PartialEq)]
+#[repr(transparent)] // The packed implementations rely on this!
+pub struct MontyField31<MP: MontyParameters> {
+    value: u32,
+    _phantom: PhantomData<MP>,
+}
+
+impl<MP: MontyParameters> MontyField31<MP> {
+    /// create a new `MontyField31` from a canonical `u32`.
+    #[inline]
+    pub(crate) const fn new(n: u32) -> Self {
+        Self::new_monty(to_monty::<MP>(n))
+    }
+
+    /// Wrap a value which is already in Montgomery form.
+    #[inline]
+    pub(crate) const fn new_monty(value: u32) -> Self {
+        Self {
+            value,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create a new `MontyField31` from its Montgomery form `x 2^MONTY_BITS mod P`, which must be
+    /// in `0..P`.
+    #[inline]
+    pub const fn from_monty_u32(value: u32) -> Self {
+        debug_assert!(value < MP::PRIME);
+        Self::new_monty(value)
+    }
+
+    /// The Montgomery form `x 2^MONTY_BITS mod P` of this element, which is in `0..P`.
+    #[inline]
+    pub const fn as_monty_u32(&self) -> u32 {
+        self.value
+    }
+
+    const ZERO: Self = Self::new(0);
+    const ONE: Self = Self::new(1);
+    const TWO: Self = Self::new(2);
+    const NEG_ONE: Self = Self::new(MP::PRIME - 1);
+}
+
+impl<MP: MontyParameters> Ord for MontyField31<MP> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_canonical_u32().cmp(&other.as_canonical_u32())
+    }
+}
+
+impl<MP: MontyParameters> PartialOrd for MontyField31<MP> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<MP: MontyParameters> Display for MontyField31<MP> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_canonical_u32(), f)
+    }
+}
+
+impl<MP: MontyParameters> Debug for MontyField31<MP> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_canonical_u32(), f)
+    }
+}
+
+impl<MP: MontyParameters> Distribution<MontyField31<MP>> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> MontyField31<MP> {
+        loop {
+            let next_u31 = rng.next_u32() >> 1;
+            let is_canonical = next_u31 < MP::PRIME;
+            if is_canonical {
+                return MontyField31::new_monty(next_u31);
+            }
+        }
+    }
+}
+
+impl<MP: MontyParameters> AbstractField for MontyField31<MP> {
+    type F = Self;
+
+    fn zero() -> Self {
+        Self::ZERO
+    }
+    fn one() -> Self {
+        Self::ONE
+    }
+    fn two() -> Self {
+        Self::TWO
+    }
+    fn neg_one() -> Self {
+        Self::NEG_ONE
+    }
+
+    #[inline]
+    fn from_f(f: Self::F) -> Self {
+        f
+    }
+
+    #[inline]
+    fn from_bool(b: bool) -> Self {
+        Self::from_canonical_u32(b as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u8(n: u8) -> Self {
+        Self::from_canonical_u32(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u16(n: u16) -> Self {
+        Self::from_canonical_u32(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u32(n: u32) -> Self {
+        debug_assert!(n < MP::PRIME);
+        Self::from_wrapped_u32(n)
+    }
+
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        debug_assert!(n < MP::PRIME as u64);
+        Self::from_canonical_u32(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_usize(n: usize) -> Self {
+        debug_assert!(n < MP::PRIME as usize);
+        Self::from_canonical_u32(n as u32)
+    }
+
+    #[inline]
+    fn from_wrapped_u32(n: u32) -> Self {
+        Self::new_monty(to_monty::<MP>(n))
+    }
+
+    #[inline]
+    fn from_wrapped_u64(n: u64) -> Self {
+        Self::new_monty(to_monty_64::<MP>(n))
+    }
+
+    #[inline]
+    fn generator() -> Self {
+        Self::from_canonical_u32(MP::GENERATOR)
+    }
+}
+
+impl<MP: MontyParameters> Field for MontyField31<MP> {
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    type Packing = crate::PackedMontyField31Neon<MP>;
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(target_feature = "avx512f")
+    ))]
+    type Packing = crate::PackedMontyField31AVX2<MP>;
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+    type Packing = crate::PackedMontyField31AVX512<MP>;
+    #[cfg(not(any(
+        all(target_arch = "aarch64", target_feature = "neon"),
+        all(target_arch = "x86_64", target_feature = "avx2"),
+    )))]
+    type Packing = FieldArray<Self, 4>;
+
+    #[inline]
+    fn mul_2exp_u64(&self, exp: u64) -> Self {
+        let product = (self.value as u64) << exp;
+        let value = (product % (MP::PRIME as u64)) as u32;
+        Self::new_monty(value)
+    }
+
+    #[inline]
+    fn exp_u64_generic<AF: AbstractField<F = Self>>(val: AF, power: u64) -> AF {
+        MP::exp_u64_generic(val, power)
+    }
+
+    fn try_inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // From Fermat's little theorem, in a prime field `F_p`, the inverse of `a` is `a^(p-2)`.
+        Some(MP::exp_p_minus_2(*self))
+    }
+}
+
+impl<MP: MontyParameters> PrimeField for MontyField31<MP> {}
+
+impl<MP: MontyParameters> PrimeField64 for MontyField31<MP> {
+    const ORDER_U64: u64 = <Self as PrimeField32>::ORDER_U32 as u64;
+
+    #[inline]
+    fn as_canonical_u64(&self) -> u64 {
+        u64::from(self.as_canonical_u32())
+    }
+
+    #[inline]
+    fn linear_combination_u64<const N: usize>(u: [u64; N], v: &[Self; N]) -> Self {
+        // In order not to overflow a u64, we must have sum(u) <= 2^32.
+        debug_assert!(u.iter().sum::<u64>() <= (1u64 << 32));
+
+        let mut dot = u[0] * v[0].value as u64;
+        for i in 1..N {
+            dot += u[i] * v[i].value as u64;
+        }
+        Self::new_monty((dot % (MP::PRIME as u64)) as u32)
+    }
+}
+
+impl<MP: MontyParameters> PrimeField32 for MontyField31<MP> {
+    const ORDER_U32: u32 = MP::PRIME;
+
+    #[inline]
+    fn as_canonical_u32(&self) -> u32 {
+        from_monty::<MP>(self.value)
+    }
+}
+
+impl<MP: MontyParameters> TwoAdicField for MontyField31<MP> {
+    const TWO_ADICITY: usize = MP::TWO_ADICITY;
+
+    fn two_adic_generator(bits: usize) -> Self {
+        // TODO: Consider a `match` which may speed this up.
+        assert!(bits <= Self::TWO_ADICITY);
+        let base = Self::from_canonical_u32(MP::TWO_ADIC_GENERATOR);
+        base.exp_power_of_2(Self::TWO_ADICITY - bits)
+    }
+}
+
+impl<MP: MontyParameters> Add for MontyField31<MP> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        let mut sum = self.value + rhs.value;
+        let (corr_sum, over) = sum.overflowing_sub(MP::PRIME);
+        if !over {
+            sum = corr_sum;
+        }
+        Self::new_monty(sum)
+    }
+}
+
+impl<MP: MontyParameters> AddAssign for MontyField31<MP> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<MP: MontyParameters> Sum for MontyField31<MP> {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x + y).unwrap_or(Self::zero())
+    }
+}
+
+impl<MP: MontyParameters> Sub for MontyField31<MP> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        let (mut diff, over) = self.value.overflowing_sub(rhs.value);
+        let corr = if over { MP::PRIME } else { 0 };
+        diff = diff.wrapping_add(corr);
+        Self::new_monty(diff)
+    }
+}
+
+impl<MP: MontyParameters> SubAssign for MontyField31<MP> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<MP: MontyParameters> Neg for MontyField31<MP> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+impl<MP: MontyParameters> Mul for MontyField31<MP> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let long_prod = self.value as u64 * rhs.value as u64;
+        Self::new_monty(monty_reduce::<MP>(long_prod))
+    }
+}
+
+impl<MP: MontyParameters> MulAssign for MontyField31<MP> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<MP: MontyParameters> Product for MontyField31<MP> {
+    #[inline]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x * y).unwrap_or(Self::one())
+    }
+}
+
+impl<MP: MontyParameters> Div for MontyField31<MP> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl<MP: MontyParameters, const N: usize> Add<FieldArray<Self, N>> for MontyField31<MP> {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn add(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) + rhs
+    }
+}
+
+impl<MP: MontyParameters, const N: usize> Mul<FieldArray<Self, N>> for MontyField31<MP> {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn mul(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) * rhs
+    }
+}
+
+impl<MP: MontyParameters, const N: usize> Sub<FieldArray<Self, N>> for MontyField31<MP> {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn sub(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) - rhs
+    }
+}
+
+#[inline]
+#[must_use]
+const fn to_monty<MP: MontyParameters>(x: u32) -> u32 {
+    (((x as u64) << MONTY_BITS) % MP::PRIME as u64) as u32
+}
+
+#[inline]
+#[must_use]
+fn to_monty_64<MP: MontyParameters>(x: u64) -> u32 {
+    (((x as u128) << MONTY_BITS) % MP::PRIME as u128) as u32
+}
+
+#[inline]
+#[must_use]
+fn from_monty<MP: MontyParameters>(x: u32) -> u32 {
+    monty_reduce::<MP>(x as u64)
+}
+
+/// Montgomery reduction of a value in `0..P << MONTY_BITS`.
+#[inline]
+#[must_use]
+fn monty_reduce<MP: MontyParameters>(x: u64) -> u32 {
+    let t = x.wrapping_mul(MP::MONTY_MU as u64) & (MONTY_MASK as u64);
+    let u = t * (MP::PRIME as u64);
+
+    let (x_sub_u, over) = x.overflowing_sub(u);
+    let x_sub_u_hi = (x_sub_u >> MONTY_BITS) as u32;
+    let corr = if over { MP::PRIME } else { 0 };
+    x_sub_u_hi.wrapping_add(corr)
+}
@@ -0,0 +1,238 @@
+//! A portable, lane-by-lane emulation of the `uint32x4_t` arithmetic in `aarch64_neon.rs`.
+//!
+//! Each NEON intrinsic used there is reimplemented on `[u32; 4]` with the semantics given in the
+//! Arm architecture reference, and the field routines are transcribed step for step on top of
+//! them. This lets the reduction logic be tested against scalar `BabyBear` on any target. On
+//! aarch64, `aarch64_neon.rs` additionally checks the emulation against the real intrinsics, so
+//! the two cannot drift apart unnoticed.
+
+use core::array;
+
+const WIDTH: usize = 4;
+const P: Vector = [0x78000001; WIDTH];
+const MU: Vector = [0x08000001; WIDTH];
+const TOP_BIT: Vector = [0x80000000; WIDTH];
+
+/// Stand-in for `uint32x4_t`.
+pub(crate) type Vector = [u32; WIDTH];
+
+fn lanewise(lhs: Vector, rhs: Vector, f: impl Fn(u32, u32) -> u32) -> Vector {
+    array::from_fn(|i| f(lhs[i], rhs[i]))
+}
+
+fn vaddq_u32(a: Vector, b: Vector) -> Vector {
+    lanewise(a, b, u32::wrapping_add)
+}
+
+fn vsubq_u32(a: Vector, b: Vector) -> Vector {
+    lanewise(a, b, u32::wrapping_sub)
+}
+
+fn vmulq_u32(a: Vector, b: Vector) -> Vector {
+    lanewise(a, b, u32::wrapping_mul)
+}
+
+fn vminq_u32(a: Vector, b: Vector) -> Vector {
+    lanewise(a, b, u32::min)
+}
+
+/// `a & !b`.
+fn vbicq_u32(a: Vector, b: Vector) -> Vector {
+    lanewise(a, b, |a, b| a & !b)
+}
+
+/// All ones where `a == 0`, zero elsewhere.
+fn vceqzq_u32(a: Vector) -> Vector {
+    a.map(|a| if a == 0 { u32::MAX } else { 0 })
+}
+
+/// All ones where `a < b`, zero elsewhere.
+fn vcltq_u32(a: Vector, b: Vector) -> Vector {
+    lanewise(a, b, |a, b| if a < b { u32::MAX } else { 0 })
+}
+
+/// `a - b * c`, wrapping.
+fn vmlsq_u32(a: Vector, b: Vector, c: Vector) -> Vector {
+    array::from_fn(|i| a[i].wrapping_sub(b[i].wrapping_mul(c[i])))
+}
+
+/// Signed saturating doubling multiply returning the high half: `sat((2 a b) >> 32)` on each
+/// lane reinterpreted as `i32`. The only input that saturates is `a = b = i32::MIN`.
+pub(crate) fn vqdmulhq_s32(a: Vector, b: Vector) -> Vector {
+    lanewise(a, b, |a, b| {
+        let doubled = 2 * (a as i32 as i128) * (b as i32 as i128);
+        (doubled >> 32).clamp(i32::MIN as i128, i32::MAX as i128) as i32 as u32
+    })
+}
+
+/// Emulates `aarch64_neon::add`.
+pub(crate) fn add(lhs: Vector, rhs: Vector) -> Vector {
+    let t = vaddq_u32(lhs, rhs);
+    let u = vsubq_u32(t, P);
+    vminq_u32(t, u)
+}
+
+/// Emulates `aarch64_neon::mul_31x31_to_hi_31`.
+pub(crate) fn mul_31x31_to_hi_31(lhs: Vector, rhs: Vector) -> Vector {
+    vqdmulhq_s32(lhs, rhs)
+}
+
+/// Emulates `aarch64_neon::monty_mul_hi`.
+pub(crate) fn monty_mul_hi(lhs: Vector, rhs: Vector) -> Vector {
+    mul_31x31_to_hi_31(lhs, rhs)
+}
+
+/// Emulates `aarch64_neon::monty_mul_lo`.
+#[allow(non_snake_case)]
+pub(crate) fn monty_mul_lo(lhs: Vector, rhs: Vector) -> Vector {
+    let rhs_mu_mod_2pow32 = vmulq_u32(rhs, MU);
+    let mu_C_mod_2pow32 = vmulq_u32(lhs, rhs_mu_mod_2pow32);
+    let mu_C_mod_2pow31 = vbicq_u32(mu_C_mod_2pow32, TOP_BIT);
+    mul_31x31_to_hi_31(mu_C_mod_2pow31, P)
+}
+
+/// Emulates `aarch64_neon::mul`.
+pub(crate) fn mul(lhs: Vector, rhs: Vector) -> Vector {
+    let hi = monty_mul_hi(lhs, rhs);
+    let lo = monty_mul_lo(lhs, rhs);
+    sub(hi, lo)
+}
+
+/// Emulates `aarch64_neon::neg`.
+pub(crate) fn neg(val: Vector) -> Vector {
+    let t = vsubq_u32(P, val);
+    let is_zero = vceqzq_u32(val);
+    vbicq_u32(t, is_zero)
+}
+
+/// Emulates `aarch64_neon::sub`.
+pub(crate) fn sub(lhs: Vector, rhs: Vector) -> Vector {
+    let diff = vsubq_u32(lhs, rhs);
+    let underflow = vcltq_u32(lhs, rhs);
+    vmlsq_u32(diff, underflow, P)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::mem::transmute;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::BabyBear;
+
+    const ORDER: u32 = P[0];
+
+    /// The internal (Montgomery) representation of each lane, as loaded by `to_vector`.
+    fn to_vector(vals: [BabyBear; WIDTH]) -> Vector {
+        unsafe {
+            // Safety: `BabyBear` is `repr(transparent)` over `u32`.
+            transmute(vals)
+        }
+    }
+
+    fn from_vector(vector: Vector) -> [BabyBear; WIDTH] {
+        assert!(
+            vector.iter().all(|&x| x < ORDER),
+            "non-canonical {vector:x?}"
+        );
+        unsafe {
+            // Safety: every lane is in `0..P`, so it is a valid `BabyBear` representation.
+            transmute(vector)
+        }
+    }
+
+    /// Internal representations that exercise the boundaries of every reduction step: the ends
+    /// of `0..P`, the neighbourhoods of `P / 2`, `2^27` and `2^30`, and the Montgomery forms of
+    /// small constants.
+    fn edge_values() -> Vec<u32> {
+        let around = |c: u32| c.saturating_sub(8)..(c + 8).min(ORDER);
+        let mut values: Vec<u32> = (0..64)
+            .chain(ORDER - 64..ORDER)
+            .chain(around(ORDER / 2))
+            .chain(around(1 << 27))
+            .chain(around(1 << 30))
+            .chain(around(MU[0]))
+            .chain([1, 2, 3, ORDER - 1].map(|x| to_vector([BabyBear::new(x); WIDTH])[0]))
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+        values
+    }
+
+    /// Checks every emulated routine against scalar `BabyBear` on one pair of vectors.
+    fn check(lhs: Vector, rhs: Vector) {
+        let (a, b) = (from_vector(lhs), from_vector(rhs));
+        let expected = |f: fn(BabyBear, BabyBear) -> BabyBear| -> [BabyBear; WIDTH] {
+            array::from_fn(|i| f(a[i], b[i]))
+        };
+        assert_eq!(from_vector(add(lhs, rhs)), expected(|x, y| x + y));
+        assert_eq!(from_vector(sub(lhs, rhs)), expected(|x, y| x - y));
+        assert_eq!(from_vector(mul(lhs, rhs)), expected(|x, y| x * y));
+        assert_eq!(from_vector(neg(lhs)), expected(|x, _| -x));
+
+        // The two halves of the Montgomery product, individually.
+        for i in 0..WIDTH {
+            let c = lhs[i] as u64 * rhs[i] as u64;
+            let q = (c as u32).wrapping_mul(MU[0]) & !TOP_BIT[0];
+            assert_eq!(monty_mul_hi(lhs, rhs)[i] as u64, c >> 31);
+            assert_eq!(
+                monty_mul_lo(lhs, rhs)[i] as u64,
+                (q as u64 * ORDER as u64) >> 31
+            );
+        }
+    }
+
+    #[test]
+    fn vqdmulhq_s32_edge_cases() {
+        let min = i32::MIN as u32;
+        let max = i32::MAX as u32;
+        assert_eq!(vqdmulhq_s32([min; 4], [min; 4]), [max; 4]);
+        assert_eq!(
+            vqdmulhq_s32([min, max, min, 0], [max, max, 1, min]),
+            [min + 1, max - 1, u32::MAX, 0]
+        );
+        assert_eq!(vqdmulhq_s32([u32::MAX; 4], [1; 4]), [u32::MAX; 4]);
+        assert_eq!(vqdmulhq_s32([u32::MAX; 4], [u32::MAX; 4]), [0; 4]);
+    }
+
+    #[test]
+    fn mul_31x31_to_hi_31_is_high_half() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            let lhs: Vector = array::from_fn(|_| rng.gen::<u32>() >> 1);
+            let rhs: Vector = array::from_fn(|_| rng.gen::<u32>() >> 1);
+            let expected = array::from_fn(|i| ((lhs[i] as u64 * rhs[i] as u64) >> 31) as u32);
+            assert_eq!(mul_31x31_to_hi_31(lhs, rhs), expected);
+        }
+    }
+
+    #[test]
+    fn edge_cases_match_scalar() {
+        let values = edge_values();
+        for &x in &values {
+            // Each `x` meets every edge value, on either side, in every lane.
+            for chunk in values.chunks(WIDTH) {
+                let mut other = [x; WIDTH];
+                other[..chunk.len()].copy_from_slice(chunk);
+                for _ in 0..WIDTH {
+                    other.rotate_left(1);
+                    check([x; WIDTH], other);
+                    check(other, [x; WIDTH]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_match_scalar() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100_000 {
+            let lhs = to_vector(rng.gen());
+            let rhs = to_vector(rng.gen());
+            check(lhs, rhs);
+        }
+    }
+}
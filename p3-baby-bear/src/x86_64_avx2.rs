@@ -0,0 +1,829 @@
+//! `PackedMontyField31AVX2` is exported when AVX2 is enabled at compile time. Otherwise this module
+//! is still compiled on x86_64, but only `dispatch` uses it, from `#[target_feature(enable =
+//! "avx2")]` functions that it calls after detecting AVX2 at runtime. Either way, AVX2 is
+//! available whenever this code runs.
+//!
+//! Everything between the arithmetic operators and the intrinsics is `#[inline(always)]`. The
+//! intrinsics can only be inlined into code compiled with AVX2, so in the runtime-dispatched case
+//! they would remain out-of-line calls unless the whole path is inlined into the
+//! `#[target_feature]` caller first.
+
+use core::arch::x86_64::{self, __m256i};
+use core::iter::{Product, Sum};
+use core::mem::transmute;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use p3_field::{AbstractField, Field, PackedField};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::{BabyBearParameters, KoalaBearParameters, MontyField31, MontyParameters};
+
+const WIDTH: usize = 8;
+const MONTY_MASK: __m256i = unsafe { transmute::<[u32; WIDTH], _>([0x7fffffff; WIDTH]) };
+
+/// Vectorized AVX2 implementation of `MontyField31` arithmetic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)] // This needed to make `transmute`s safe.
+pub struct PackedMontyField31AVX2<MP: MontyParameters>(pub [MontyField31<MP>; WIDTH]);
+
+pub type PackedBabyBearAVX2 = PackedMontyField31AVX2<BabyBearParameters>;
+// Unused unless the module is exported, as `dispatch` only covers `BabyBear`.
+#[cfg_attr(not(target_feature = "avx2"), allow(dead_code))]
+pub type PackedKoalaBearAVX2 = PackedMontyField31AVX2<KoalaBearParameters>;
+
+/// The constants of a `MontyParameters` field, broadcast to every lane.
+pub(crate) trait MontyParametersAVX2 {
+    const PACKED_P: __m256i;
+    const PACKED_MU: __m256i;
+}
+
+impl<MP: MontyParameters> MontyParametersAVX2 for MP {
+    const PACKED_P: __m256i = unsafe { transmute::<[u32; WIDTH], _>([MP::PRIME; WIDTH]) };
+    const PACKED_MU: __m256i = unsafe { transmute::<[u32; WIDTH], _>([MP::MONTY_MU; WIDTH]) };
+}
+
+impl<MP: MontyParameters> PackedMontyField31AVX2<MP> {
+    #[inline]
+    #[must_use]
+    /// Get an arch-specific vector representing the packed values.
+    fn to_vector(self) -> __m256i {
+        unsafe {
+            // Safety: `MontyField31` is `repr(transparent)` so it can be transmuted to `u32`. It
+            // follows that `[MontyField31<MP>; WIDTH]` can be transmuted to `[u32; WIDTH]`, which can be
+            // transmuted to `__m256i`, since arrays are guaranteed to be contiguous in memory.
+            // Finally `PackedMontyField31AVX2` is `repr(transparent)` so it can be transmuted to
+            // `[MontyField31<MP>; WIDTH]`.
+            transmute(self)
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    /// Make a packed field vector from an arch-specific vector.
+    ///
+    /// SAFETY: The caller must ensure that each element of `vector` represents a valid `MontyField31`.
+    /// In particular, each element of vector must be in `0..P` (canonical form).
+    unsafe fn from_vector(vector: __m256i) -> Self {
+        // Safety: It is up to the user to ensure that elements of `vector` represent valid
+        // `MontyField31` values. We must only reason about memory representations. `__m256i` can be
+        // transmuted to `[u32; WIDTH]` (since arrays elements are contiguous in memory), which can
+        // be transmuted to `[MontyField31<MP>; WIDTH]` (since `MontyField31` is `repr(transparent)`), which in
+        // turn can be transmuted to `PackedMontyField31AVX2` (since `PackedMontyField31AVX2` is also
+        // `repr(transparent)`).
+        transmute(vector)
+    }
+
+    /// Copy `value` to all positions in a packed vector. This is the same as
+    /// `From<MontyField31<MP>>::from`, but `const`.
+    #[inline]
+    #[must_use]
+    const fn broadcast(value: MontyField31<MP>) -> Self {
+        Self([value; WIDTH])
+    }
+}
+
+impl<MP: MontyParameters> Add for PackedMontyField31AVX2<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        let lhs = self.to_vector();
+        let rhs = rhs.to_vector();
+        let res = add::<MP>(lhs, rhs);
+        unsafe {
+            // Safety: `add` returns values in canonical form when given values in canonical form.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl<MP: MontyParameters> Mul for PackedMontyField31AVX2<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        let lhs = self.to_vector();
+        let rhs = rhs.to_vector();
+        let res = mul::<MP>(lhs, rhs);
+        unsafe {
+            // Safety: `mul` returns values in canonical form when given values in canonical form.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl<MP: MontyParameters> Neg for PackedMontyField31AVX2<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn neg(self) -> Self {
+        let val = self.to_vector();
+        let res = neg::<MP>(val);
+        unsafe {
+            // Safety: `neg` returns values in canonical form when given values in canonical form.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl<MP: MontyParameters> Sub for PackedMontyField31AVX2<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        let lhs = self.to_vector();
+        let rhs = rhs.to_vector();
+        let res = sub::<MP>(lhs, rhs);
+        unsafe {
+            // Safety: `sub` returns values in canonical form when given values in canonical form.
+            Self::from_vector(res)
+        }
+    }
+}
+
+/// Add two vectors of field elements in canonical form.
+/// If the inputs are not in canonical form, the result is undefined.
+#[inline(always)]
+#[must_use]
+pub(crate) fn add<MP: MontyParametersAVX2>(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // We want this to compile to:
+    //      vpaddd   t, lhs, rhs
+    //      vpsubd   u, t, P
+    //      vpminud  res, t, u
+    // throughput: 1 cyc/vec (8 els/cyc)
+    // latency: 3 cyc
+
+    //   Let `t := lhs + rhs`. We want to return `t mod P`. Recall that `lhs` and `rhs` are in
+    // `0, ..., P - 1`, so `t` is in `0, ..., 2 P - 2 (< 2^32)`. It suffices to return `t` if
+    // `t < P` and `t - P` otherwise.
+    //   Let `u := (t - P) mod 2^32` and `r := unsigned_min(t, u)`.
+    //   If `t` is in `0, ..., P - 1`, then `u` is in `(P - 1 <) 2^32 - P, ..., 2^32 - 1` and
+    // `r = t`. Otherwise `t` is in `P, ..., 2 P - 2`, `u` is in `0, ..., P - 2 (< P)` and `r = u`.
+    // Hence, `r` is `t` if `t < P` and `t - P` otherwise, as desired.
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        let t = x86_64::_mm256_add_epi32(lhs, rhs);
+        let u = x86_64::_mm256_sub_epi32(t, MP::PACKED_P);
+        x86_64::_mm256_min_epu32(t, u)
+    }
+}
+
+/// Subtract vectors of field elements in canonical form.
+/// If the inputs are not in canonical form, the result is undefined.
+#[inline(always)]
+#[must_use]
+pub(crate) fn sub<MP: MontyParametersAVX2>(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // We want this to compile to:
+    //      vpsubd   t, lhs, rhs
+    //      vpaddd   u, t, P
+    //      vpminud  res, t, u
+    // throughput: 1 cyc/vec (8 els/cyc)
+    // latency: 3 cyc
+
+    //   Let `d := lhs - rhs`. We want to return `d mod P`.
+    //   Since `lhs` and `rhs` are both in `0, ..., P - 1`, `d` is in `-P + 1, ..., P - 1`. It
+    // suffices to return `d + P` if `d < 0` and `d` otherwise.
+    //   Let `t := d mod 2^32` and `u := (t + P) mod 2^32`, and return `r := unsigned_min(t, u)`.
+    //   If `d >= 0`, then `t = d` is in `0, ..., P - 1` and `u = d + P` is in `P, ..., 2 P - 1`, so
+    // `r = t`. Otherwise `t = 2^32 + d` is in `2^32 - P + 1, ..., 2^32 - 1` and `u = d + P` is in
+    // `1, ..., P - 1`, so `r = u`, as desired.
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        let t = x86_64::_mm256_sub_epi32(lhs, rhs);
+        let u = x86_64::_mm256_add_epi32(t, MP::PACKED_P);
+        x86_64::_mm256_min_epu32(t, u)
+    }
+}
+
+/// Negate a vector of field elements in canonical form.
+/// If the inputs are not in canonical form, the result is undefined.
+#[inline(always)]
+#[must_use]
+pub(crate) fn neg<MP: MontyParametersAVX2>(val: __m256i) -> __m256i {
+    // We want this to compile to:
+    //      vpsubd   t, P, val
+    //      vpsignd  res, t, val
+    // throughput: .67 cyc/vec (12 els/cyc)
+    // latency: 2 cyc
+
+    //   We want to return (-val) mod P. This is equivalent to returning `0` if `val = 0` and
+    // `P - val` otherwise, since `val` is in `0, ..., P - 1`.
+    //   `vpsignd` zeroes lanes of `t` where `val` is zero and negates lanes where `val` is negative
+    // as a signed integer. Since `val < P < 2^31`, no lane is negative, so `r` is `0` if
+    // `val = 0` and `P - val` otherwise, as desired.
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        let t = x86_64::_mm256_sub_epi32(MP::PACKED_P, val);
+        x86_64::_mm256_sign_epi32(t, val)
+    }
+}
+
+/// Copy the odd-indexed 32-bit lanes of `x` into the even-indexed lanes below them.
+#[inline(always)]
+#[must_use]
+pub(crate) fn movehdup_epi32(x: __m256i) -> __m256i {
+    // This instruction is only available in the floating-point flavor; this distinction is only
+    // for historical reasons and no longer matters. We cast to floats, duplicate, and cast back.
+    // It runs on port 5, unlike a 64-bit shift, which competes with the multiplies.
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        x86_64::_mm256_castps_si256(x86_64::_mm256_movehdup_ps(x86_64::_mm256_castsi256_ps(x)))
+    }
+}
+
+/// Copy the even-indexed 32-bit lanes of `x` into the odd-indexed lanes above them.
+#[inline(always)]
+#[must_use]
+pub(crate) fn moveldup_epi32(x: __m256i) -> __m256i {
+    // See `movehdup_epi32` for why this goes through floats.
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        x86_64::_mm256_castps_si256(x86_64::_mm256_moveldup_ps(x86_64::_mm256_castsi256_ps(x)))
+    }
+}
+
+// MONTGOMERY MULTIPLICATION
+//   We use the same reduction as the NEON implementation, with B = 2^31 so that results agree with
+// the scalar `MontyField31` representation:
+//
+// Constants: P < 2^31 prime
+//            B = 2^31
+//            mu = P^-1 mod B
+// Input: 0 <= C < P B
+// Output: 0 <= R < P such that R = C B^-1 (mod P)
+//   1. Q := mu C mod B
+//   2. D := C - Q P, which is divisible by B
+//   3. T := D / B, which is in -P < T < P
+//   4. R := if T < 0 then T + P else T
+//
+// `_mm256_mul_epu32` multiplies the low 32 bits of each 64-bit lane, giving a full 64-bit product.
+// We therefore handle the even and odd 32-bit lanes separately: the even lanes directly, and the odd
+// lanes after moving them down with `movehdup_epi32`. Each half needs three multiplies: C, Q and
+// Q P.
+//
+// In step 3, the division is exact and `|T| < 2^31`, so the low 32 bits of `D >> 31` (logical
+// shift) are `T` in two's complement, even when `D` is negative. For the odd lanes we want `T` in
+// the high 32 bits instead, which `D << 1` gives us.
+
+/// Montgomery-reduce one half of the lanes: the 64-bit lanes of `prod` each hold a product `C`,
+/// and the result holds `T` (before the final correction) in the low 32 bits of each 64-bit lane.
+#[inline(always)]
+#[must_use]
+fn partial_monty_reduce_evn<MP: MontyParametersAVX2>(prod: __m256i) -> __m256i {
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        let q = x86_64::_mm256_and_si256(x86_64::_mm256_mul_epu32(prod, MP::PACKED_MU), MONTY_MASK);
+        let q_p = x86_64::_mm256_mul_epu32(q, MP::PACKED_P);
+        let d = x86_64::_mm256_sub_epi64(prod, q_p);
+        x86_64::_mm256_srli_epi64::<31>(d)
+    }
+}
+
+/// As `partial_monty_reduce_evn`, but leaves `T` in the high 32 bits of each 64-bit lane.
+#[inline(always)]
+#[must_use]
+fn partial_monty_reduce_odd<MP: MontyParametersAVX2>(prod: __m256i) -> __m256i {
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        let q = x86_64::_mm256_and_si256(x86_64::_mm256_mul_epu32(prod, MP::PACKED_MU), MONTY_MASK);
+        let q_p = x86_64::_mm256_mul_epu32(q, MP::PACKED_P);
+        let d = x86_64::_mm256_sub_epi64(prod, q_p);
+        x86_64::_mm256_slli_epi64::<1>(d)
+    }
+}
+
+/// Multiply vectors of field elements in canonical form.
+/// If the inputs are not in canonical form, the result is undefined.
+#[inline(always)]
+#[must_use]
+fn mul<MP: MontyParametersAVX2>(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // throughput: 4 cyc/vec (2 els/cyc)
+    // latency: 21 cyc
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        let lhs_odd = movehdup_epi32(lhs);
+        let rhs_odd = movehdup_epi32(rhs);
+
+        let prod_evn = x86_64::_mm256_mul_epu32(lhs, rhs);
+        let prod_odd = x86_64::_mm256_mul_epu32(lhs_odd, rhs_odd);
+
+        let t_evn = partial_monty_reduce_evn::<MP>(prod_evn);
+        let t_odd = partial_monty_reduce_odd::<MP>(prod_odd);
+        let t = x86_64::_mm256_blend_epi32::<0b10101010>(t_evn, t_odd);
+
+        // `t` is in `-P + 1, ..., P - 1`; add `P` to the negative lanes, as in `sub`.
+        let u = x86_64::_mm256_add_epi32(t, MP::PACKED_P);
+        x86_64::_mm256_min_epu32(t, u)
+    }
+}
+
+impl<MP: MontyParameters> From<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    #[inline]
+    fn from(value: MontyField31<MP>) -> Self {
+        Self::broadcast(value)
+    }
+}
+
+impl<MP: MontyParameters> Default for PackedMontyField31AVX2<MP> {
+    #[inline]
+    fn default() -> Self {
+        MontyField31::<MP>::default().into()
+    }
+}
+
+impl<MP: MontyParameters> AddAssign for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<MP: MontyParameters> MulAssign for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<MP: MontyParameters> SubAssign for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<MP: MontyParameters> Sum for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        iter.reduce(|lhs, rhs| lhs + rhs).unwrap_or(Self::zero())
+    }
+}
+
+impl<MP: MontyParameters> Product for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        iter.reduce(|lhs, rhs| lhs * rhs).unwrap_or(Self::one())
+    }
+}
+
+impl<MP: MontyParameters> AbstractField for PackedMontyField31AVX2<MP> {
+    type F = MontyField31<MP>;
+
+    fn zero() -> Self {
+        Self::broadcast(MontyField31::<MP>::zero())
+    }
+    fn one() -> Self {
+        Self::broadcast(MontyField31::<MP>::one())
+    }
+    fn two() -> Self {
+        Self::broadcast(MontyField31::<MP>::two())
+    }
+    fn neg_one() -> Self {
+        Self::broadcast(MontyField31::<MP>::neg_one())
+    }
+
+    #[inline]
+    fn from_f(f: Self::F) -> Self {
+        f.into()
+    }
+
+    #[inline]
+    fn from_bool(b: bool) -> Self {
+        MontyField31::<MP>::from_bool(b).into()
+    }
+    #[inline]
+    fn from_canonical_u8(n: u8) -> Self {
+        MontyField31::<MP>::from_canonical_u8(n).into()
+    }
+    #[inline]
+    fn from_canonical_u16(n: u16) -> Self {
+        MontyField31::<MP>::from_canonical_u16(n).into()
+    }
+    #[inline]
+    fn from_canonical_u32(n: u32) -> Self {
+        MontyField31::<MP>::from_canonical_u32(n).into()
+    }
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        MontyField31::<MP>::from_canonical_u64(n).into()
+    }
+    #[inline]
+    fn from_canonical_usize(n: usize) -> Self {
+        MontyField31::<MP>::from_canonical_usize(n).into()
+    }
+
+    #[inline]
+    fn from_wrapped_u32(n: u32) -> Self {
+        MontyField31::<MP>::from_wrapped_u32(n).into()
+    }
+    #[inline]
+    fn from_wrapped_u64(n: u64) -> Self {
+        MontyField31::<MP>::from_wrapped_u64(n).into()
+    }
+
+    #[inline]
+    fn generator() -> Self {
+        MontyField31::<MP>::generator().into()
+    }
+}
+
+impl<MP: MontyParameters> Add<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: MontyField31<MP>) -> Self {
+        self + Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> Mul<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: MontyField31<MP>) -> Self {
+        self * Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> Sub<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: MontyField31<MP>) -> Self {
+        self - Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> AddAssign<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: MontyField31<MP>) {
+        *self += Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> MulAssign<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: MontyField31<MP>) {
+        *self *= Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> SubAssign<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: MontyField31<MP>) {
+        *self -= Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> Sum<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = MontyField31<MP>>,
+    {
+        iter.sum::<MontyField31<MP>>().into()
+    }
+}
+
+impl<MP: MontyParameters> Product<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    #[inline(always)]
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = MontyField31<MP>>,
+    {
+        iter.product::<MontyField31<MP>>().into()
+    }
+}
+
+impl<MP: MontyParameters> Div<MontyField31<MP>> for PackedMontyField31AVX2<MP> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline(always)]
+    fn div(self, rhs: MontyField31<MP>) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl<MP: MontyParameters> Add<PackedMontyField31AVX2<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31AVX2<MP>;
+    #[inline(always)]
+    fn add(self, rhs: PackedMontyField31AVX2<MP>) -> PackedMontyField31AVX2<MP> {
+        PackedMontyField31AVX2::from(self) + rhs
+    }
+}
+
+impl<MP: MontyParameters> Mul<PackedMontyField31AVX2<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31AVX2<MP>;
+    #[inline(always)]
+    fn mul(self, rhs: PackedMontyField31AVX2<MP>) -> PackedMontyField31AVX2<MP> {
+        PackedMontyField31AVX2::from(self) * rhs
+    }
+}
+
+impl<MP: MontyParameters> Sub<PackedMontyField31AVX2<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31AVX2<MP>;
+    #[inline(always)]
+    fn sub(self, rhs: PackedMontyField31AVX2<MP>) -> PackedMontyField31AVX2<MP> {
+        PackedMontyField31AVX2::from(self) - rhs
+    }
+}
+
+impl<MP: MontyParameters> Distribution<PackedMontyField31AVX2<MP>> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> PackedMontyField31AVX2<MP> {
+        PackedMontyField31AVX2(rng.gen())
+    }
+}
+
+#[inline(always)]
+#[must_use]
+pub(crate) fn interleave1(v0: __m256i, v1: __m256i) -> (__m256i, __m256i) {
+    // We want this to compile to:
+    //      vmovshdup  t, v0
+    //      vpblendd   res0, v0, v1 << 32, aah
+    //      vpblendd   res1, t, v1, aah
+    // where the shift of v1 is a vmovsldup.
+    // throughput: 1 cyc/2 vec (16 els/cyc)
+    // latency: 2 cyc
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        let a = x86_64::_mm256_blend_epi32::<0b10101010>(v0, moveldup_epi32(v1));
+        let b = x86_64::_mm256_blend_epi32::<0b10101010>(movehdup_epi32(v0), v1);
+        (a, b)
+    }
+}
+
+#[inline(always)]
+#[must_use]
+pub(crate) fn interleave2(v0: __m256i, v1: __m256i) -> (__m256i, __m256i) {
+    // We want this to compile to:
+    //      vpunpcklqdq  res0, v0, v1
+    //      vpunpckhqdq  res1, v0, v1
+    // throughput: 1 cyc/2 vec (16 els/cyc)
+    // latency: 1 cyc
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        (
+            x86_64::_mm256_unpacklo_epi64(v0, v1),
+            x86_64::_mm256_unpackhi_epi64(v0, v1),
+        )
+    }
+}
+
+#[inline(always)]
+#[must_use]
+pub(crate) fn interleave4(v0: __m256i, v1: __m256i) -> (__m256i, __m256i) {
+    // We want this to compile to:
+    //      vperm2i128  res0, v0, v1, 20h
+    //      vperm2i128  res1, v0, v1, 31h
+    // throughput: 2 cyc/2 vec (8 els/cyc)
+    // latency: 3 cyc
+    unsafe {
+        // Safety: AVX2 is available; see the module documentation.
+        (
+            x86_64::_mm256_permute2x128_si256::<0x20>(v0, v1),
+            x86_64::_mm256_permute2x128_si256::<0x31>(v0, v1),
+        )
+    }
+}
+
+unsafe impl<MP: MontyParameters> PackedField for PackedMontyField31AVX2<MP> {
+    type Scalar = MontyField31<MP>;
+
+    const WIDTH: usize = WIDTH;
+
+    #[inline]
+    fn from_slice(slice: &[MontyField31<MP>]) -> &Self {
+        assert_eq!(slice.len(), Self::WIDTH);
+        unsafe {
+            // Safety: `[MontyField31<MP>; WIDTH]` can be transmuted to `PackedMontyField31AVX2` since the
+            // latter is `repr(transparent)`. They have the same alignment, so the reference cast is
+            // safe too.
+            &*slice.as_ptr().cast()
+        }
+    }
+    #[inline]
+    fn from_slice_mut(slice: &mut [MontyField31<MP>]) -> &mut Self {
+        assert_eq!(slice.len(), Self::WIDTH);
+        unsafe {
+            // Safety: `[MontyField31<MP>; WIDTH]` can be transmuted to `PackedMontyField31AVX2` since the
+            // latter is `repr(transparent)`. They have the same alignment, so the reference cast is
+            // safe too.
+            &mut *slice.as_mut_ptr().cast()
+        }
+    }
+
+    /// Similar to `core:array::from_fn`.
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> MontyField31<MP>>(f: F) -> Self {
+        let vals_arr: [_; WIDTH] = core::array::from_fn(f);
+        Self(vals_arr)
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[MontyField31<MP>] {
+        &self.0[..]
+    }
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [MontyField31<MP>] {
+        &mut self.0[..]
+    }
+
+    #[inline(always)]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (v0, v1) = (self.to_vector(), other.to_vector());
+        let (res0, res1) = match block_len {
+            1 => interleave1(v0, v1),
+            2 => interleave2(v0, v1),
+            4 => interleave4(v0, v1),
+            8 => (v0, v1),
+            _ => panic!("unsupported block_len"),
+        };
+        unsafe {
+            // Safety: all values are in canonical form (we haven't changed them).
+            (Self::from_vector(res0), Self::from_vector(res1))
+        }
+    }
+}
+
+#[cfg(all(test, target_feature = "avx2"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_field::PrimeField32;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{BabyBear, KoalaBear};
+
+    type F = BabyBear;
+    type P = PackedBabyBearAVX2;
+
+    fn packed_from_canonical(vals: [u32; WIDTH]) -> P {
+        PackedMontyField31AVX2(vals.map(F::from_canonical_u32))
+    }
+
+    /// Values around the edges of the range, where overflow and underflow handling matters.
+    fn special_vals() -> [F; WIDTH] {
+        [
+            F::zero(),
+            F::one(),
+            F::two(),
+            F::neg_one(),
+            F::from_canonical_u32(0x3c000000),
+            F::from_canonical_u32(0x3c000001),
+            F::from_canonical_u32(0x40000000),
+            F::from_canonical_u32(0x77ffffff),
+        ]
+    }
+
+    /// Random vectors, plus the special values on either side.
+    fn test_inputs() -> Vec<(P, P)> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let special = PackedMontyField31AVX2(special_vals());
+        let mut inputs: Vec<(P, P)> = (0..1000).map(|_| (rng.gen(), rng.gen())).collect();
+        for _ in 0..100 {
+            inputs.push((special, rng.gen()));
+            inputs.push((rng.gen(), special));
+        }
+        // Every pair of special values.
+        for i in 0..WIDTH {
+            let rotated =
+                PackedMontyField31AVX2(core::array::from_fn(|j| special.0[(i + j) % WIDTH]));
+            inputs.push((special, rotated));
+        }
+        inputs
+    }
+
+    fn check_binary_op(op: fn(P, P) -> P, scalar_op: fn(F, F) -> F) {
+        for (lhs, rhs) in test_inputs() {
+            let res = op(lhs, rhs);
+            for i in 0..WIDTH {
+                assert_eq!(res.0[i], scalar_op(lhs.0[i], rhs.0[i]), "{lhs:?} {rhs:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_vs_scalar() {
+        check_binary_op(|a, b| a + b, |a, b| a + b);
+    }
+
+    #[test]
+    fn test_sub_vs_scalar() {
+        check_binary_op(|a, b| a - b, |a, b| a - b);
+    }
+
+    #[test]
+    fn test_mul_vs_scalar() {
+        check_binary_op(|a, b| a * b, |a, b| a * b);
+    }
+
+    #[test]
+    fn test_neg_vs_scalar() {
+        for (val, _) in test_inputs() {
+            let res = -val;
+            for i in 0..WIDTH {
+                assert_eq!(res.0[i], -val.0[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_results_canonical() {
+        for (lhs, rhs) in test_inputs() {
+            for res in [lhs + rhs, lhs - rhs, lhs * rhs, -lhs] {
+                for x in res.0 {
+                    assert!(x.as_canonical_u32() < F::ORDER_U32);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiplicative_inverse() {
+        let nonzero = special_vals().map(|x| if x.is_zero() { F::generator() } else { x });
+        let vec = PackedMontyField31AVX2(nonzero);
+        let inverses = PackedMontyField31AVX2(vec.0.map(|x| x.inverse()));
+        assert_eq!(vec * inverses, P::one());
+    }
+
+    #[test]
+    fn test_interleave_1() {
+        let vec0 = packed_from_canonical([1, 2, 3, 4, 5, 6, 7, 8]);
+        let vec1 = packed_from_canonical([9, 10, 11, 12, 13, 14, 15, 16]);
+
+        let expected0 = packed_from_canonical([1, 9, 3, 11, 5, 13, 7, 15]);
+        let expected1 = packed_from_canonical([2, 10, 4, 12, 6, 14, 8, 16]);
+
+        let (res0, res1) = vec0.interleave(vec1, 1);
+        assert_eq!(res0, expected0);
+        assert_eq!(res1, expected1);
+    }
+
+    #[test]
+    fn test_interleave_2() {
+        let vec0 = packed_from_canonical([1, 2, 3, 4, 5, 6, 7, 8]);
+        let vec1 = packed_from_canonical([9, 10, 11, 12, 13, 14, 15, 16]);
+
+        let expected0 = packed_from_canonical([1, 2, 9, 10, 5, 6, 13, 14]);
+        let expected1 = packed_from_canonical([3, 4, 11, 12, 7, 8, 15, 16]);
+
+        let (res0, res1) = vec0.interleave(vec1, 2);
+        assert_eq!(res0, expected0);
+        assert_eq!(res1, expected1);
+    }
+
+    #[test]
+    fn test_interleave_4() {
+        let vec0 = packed_from_canonical([1, 2, 3, 4, 5, 6, 7, 8]);
+        let vec1 = packed_from_canonical([9, 10, 11, 12, 13, 14, 15, 16]);
+
+        let expected0 = packed_from_canonical([1, 2, 3, 4, 9, 10, 11, 12]);
+        let expected1 = packed_from_canonical([5, 6, 7, 8, 13, 14, 15, 16]);
+
+        let (res0, res1) = vec0.interleave(vec1, 4);
+        assert_eq!(res0, expected0);
+        assert_eq!(res1, expected1);
+    }
+
+    #[test]
+    fn test_interleave_8() {
+        let vec0 = packed_from_canonical([1, 2, 3, 4, 5, 6, 7, 8]);
+        let vec1 = packed_from_canonical([9, 10, 11, 12, 13, 14, 15, 16]);
+
+        let (res0, res1) = vec0.interleave(vec1, 8);
+        assert_eq!(res0, vec0);
+        assert_eq!(res1, vec1);
+    }
+
+    #[test]
+    fn test_distributivity() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let (a, b, c): (P, P, P) = (rng.gen(), rng.gen(), rng.gen());
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!(a * (b - c), a * b - a * c);
+            assert_eq!(-a * b, -(a * b));
+        }
+    }
+
+    #[test]
+    fn test_koala_bear_vs_scalar() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..1000 {
+            let (lhs, rhs): (PackedKoalaBearAVX2, PackedKoalaBearAVX2) = (rng.gen(), rng.gen());
+            let (sum, diff, prod, neg) = (lhs + rhs, lhs - rhs, lhs * rhs, -lhs);
+            for i in 0..WIDTH {
+                assert_eq!(sum.0[i], lhs.0[i] + rhs.0[i]);
+                assert_eq!(diff.0[i], lhs.0[i] - rhs.0[i]);
+                assert_eq!(prod.0[i], lhs.0[i] * rhs.0[i]);
+                assert_eq!(neg.0[i], -lhs.0[i]);
+                let (a, b) = (lhs.0[i].as_canonical_u32(), rhs.0[i].as_canonical_u32());
+                let expected = a as u64 * b as u64 % KoalaBear::ORDER_U32 as u64;
+                assert_eq!(prod.0[i].as_canonical_u32() as u64, expected);
+            }
+        }
+    }
+}
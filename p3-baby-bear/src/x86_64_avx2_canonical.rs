@@ -0,0 +1,606 @@
+//! `PackedBabyBearCanonicalAVX2`, the AVX2 packing of `BabyBearCanonical`. Unlike the Montgomery
+//! packing, it is only compiled when AVX2 is enabled at compile time, as nothing dispatches to it
+//! at runtime.
+//!
+//! Addition, subtraction and negation are the same as in Montgomery form, since both keep values
+//! in `0..P`, so they are shared with `x86_64_avx2`. Only multiplication differs: it reduces the
+//! product with Barrett reduction rather than Montgomery reduction.
+
+use core::arch::x86_64::{self, __m256i};
+use core::iter::{Product, Sum};
+use core::mem::transmute;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use p3_field::{AbstractField, Field, PackedField};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::x86_64_avx2::{
+    add, interleave1, interleave2, interleave4, movehdup_epi32, moveldup_epi32, neg, sub,
+    MontyParametersAVX2,
+};
+use crate::{BabyBearCanonical, BabyBearParameters, MontyParameters};
+
+const WIDTH: usize = 8;
+const P: u32 = BabyBearParameters::PRIME;
+/// `floor(2^62 / P)`, the Barrett constant.
+const BARRETT_MU: u32 = ((1u64 << 62) / P as u64) as u32;
+const PACKED_MU: __m256i = unsafe { transmute::<[u32; WIDTH], _>([BARRETT_MU; WIDTH]) };
+
+/// Vectorized AVX2 implementation of `BabyBearCanonical` arithmetic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)] // This needed to make `transmute`s safe.
+pub struct PackedBabyBearCanonicalAVX2(pub [BabyBearCanonical; WIDTH]);
+
+impl PackedBabyBearCanonicalAVX2 {
+    #[inline]
+    #[must_use]
+    /// Get an arch-specific vector representing the packed values.
+    fn to_vector(self) -> __m256i {
+        unsafe {
+            // Safety: `BabyBearCanonical` is `repr(transparent)` so it can be transmuted to `u32`.
+            // It follows that `[BabyBearCanonical; WIDTH]` can be transmuted to `[u32; WIDTH]`,
+            // which can be transmuted to `__m256i`, since arrays are guaranteed to be contiguous in
+            // memory. Finally `PackedBabyBearCanonicalAVX2` is `repr(transparent)` so it can be
+            // transmuted to `[BabyBearCanonical; WIDTH]`.
+            transmute(self)
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    /// Make a packed field vector from an arch-specific vector.
+    ///
+    /// SAFETY: The caller must ensure that each element of `vector` is in `0..P`.
+    unsafe fn from_vector(vector: __m256i) -> Self {
+        // Safety: It is up to the user to ensure that elements of `vector` represent valid
+        // `BabyBearCanonical` values. The memory representations agree, as in `to_vector`.
+        transmute(vector)
+    }
+
+    /// Copy `value` to all positions in a packed vector. This is the same as
+    /// `From<BabyBearCanonical>::from`, but `const`.
+    #[inline]
+    #[must_use]
+    const fn broadcast(value: BabyBearCanonical) -> Self {
+        Self([value; WIDTH])
+    }
+}
+
+impl Add for PackedBabyBearCanonicalAVX2 {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        let res = add::<BabyBearParameters>(self.to_vector(), rhs.to_vector());
+        unsafe {
+            // Safety: `add` returns values in `0..P` when given values in `0..P`.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl Mul for PackedBabyBearCanonicalAVX2 {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        let res = mul(self.to_vector(), rhs.to_vector());
+        unsafe {
+            // Safety: `mul` returns values in `0..P` when given values in `0..P`.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl Neg for PackedBabyBearCanonicalAVX2 {
+    type Output = Self;
+    #[inline(always)]
+    fn neg(self) -> Self {
+        let res = neg::<BabyBearParameters>(self.to_vector());
+        unsafe {
+            // Safety: `neg` returns values in `0..P` when given values in `0..P`.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl Sub for PackedBabyBearCanonicalAVX2 {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        let res = sub::<BabyBearParameters>(self.to_vector(), rhs.to_vector());
+        unsafe {
+            // Safety: `sub` returns values in `0..P` when given values in `0..P`.
+            Self::from_vector(res)
+        }
+    }
+}
+
+// BARRETT MULTIPLICATION
+//
+// Constants: P = 15 2^27 + 1 < 2^31
+//            mu = floor(2^62 / P) < 2^32
+// Input: 0 <= C <= (P - 1)^2 < 2^62
+// Output: 0 <= R < P such that R = C (mod P)
+//   1. A := floor(C / 2^30), which is < 2^32
+//   2. Q := floor(A mu / 2^32), an estimate of floor(C / P)
+//   3. T := C - Q P
+//   4. R := if T >= P then T - P else T
+//
+//   Q never exceeds floor(C / P), and falls short of it by at most 1, so 0 <= T < 2 P < 2^32. To
+// see this, write C = A 2^30 + B with B < 2^30 and 2^62 / P = mu + e with e < 1. Then
+//     C / P - A mu / 2^32 = B / P + A e / 2^32 < 2^30 / P + e (P - 1)^2 / 2^62 < 0.54 + 0.35 < 1,
+// and the floor in step 2 loses less than 1 more. As T < 2^32, it can be computed from the low
+// 32 bits of C and Q P alone, and step 4 is the same as the final correction of `add`.
+//
+// `_mm256_mul_epu32` multiplies the low 32 bits of each 64-bit lane, giving a full 64-bit product,
+// so as in Montgomery multiplication the even and odd 32-bit lanes are handled separately, with
+// three multiplies each: C, A mu and Q P.
+
+/// Barrett-reduce one half of the lanes: the 64-bit lanes of `prod` each hold a product `C`, and
+/// the result holds `T` in the low 32 bits of each 64-bit lane.
+#[inline(always)]
+#[must_use]
+fn partial_barrett_reduce(prod: __m256i) -> __m256i {
+    unsafe {
+        // Safety: AVX2 is available, as this module is only compiled with it.
+        let a = x86_64::_mm256_srli_epi64::<30>(prod);
+        let q = movehdup_epi32(x86_64::_mm256_mul_epu32(a, PACKED_MU));
+        let q_p = x86_64::_mm256_mul_epu32(q, BabyBearParameters::PACKED_P);
+        x86_64::_mm256_sub_epi32(prod, q_p)
+    }
+}
+
+/// Multiply vectors of field elements in `0..P`.
+/// If the inputs are not in `0..P`, the result is undefined.
+#[inline(always)]
+#[must_use]
+fn mul(lhs: __m256i, rhs: __m256i) -> __m256i {
+    unsafe {
+        // Safety: AVX2 is available, as this module is only compiled with it.
+        let lhs_odd = movehdup_epi32(lhs);
+        let rhs_odd = movehdup_epi32(rhs);
+
+        let prod_evn = x86_64::_mm256_mul_epu32(lhs, rhs);
+        let prod_odd = x86_64::_mm256_mul_epu32(lhs_odd, rhs_odd);
+
+        let t_evn = partial_barrett_reduce(prod_evn);
+        let t_odd = partial_barrett_reduce(prod_odd);
+        let t = x86_64::_mm256_blend_epi32::<0b10101010>(t_evn, moveldup_epi32(t_odd));
+
+        // `t` is in `0, ..., 2 P - 1`; subtract `P` from the lanes that are at least `P`.
+        let u = x86_64::_mm256_sub_epi32(t, BabyBearParameters::PACKED_P);
+        x86_64::_mm256_min_epu32(t, u)
+    }
+}
+
+impl From<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    #[inline]
+    fn from(value: BabyBearCanonical) -> Self {
+        Self::broadcast(value)
+    }
+}
+
+impl Default for PackedBabyBearCanonicalAVX2 {
+    #[inline]
+    fn default() -> Self {
+        BabyBearCanonical::default().into()
+    }
+}
+
+impl AddAssign for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl MulAssign for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl SubAssign for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        iter.reduce(|lhs, rhs| lhs + rhs).unwrap_or(Self::zero())
+    }
+}
+
+impl Product for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        iter.reduce(|lhs, rhs| lhs * rhs).unwrap_or(Self::one())
+    }
+}
+
+impl AbstractField for PackedBabyBearCanonicalAVX2 {
+    type F = BabyBearCanonical;
+
+    fn zero() -> Self {
+        Self::broadcast(BabyBearCanonical::zero())
+    }
+    fn one() -> Self {
+        Self::broadcast(BabyBearCanonical::one())
+    }
+    fn two() -> Self {
+        Self::broadcast(BabyBearCanonical::two())
+    }
+    fn neg_one() -> Self {
+        Self::broadcast(BabyBearCanonical::neg_one())
+    }
+
+    #[inline]
+    fn from_f(f: Self::F) -> Self {
+        f.into()
+    }
+
+    #[inline]
+    fn from_bool(b: bool) -> Self {
+        BabyBearCanonical::from_bool(b).into()
+    }
+    #[inline]
+    fn from_canonical_u8(n: u8) -> Self {
+        BabyBearCanonical::from_canonical_u8(n).into()
+    }
+    #[inline]
+    fn from_canonical_u16(n: u16) -> Self {
+        BabyBearCanonical::from_canonical_u16(n).into()
+    }
+    #[inline]
+    fn from_canonical_u32(n: u32) -> Self {
+        BabyBearCanonical::from_canonical_u32(n).into()
+    }
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        BabyBearCanonical::from_canonical_u64(n).into()
+    }
+    #[inline]
+    fn from_canonical_usize(n: usize) -> Self {
+        BabyBearCanonical::from_canonical_usize(n).into()
+    }
+
+    #[inline]
+    fn from_wrapped_u32(n: u32) -> Self {
+        BabyBearCanonical::from_wrapped_u32(n).into()
+    }
+    #[inline]
+    fn from_wrapped_u64(n: u64) -> Self {
+        BabyBearCanonical::from_wrapped_u64(n).into()
+    }
+
+    #[inline]
+    fn generator() -> Self {
+        BabyBearCanonical::generator().into()
+    }
+}
+
+impl Add<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: BabyBearCanonical) -> Self {
+        self + Self::from(rhs)
+    }
+}
+
+impl Mul<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: BabyBearCanonical) -> Self {
+        self * Self::from(rhs)
+    }
+}
+
+impl Sub<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: BabyBearCanonical) -> Self {
+        self - Self::from(rhs)
+    }
+}
+
+impl AddAssign<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: BabyBearCanonical) {
+        *self += Self::from(rhs)
+    }
+}
+
+impl MulAssign<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: BabyBearCanonical) {
+        *self *= Self::from(rhs)
+    }
+}
+
+impl SubAssign<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: BabyBearCanonical) {
+        *self -= Self::from(rhs)
+    }
+}
+
+impl Sum<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = BabyBearCanonical>,
+    {
+        iter.sum::<BabyBearCanonical>().into()
+    }
+}
+
+impl Product<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    #[inline(always)]
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = BabyBearCanonical>,
+    {
+        iter.product::<BabyBearCanonical>().into()
+    }
+}
+
+impl Div<BabyBearCanonical> for PackedBabyBearCanonicalAVX2 {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline(always)]
+    fn div(self, rhs: BabyBearCanonical) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl Add<PackedBabyBearCanonicalAVX2> for BabyBearCanonical {
+    type Output = PackedBabyBearCanonicalAVX2;
+    #[inline(always)]
+    fn add(self, rhs: PackedBabyBearCanonicalAVX2) -> PackedBabyBearCanonicalAVX2 {
+        PackedBabyBearCanonicalAVX2::from(self) + rhs
+    }
+}
+
+impl Mul<PackedBabyBearCanonicalAVX2> for BabyBearCanonical {
+    type Output = PackedBabyBearCanonicalAVX2;
+    #[inline(always)]
+    fn mul(self, rhs: PackedBabyBearCanonicalAVX2) -> PackedBabyBearCanonicalAVX2 {
+        PackedBabyBearCanonicalAVX2::from(self) * rhs
+    }
+}
+
+impl Sub<PackedBabyBearCanonicalAVX2> for BabyBearCanonical {
+    type Output = PackedBabyBearCanonicalAVX2;
+    #[inline(always)]
+    fn sub(self, rhs: PackedBabyBearCanonicalAVX2) -> PackedBabyBearCanonicalAVX2 {
+        PackedBabyBearCanonicalAVX2::from(self) - rhs
+    }
+}
+
+impl Distribution<PackedBabyBearCanonicalAVX2> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> PackedBabyBearCanonicalAVX2 {
+        PackedBabyBearCanonicalAVX2(rng.gen())
+    }
+}
+
+unsafe impl PackedField for PackedBabyBearCanonicalAVX2 {
+    type Scalar = BabyBearCanonical;
+
+    const WIDTH: usize = WIDTH;
+
+    #[inline]
+    fn from_slice(slice: &[BabyBearCanonical]) -> &Self {
+        assert_eq!(slice.len(), Self::WIDTH);
+        unsafe {
+            // Safety: `[BabyBearCanonical; WIDTH]` can be transmuted to
+            // `PackedBabyBearCanonicalAVX2` since the latter is `repr(transparent)`. They have the
+            // same alignment, so the reference cast is safe too.
+            &*slice.as_ptr().cast()
+        }
+    }
+    #[inline]
+    fn from_slice_mut(slice: &mut [BabyBearCanonical]) -> &mut Self {
+        assert_eq!(slice.len(), Self::WIDTH);
+        unsafe {
+            // Safety: as in `from_slice`.
+            &mut *slice.as_mut_ptr().cast()
+        }
+    }
+
+    /// Similar to `core:array::from_fn`.
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> BabyBearCanonical>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[BabyBearCanonical] {
+        &self.0[..]
+    }
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [BabyBearCanonical] {
+        &mut self.0[..]
+    }
+
+    #[inline(always)]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (v0, v1) = (self.to_vector(), other.to_vector());
+        let (res0, res1) = match block_len {
+            1 => interleave1(v0, v1),
+            2 => interleave2(v0, v1),
+            4 => interleave4(v0, v1),
+            8 => (v0, v1),
+            _ => panic!("unsupported block_len"),
+        };
+        unsafe {
+            // Safety: all values are in `0..P` (we haven't changed them).
+            (Self::from_vector(res0), Self::from_vector(res1))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_field::PrimeField32;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    type F = BabyBearCanonical;
+    type P = PackedBabyBearCanonicalAVX2;
+
+    fn packed_from_canonical(vals: [u32; WIDTH]) -> P {
+        PackedBabyBearCanonicalAVX2(vals.map(F::from_canonical_u32))
+    }
+
+    /// Values around the edges of the range, where overflow, underflow and the Barrett estimate
+    /// matter.
+    fn special_vals() -> [F; WIDTH] {
+        [
+            F::zero(),
+            F::one(),
+            F::two(),
+            F::neg_one(),
+            F::from_canonical_u32(0x3c000000),
+            F::from_canonical_u32(0x3fffffff),
+            F::from_canonical_u32(0x40000000),
+            F::from_canonical_u32(0x77ffffff),
+        ]
+    }
+
+    /// Random vectors, plus the special values on either side.
+    fn test_inputs() -> Vec<(P, P)> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let special = PackedBabyBearCanonicalAVX2(special_vals());
+        let mut inputs: Vec<(P, P)> = (0..1000).map(|_| (rng.gen(), rng.gen())).collect();
+        for _ in 0..100 {
+            inputs.push((special, rng.gen()));
+            inputs.push((rng.gen(), special));
+        }
+        // Every pair of special values.
+        for i in 0..WIDTH {
+            let rotated =
+                PackedBabyBearCanonicalAVX2(core::array::from_fn(|j| special.0[(i + j) % WIDTH]));
+            inputs.push((special, rotated));
+        }
+        inputs
+    }
+
+    fn check_binary_op(op: fn(P, P) -> P, scalar_op: fn(F, F) -> F) {
+        for (lhs, rhs) in test_inputs() {
+            let res = op(lhs, rhs);
+            for i in 0..WIDTH {
+                assert_eq!(res.0[i], scalar_op(lhs.0[i], rhs.0[i]), "{lhs:?} {rhs:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_vs_scalar() {
+        check_binary_op(|a, b| a + b, |a, b| a + b);
+    }
+
+    #[test]
+    fn test_sub_vs_scalar() {
+        check_binary_op(|a, b| a - b, |a, b| a - b);
+    }
+
+    #[test]
+    fn test_mul_vs_scalar() {
+        check_binary_op(|a, b| a * b, |a, b| a * b);
+    }
+
+    #[test]
+    fn test_neg_vs_scalar() {
+        for (val, _) in test_inputs() {
+            let res = -val;
+            for i in 0..WIDTH {
+                assert_eq!(res.0[i], -val.0[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_results_canonical() {
+        for (lhs, rhs) in test_inputs() {
+            for res in [lhs + rhs, lhs - rhs, lhs * rhs, -lhs] {
+                for x in res.0 {
+                    assert!(x.as_canonical_u32() < F::ORDER_U32);
+                }
+            }
+        }
+    }
+
+    /// The products whose Barrett estimate is furthest from the true quotient: the largest
+    /// inputs, where `A e / 2^32` is largest, and products just below a multiple of `P`.
+    #[test]
+    fn test_mul_barrett_edges() {
+        let top = |k: u32| F::from_canonical_u32(F::ORDER_U32 - 1 - k);
+        let lhs = PackedBabyBearCanonicalAVX2(core::array::from_fn(|i| top(i as u32)));
+        for k in 0..1000 {
+            let rhs = PackedBabyBearCanonicalAVX2(core::array::from_fn(|i| top(k + i as u32)));
+            let res = lhs * rhs;
+            for i in 0..WIDTH {
+                let expected = lhs.0[i].as_canonical_u32() as u64
+                    * rhs.0[i].as_canonical_u32() as u64
+                    % F::ORDER_U32 as u64;
+                assert_eq!(res.0[i].as_canonical_u32() as u64, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiplicative_inverse() {
+        let nonzero = special_vals().map(|x| if x.is_zero() { F::generator() } else { x });
+        let vec = PackedBabyBearCanonicalAVX2(nonzero);
+        let inverses = PackedBabyBearCanonicalAVX2(vec.0.map(|x| x.inverse()));
+        assert_eq!(vec * inverses, P::one());
+    }
+
+    #[test]
+    fn test_interleave() {
+        let vec0 = packed_from_canonical([1, 2, 3, 4, 5, 6, 7, 8]);
+        let vec1 = packed_from_canonical([9, 10, 11, 12, 13, 14, 15, 16]);
+
+        let (res0, res1) = vec0.interleave(vec1, 1);
+        assert_eq!(res0, packed_from_canonical([1, 9, 3, 11, 5, 13, 7, 15]));
+        assert_eq!(res1, packed_from_canonical([2, 10, 4, 12, 6, 14, 8, 16]));
+
+        let (res0, res1) = vec0.interleave(vec1, 2);
+        assert_eq!(res0, packed_from_canonical([1, 2, 9, 10, 5, 6, 13, 14]));
+        assert_eq!(res1, packed_from_canonical([3, 4, 11, 12, 7, 8, 15, 16]));
+
+        let (res0, res1) = vec0.interleave(vec1, 4);
+        assert_eq!(res0, packed_from_canonical([1, 2, 3, 4, 9, 10, 11, 12]));
+        assert_eq!(res1, packed_from_canonical([5, 6, 7, 8, 13, 14, 15, 16]));
+
+        let (res0, res1) = vec0.interleave(vec1, 8);
+        assert_eq!(res0, vec0);
+        assert_eq!(res1, vec1);
+    }
+
+    #[test]
+    fn test_distributivity() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let (a, b, c): (P, P, P) = (rng.gen(), rng.gen(), rng.gen());
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!(a * (b - c), a * b - a * c);
+            assert_eq!(-a * b, -(a * b));
+        }
+    }
+}
@@ -0,0 +1,751 @@
+//! `PackedMontyField31AVX512` is exported when AVX-512F is enabled at compile time. Otherwise this
+//! module is still compiled on x86_64, but only `dispatch` uses it, from `#[target_feature(enable
+//! = "avx512f")]` functions that it calls after detecting AVX-512F at runtime. Either way,
+//! AVX-512F is available whenever this code runs.
+//!
+//! Everything between the arithmetic operators and the intrinsics is `#[inline(always)]`, as in
+//! `x86_64_avx2.rs`, so that the intrinsics are inlined into the `#[target_feature]` caller.
+
+use core::arch::x86_64::{self, __m512i, __mmask16};
+use core::iter::{Product, Sum};
+use core::mem::transmute;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use p3_field::{AbstractField, Field, PackedField};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::{BabyBearParameters, KoalaBearParameters, MontyField31, MontyParameters};
+
+const WIDTH: usize = 16;
+const MONTY_MASK: __m512i = unsafe { transmute::<[u32; WIDTH], _>([0x7fffffff; WIDTH]) };
+const ODD_LANES: __mmask16 = 0b1010101010101010;
+
+/// Vectorized AVX-512 implementation of `MontyField31` arithmetic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)] // This needed to make `transmute`s safe.
+pub struct PackedMontyField31AVX512<MP: MontyParameters>(pub [MontyField31<MP>; WIDTH]);
+
+pub type PackedBabyBearAVX512 = PackedMontyField31AVX512<BabyBearParameters>;
+// Unused unless the module is exported, as `dispatch` only covers `BabyBear`.
+#[cfg_attr(not(target_feature = "avx512f"), allow(dead_code))]
+pub type PackedKoalaBearAVX512 = PackedMontyField31AVX512<KoalaBearParameters>;
+
+/// The constants of a `MontyParameters` field, broadcast to every lane.
+trait MontyParametersAVX512 {
+    const PACKED_P: __m512i;
+    const PACKED_MU: __m512i;
+}
+
+impl<MP: MontyParameters> MontyParametersAVX512 for MP {
+    const PACKED_P: __m512i = unsafe { transmute::<[u32; WIDTH], _>([MP::PRIME; WIDTH]) };
+    const PACKED_MU: __m512i = unsafe { transmute::<[u32; WIDTH], _>([MP::MONTY_MU; WIDTH]) };
+}
+
+impl<MP: MontyParameters> PackedMontyField31AVX512<MP> {
+    #[inline]
+    #[must_use]
+    /// Get an arch-specific vector representing the packed values.
+    fn to_vector(self) -> __m512i {
+        unsafe {
+            // Safety: `MontyField31` is `repr(transparent)` so it can be transmuted to `u32`. It
+            // follows that `[MontyField31<MP>; WIDTH]` can be transmuted to `[u32; WIDTH]`, which can be
+            // transmuted to `__m512i`, since arrays are guaranteed to be contiguous in memory.
+            // Finally `PackedMontyField31AVX512` is `repr(transparent)` so it can be transmuted to
+            // `[MontyField31<MP>; WIDTH]`.
+            transmute(self)
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    /// Make a packed field vector from an arch-specific vector.
+    ///
+    /// SAFETY: The caller must ensure that each element of `vector` represents a valid `MontyField31`.
+    /// In particular, each element of vector must be in `0..P` (canonical form).
+    unsafe fn from_vector(vector: __m512i) -> Self {
+        // Safety: It is up to the user to ensure that elements of `vector` represent valid
+        // `MontyField31` values. We must only reason about memory representations. `__m512i` can be
+        // transmuted to `[u32; WIDTH]` (since arrays elements are contiguous in memory), which can
+        // be transmuted to `[MontyField31<MP>; WIDTH]` (since `MontyField31` is `repr(transparent)`), which in
+        // turn can be transmuted to `PackedMontyField31AVX512` (since `PackedMontyField31AVX512` is also
+        // `repr(transparent)`).
+        transmute(vector)
+    }
+
+    /// Copy `value` to all positions in a packed vector. This is the same as
+    /// `From<MontyField31<MP>>::from`, but `const`.
+    #[inline]
+    #[must_use]
+    const fn broadcast(value: MontyField31<MP>) -> Self {
+        Self([value; WIDTH])
+    }
+}
+
+impl<MP: MontyParameters> Add for PackedMontyField31AVX512<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        let lhs = self.to_vector();
+        let rhs = rhs.to_vector();
+        let res = add::<MP>(lhs, rhs);
+        unsafe {
+            // Safety: `add` returns values in canonical form when given values in canonical form.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl<MP: MontyParameters> Mul for PackedMontyField31AVX512<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        let lhs = self.to_vector();
+        let rhs = rhs.to_vector();
+        let res = mul::<MP>(lhs, rhs);
+        unsafe {
+            // Safety: `mul` returns values in canonical form when given values in canonical form.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl<MP: MontyParameters> Neg for PackedMontyField31AVX512<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn neg(self) -> Self {
+        let val = self.to_vector();
+        let res = neg::<MP>(val);
+        unsafe {
+            // Safety: `neg` returns values in canonical form when given values in canonical form.
+            Self::from_vector(res)
+        }
+    }
+}
+
+impl<MP: MontyParameters> Sub for PackedMontyField31AVX512<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        let lhs = self.to_vector();
+        let rhs = rhs.to_vector();
+        let res = sub::<MP>(lhs, rhs);
+        unsafe {
+            // Safety: `sub` returns values in canonical form when given values in canonical form.
+            Self::from_vector(res)
+        }
+    }
+}
+
+/// Add two vectors of field elements in canonical form.
+/// If the inputs are not in canonical form, the result is undefined.
+#[inline(always)]
+#[must_use]
+fn add<MP: MontyParametersAVX512>(lhs: __m512i, rhs: __m512i) -> __m512i {
+    // We want this to compile to:
+    //      vpaddd   t, lhs, rhs
+    //      vpsubd   u, t, P
+    //      vpminud  res, t, u
+
+    // This is the AVX2 algorithm on wider vectors; see `x86_64_avx2::add` for why it is correct.
+    unsafe {
+        // Safety: AVX-512F is available; see the module documentation.
+        let t = x86_64::_mm512_add_epi32(lhs, rhs);
+        let u = x86_64::_mm512_sub_epi32(t, MP::PACKED_P);
+        x86_64::_mm512_min_epu32(t, u)
+    }
+}
+
+/// Subtract vectors of field elements in canonical form.
+/// If the inputs are not in canonical form, the result is undefined.
+#[inline(always)]
+#[must_use]
+fn sub<MP: MontyParametersAVX512>(lhs: __m512i, rhs: __m512i) -> __m512i {
+    // We want this to compile to:
+    //      vpsubd   t, lhs, rhs
+    //      vpaddd   u, t, P
+    //      vpminud  res, t, u
+
+    // This is the AVX2 algorithm on wider vectors; see `x86_64_avx2::sub` for why it is correct.
+    unsafe {
+        // Safety: AVX-512F is available; see the module documentation.
+        let t = x86_64::_mm512_sub_epi32(lhs, rhs);
+        let u = x86_64::_mm512_add_epi32(t, MP::PACKED_P);
+        x86_64::_mm512_min_epu32(t, u)
+    }
+}
+
+/// Negate a vector of field elements in canonical form.
+/// If the inputs are not in canonical form, the result is undefined.
+#[inline(always)]
+#[must_use]
+fn neg<MP: MontyParametersAVX512>(val: __m512i) -> __m512i {
+    // We want this to compile to:
+    //      vptestmd  nonzero, val, val
+    //      vpsubd    res{nonzero}{z}, P, val
+
+    //   We want to return (-val) mod P. This is equivalent to returning `0` if `val = 0` and
+    // `P - val` otherwise, since `val` is in `0, ..., P - 1`. AVX-512 has no `vpsignd`, but a
+    // zero-masked subtraction does the same job.
+    unsafe {
+        // Safety: AVX-512F is available; see the module documentation.
+        let nonzero = x86_64::_mm512_test_epi32_mask(val, val);
+        x86_64::_mm512_maskz_sub_epi32(nonzero, MP::PACKED_P, val)
+    }
+}
+
+/// Copy the odd-indexed 32-bit lanes of `x` into the even-indexed lanes below them.
+#[inline(always)]
+#[must_use]
+fn movehdup_epi32(x: __m512i) -> __m512i {
+    // As in AVX2, this instruction only exists in the floating-point flavor, and it runs on a
+    // different port from the multiplies, unlike a 64-bit shift.
+    unsafe {
+        // Safety: AVX-512F is available; see the module documentation.
+        x86_64::_mm512_castps_si512(x86_64::_mm512_movehdup_ps(x86_64::_mm512_castsi512_ps(x)))
+    }
+}
+
+// MONTGOMERY MULTIPLICATION
+//   This is the AVX2 algorithm on wider vectors; see the comment in `x86_64_avx2.rs`. The only
+// difference is that the even and odd halves are merged with a mask register instead of an
+// immediate blend.
+
+/// Montgomery-reduce one half of the lanes: the 64-bit lanes of `prod` each hold a product `C`,
+/// and the result holds `T` (before the final correction) in the low 32 bits of each 64-bit lane.
+#[inline(always)]
+#[must_use]
+fn partial_monty_reduce_evn<MP: MontyParametersAVX512>(prod: __m512i) -> __m512i {
+    unsafe {
+        // Safety: AVX-512F is available; see the module documentation.
+        let q = x86_64::_mm512_and_si512(x86_64::_mm512_mul_epu32(prod, MP::PACKED_MU), MONTY_MASK);
+        let q_p = x86_64::_mm512_mul_epu32(q, MP::PACKED_P);
+        let d = x86_64::_mm512_sub_epi64(prod, q_p);
+        x86_64::_mm512_srli_epi64::<31>(d)
+    }
+}
+
+/// As `partial_monty_reduce_evn`, but leaves `T` in the high 32 bits of each 64-bit lane.
+#[inline(always)]
+#[must_use]
+fn partial_monty_reduce_odd<MP: MontyParametersAVX512>(prod: __m512i) -> __m512i {
+    unsafe {
+        // Safety: AVX-512F is available; see the module documentation.
+        let q = x86_64::_mm512_and_si512(x86_64::_mm512_mul_epu32(prod, MP::PACKED_MU), MONTY_MASK);
+        let q_p = x86_64::_mm512_mul_epu32(q, MP::PACKED_P);
+        let d = x86_64::_mm512_sub_epi64(prod, q_p);
+        x86_64::_mm512_slli_epi64::<1>(d)
+    }
+}
+
+/// Multiply vectors of field elements in canonical form.
+/// If the inputs are not in canonical form, the result is undefined.
+#[inline(always)]
+#[must_use]
+fn mul<MP: MontyParametersAVX512>(lhs: __m512i, rhs: __m512i) -> __m512i {
+    unsafe {
+        // Safety: AVX-512F is available; see the module documentation.
+        let lhs_odd = movehdup_epi32(lhs);
+        let rhs_odd = movehdup_epi32(rhs);
+
+        let prod_evn = x86_64::_mm512_mul_epu32(lhs, rhs);
+        let prod_odd = x86_64::_mm512_mul_epu32(lhs_odd, rhs_odd);
+
+        let t_evn = partial_monty_reduce_evn::<MP>(prod_evn);
+        let t_odd = partial_monty_reduce_odd::<MP>(prod_odd);
+        let t = x86_64::_mm512_mask_blend_epi32(ODD_LANES, t_evn, t_odd);
+
+        // `t` is in `-P + 1, ..., P - 1`; add `P` to the negative lanes, as in `sub`.
+        let u = x86_64::_mm512_add_epi32(t, MP::PACKED_P);
+        x86_64::_mm512_min_epu32(t, u)
+    }
+}
+
+impl<MP: MontyParameters> From<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    #[inline]
+    fn from(value: MontyField31<MP>) -> Self {
+        Self::broadcast(value)
+    }
+}
+
+impl<MP: MontyParameters> Default for PackedMontyField31AVX512<MP> {
+    #[inline]
+    fn default() -> Self {
+        MontyField31::<MP>::default().into()
+    }
+}
+
+impl<MP: MontyParameters> AddAssign for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<MP: MontyParameters> MulAssign for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<MP: MontyParameters> SubAssign for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<MP: MontyParameters> Sum for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        iter.reduce(|lhs, rhs| lhs + rhs).unwrap_or(Self::zero())
+    }
+}
+
+impl<MP: MontyParameters> Product for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        iter.reduce(|lhs, rhs| lhs * rhs).unwrap_or(Self::one())
+    }
+}
+
+impl<MP: MontyParameters> AbstractField for PackedMontyField31AVX512<MP> {
+    type F = MontyField31<MP>;
+
+    fn zero() -> Self {
+        Self::broadcast(MontyField31::<MP>::zero())
+    }
+    fn one() -> Self {
+        Self::broadcast(MontyField31::<MP>::one())
+    }
+    fn two() -> Self {
+        Self::broadcast(MontyField31::<MP>::two())
+    }
+    fn neg_one() -> Self {
+        Self::broadcast(MontyField31::<MP>::neg_one())
+    }
+
+    #[inline]
+    fn from_f(f: Self::F) -> Self {
+        f.into()
+    }
+
+    #[inline]
+    fn from_bool(b: bool) -> Self {
+        MontyField31::<MP>::from_bool(b).into()
+    }
+    #[inline]
+    fn from_canonical_u8(n: u8) -> Self {
+        MontyField31::<MP>::from_canonical_u8(n).into()
+    }
+    #[inline]
+    fn from_canonical_u16(n: u16) -> Self {
+        MontyField31::<MP>::from_canonical_u16(n).into()
+    }
+    #[inline]
+    fn from_canonical_u32(n: u32) -> Self {
+        MontyField31::<MP>::from_canonical_u32(n).into()
+    }
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        MontyField31::<MP>::from_canonical_u64(n).into()
+    }
+    #[inline]
+    fn from_canonical_usize(n: usize) -> Self {
+        MontyField31::<MP>::from_canonical_usize(n).into()
+    }
+
+    #[inline]
+    fn from_wrapped_u32(n: u32) -> Self {
+        MontyField31::<MP>::from_wrapped_u32(n).into()
+    }
+    #[inline]
+    fn from_wrapped_u64(n: u64) -> Self {
+        MontyField31::<MP>::from_wrapped_u64(n).into()
+    }
+
+    #[inline]
+    fn generator() -> Self {
+        MontyField31::<MP>::generator().into()
+    }
+}
+
+impl<MP: MontyParameters> Add<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: MontyField31<MP>) -> Self {
+        self + Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> Mul<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: MontyField31<MP>) -> Self {
+        self * Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> Sub<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: MontyField31<MP>) -> Self {
+        self - Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> AddAssign<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: MontyField31<MP>) {
+        *self += Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> MulAssign<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: MontyField31<MP>) {
+        *self *= Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> SubAssign<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: MontyField31<MP>) {
+        *self -= Self::from(rhs)
+    }
+}
+
+impl<MP: MontyParameters> Sum<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = MontyField31<MP>>,
+    {
+        iter.sum::<MontyField31<MP>>().into()
+    }
+}
+
+impl<MP: MontyParameters> Product<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    #[inline(always)]
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = MontyField31<MP>>,
+    {
+        iter.product::<MontyField31<MP>>().into()
+    }
+}
+
+impl<MP: MontyParameters> Div<MontyField31<MP>> for PackedMontyField31AVX512<MP> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline(always)]
+    fn div(self, rhs: MontyField31<MP>) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl<MP: MontyParameters> Add<PackedMontyField31AVX512<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31AVX512<MP>;
+    #[inline(always)]
+    fn add(self, rhs: PackedMontyField31AVX512<MP>) -> PackedMontyField31AVX512<MP> {
+        PackedMontyField31AVX512::from(self) + rhs
+    }
+}
+
+impl<MP: MontyParameters> Mul<PackedMontyField31AVX512<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31AVX512<MP>;
+    #[inline(always)]
+    fn mul(self, rhs: PackedMontyField31AVX512<MP>) -> PackedMontyField31AVX512<MP> {
+        PackedMontyField31AVX512::from(self) * rhs
+    }
+}
+
+impl<MP: MontyParameters> Sub<PackedMontyField31AVX512<MP>> for MontyField31<MP> {
+    type Output = PackedMontyField31AVX512<MP>;
+    #[inline(always)]
+    fn sub(self, rhs: PackedMontyField31AVX512<MP>) -> PackedMontyField31AVX512<MP> {
+        PackedMontyField31AVX512::from(self) - rhs
+    }
+}
+
+impl<MP: MontyParameters> Distribution<PackedMontyField31AVX512<MP>> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> PackedMontyField31AVX512<MP> {
+        PackedMontyField31AVX512(rng.gen())
+    }
+}
+
+/// Indices for `_mm512_permutex2var_epi32` that interleave blocks of `block_len` lanes. Index
+/// `i` selects lane `i` of the first operand and `16 + i` lane `i` of the second.
+const fn interleave_indices(block_len: usize, odd: bool) -> __m512i {
+    let mut indices = [0u32; WIDTH];
+    let mut i = 0;
+    while i < WIDTH {
+        indices[i] = match (i & block_len == 0, odd) {
+            (true, false) => i,
+            (false, false) => WIDTH + i - block_len,
+            (true, true) => i + block_len,
+            (false, true) => WIDTH + i,
+        } as u32;
+        i += 1;
+    }
+    unsafe { transmute(indices) }
+}
+
+const INTERLEAVE_INDICES: [(__m512i, __m512i); 4] = [
+    (interleave_indices(1, false), interleave_indices(1, true)),
+    (interleave_indices(2, false), interleave_indices(2, true)),
+    (interleave_indices(4, false), interleave_indices(4, true)),
+    (interleave_indices(8, false), interleave_indices(8, true)),
+];
+
+#[inline(always)]
+#[must_use]
+fn interleave(v0: __m512i, v1: __m512i, indices: (__m512i, __m512i)) -> (__m512i, __m512i) {
+    // We want this to compile to:
+    //      vmovdqa32  res0, idx0
+    //      vpermt2d   res0, v0, v1
+    //      vmovdqa32  res1, idx1
+    //      vpermt2d   res1, v0, v1
+    // A single two-source permute handles every block length, at the cost of an index register.
+    unsafe {
+        // Safety: AVX-512F is available; see the module documentation.
+        (
+            x86_64::_mm512_permutex2var_epi32(v0, indices.0, v1),
+            x86_64::_mm512_permutex2var_epi32(v0, indices.1, v1),
+        )
+    }
+}
+
+unsafe impl<MP: MontyParameters> PackedField for PackedMontyField31AVX512<MP> {
+    type Scalar = MontyField31<MP>;
+
+    const WIDTH: usize = WIDTH;
+
+    #[inline]
+    fn from_slice(slice: &[MontyField31<MP>]) -> &Self {
+        assert_eq!(slice.len(), Self::WIDTH);
+        unsafe {
+            // Safety: `[MontyField31<MP>; WIDTH]` can be transmuted to `PackedMontyField31AVX512` since the
+            // latter is `repr(transparent)`. They have the same alignment, so the reference cast is
+            // safe too.
+            &*slice.as_ptr().cast()
+        }
+    }
+    #[inline]
+    fn from_slice_mut(slice: &mut [MontyField31<MP>]) -> &mut Self {
+        assert_eq!(slice.len(), Self::WIDTH);
+        unsafe {
+            // Safety: `[MontyField31<MP>; WIDTH]` can be transmuted to `PackedMontyField31AVX512` since the
+            // latter is `repr(transparent)`. They have the same alignment, so the reference cast is
+            // safe too.
+            &mut *slice.as_mut_ptr().cast()
+        }
+    }
+
+    /// Similar to `core:array::from_fn`.
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> MontyField31<MP>>(f: F) -> Self {
+        let vals_arr: [_; WIDTH] = core::array::from_fn(f);
+        Self(vals_arr)
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[MontyField31<MP>] {
+        &self.0[..]
+    }
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [MontyField31<MP>] {
+        &mut self.0[..]
+    }
+
+    #[inline(always)]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (v0, v1) = (self.to_vector(), other.to_vector());
+        let (res0, res1) = match block_len {
+            1 => interleave(v0, v1, INTERLEAVE_INDICES[0]),
+            2 => interleave(v0, v1, INTERLEAVE_INDICES[1]),
+            4 => interleave(v0, v1, INTERLEAVE_INDICES[2]),
+            8 => interleave(v0, v1, INTERLEAVE_INDICES[3]),
+            16 => (v0, v1),
+            _ => panic!("unsupported block_len"),
+        };
+        unsafe {
+            // Safety: all values are in canonical form (we haven't changed them).
+            (Self::from_vector(res0), Self::from_vector(res1))
+        }
+    }
+}
+
+#[cfg(all(test, target_feature = "avx512f"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_field::PrimeField32;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{BabyBear, KoalaBear};
+
+    type F = BabyBear;
+    type P = PackedBabyBearAVX512;
+
+    fn packed_from_canonical(vals: [u32; WIDTH]) -> P {
+        PackedMontyField31AVX512(vals.map(F::from_canonical_u32))
+    }
+
+    /// Values around the edges of the range, where overflow and underflow handling matters.
+    fn special_vals() -> [F; WIDTH] {
+        [
+            0, 1, 2, 3, 0x3c000000, 0x3c000001, 0x40000000, 0x07ffffff, 0x08000000, 0x08000001,
+            0x3fffffff, 0x77fffffe, 0x77ffffff, 0x78000000, 0x7fff, 0x10000,
+        ]
+        .map(F::from_canonical_u32)
+    }
+
+    /// Random vectors, plus the special values on either side.
+    fn test_inputs() -> Vec<(P, P)> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let special = PackedMontyField31AVX512(special_vals());
+        let mut inputs: Vec<(P, P)> = (0..1000).map(|_| (rng.gen(), rng.gen())).collect();
+        for _ in 0..100 {
+            inputs.push((special, rng.gen()));
+            inputs.push((rng.gen(), special));
+        }
+        // Every pair of special values.
+        for i in 0..WIDTH {
+            let rotated =
+                PackedMontyField31AVX512(core::array::from_fn(|j| special.0[(i + j) % WIDTH]));
+            inputs.push((special, rotated));
+        }
+        inputs
+    }
+
+    fn check_binary_op(op: fn(P, P) -> P, scalar_op: fn(F, F) -> F) {
+        for (lhs, rhs) in test_inputs() {
+            let res = op(lhs, rhs);
+            for i in 0..WIDTH {
+                assert_eq!(res.0[i], scalar_op(lhs.0[i], rhs.0[i]), "{lhs:?} {rhs:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_vs_scalar() {
+        check_binary_op(|a, b| a + b, |a, b| a + b);
+    }
+
+    #[test]
+    fn test_sub_vs_scalar() {
+        check_binary_op(|a, b| a - b, |a, b| a - b);
+    }
+
+    #[test]
+    fn test_mul_vs_scalar() {
+        check_binary_op(|a, b| a * b, |a, b| a * b);
+    }
+
+    #[test]
+    fn test_neg_vs_scalar() {
+        for (val, _) in test_inputs() {
+            let res = -val;
+            for i in 0..WIDTH {
+                assert_eq!(res.0[i], -val.0[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_results_canonical() {
+        for (lhs, rhs) in test_inputs() {
+            for res in [lhs + rhs, lhs - rhs, lhs * rhs, -lhs] {
+                for x in res.0 {
+                    assert!(x.as_canonical_u32() < F::ORDER_U32);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiplicative_inverse() {
+        let nonzero = special_vals().map(|x| if x.is_zero() { F::generator() } else { x });
+        let vec = PackedMontyField31AVX512(nonzero);
+        let inverses = PackedMontyField31AVX512(vec.0.map(|x| x.inverse()));
+        assert_eq!(vec * inverses, P::one());
+    }
+
+    #[test]
+    fn test_interleave() {
+        let vec0 = packed_from_canonical(core::array::from_fn(|i| i as u32));
+        let vec1 = packed_from_canonical(core::array::from_fn(|i| (WIDTH + i) as u32));
+
+        for block_len in [1, 2, 4, 8] {
+            // The first output takes the even blocks of both inputs, the second the odd blocks.
+            let expected0 = packed_from_canonical(core::array::from_fn(|i| {
+                (if i & block_len == 0 {
+                    i
+                } else {
+                    WIDTH + i - block_len
+                }) as u32
+            }));
+            let expected1 = packed_from_canonical(core::array::from_fn(|i| {
+                (if i & block_len == 0 {
+                    i + block_len
+                } else {
+                    WIDTH + i
+                }) as u32
+            }));
+            let res = vec0.interleave(vec1, block_len);
+            assert_eq!(res, (expected0, expected1), "block_len {block_len}");
+        }
+        assert_eq!(vec0.interleave(vec1, WIDTH), (vec0, vec1));
+    }
+
+    #[test]
+    fn test_interleave_1_explicit() {
+        let vec0 = packed_from_canonical(core::array::from_fn(|i| i as u32));
+        let vec1 = packed_from_canonical(core::array::from_fn(|i| (WIDTH + i) as u32));
+        let expected0 =
+            packed_from_canonical([0, 16, 2, 18, 4, 20, 6, 22, 8, 24, 10, 26, 12, 28, 14, 30]);
+        let expected1 =
+            packed_from_canonical([1, 17, 3, 19, 5, 21, 7, 23, 9, 25, 11, 27, 13, 29, 15, 31]);
+        assert_eq!(vec0.interleave(vec1, 1), (expected0, expected1));
+    }
+
+    #[test]
+    fn test_distributivity() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let (a, b, c): (P, P, P) = (rng.gen(), rng.gen(), rng.gen());
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!(a * (b - c), a * b - a * c);
+            assert_eq!(-a * b, -(a * b));
+        }
+    }
+
+    #[test]
+    fn test_koala_bear_vs_scalar() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..1000 {
+            let (lhs, rhs): (PackedKoalaBearAVX512, PackedKoalaBearAVX512) = (rng.gen(), rng.gen());
+            let (sum, diff, prod, neg) = (lhs + rhs, lhs - rhs, lhs * rhs, -lhs);
+            for i in 0..WIDTH {
+                assert_eq!(sum.0[i], lhs.0[i] + rhs.0[i]);
+                assert_eq!(diff.0[i], lhs.0[i] - rhs.0[i]);
+                assert_eq!(prod.0[i], lhs.0[i] * rhs.0[i]);
+                assert_eq!(neg.0[i], -lhs.0[i]);
+                let (a, b) = (lhs.0[i].as_canonical_u32(), rhs.0[i].as_canonical_u32());
+                let expected = a as u64 * b as u64 % KoalaBear::ORDER_U32 as u64;
+                assert_eq!(prod.0[i].as_canonical_u32() as u64, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use p3_field::extension::BinomiallyExtendable;
+
+use crate::Goldilocks;
+
+impl BinomiallyExtendable<2> for Goldilocks {
+    // Verifiable in Sage with
+    // `R.<x> = GF(p)[]; assert (x^2 - 7).is_irreducible()`.
+    fn w() -> Self {
+        Self::new(7)
+    }
+
+    // DTH_ROOT = W^((p - 1)/2)
+    fn dth_root() -> Self {
+        Self::new(18446744069414584320)
+    }
+
+    fn ext_generator() -> [Self; 2] {
+        [
+            Self::new(18081566051660590251),
+            Self::new(16121475356294670766),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test_quadratic_extension {
+    use p3_field::extension::{BinomialExtensionField, BinomiallyExtendable};
+    use p3_field::{AbstractExtensionField, AbstractField, Field, PrimeField64};
+
+    use crate::Goldilocks;
+
+    type F = Goldilocks;
+    type EF = BinomialExtensionField<F, 2>;
+
+    #[test]
+    fn dth_root() {
+        let w = <F as BinomiallyExtendable<2>>::w();
+        assert_eq!(w.exp_u64((F::ORDER_U64 - 1) / 2), F::dth_root());
+    }
+
+    #[test]
+    fn ext_generator() {
+        // The prime factors of p^2 - 1 = (p - 1)(p + 1).
+        const FACTORS: [u64; 9] = [2, 3, 5, 7, 17, 179, 257, 65537, 7361031152998637];
+        let g = EF::generator();
+        let order = (F::ORDER_U64 as u128).pow(2) - 1;
+        for q in FACTORS {
+            let exponent = order / q as u128;
+            let (hi, lo) = ((exponent >> 64) as u64, exponent as u64);
+            let power = g.exp_u64(hi).exp_power_of_2(64) * g.exp_u64(lo);
+            assert_ne!(
+                power,
+                EF::one(),
+                "generator has order dividing (p^2 - 1) / {q}"
+            );
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let x = EF::from_base_slice(&[F::from_canonical_u32(3), F::from_canonical_u32(5)]);
+        assert_eq!(x * x.inverse(), EF::one());
+        assert_eq!(x.square(), x * x);
+    }
+}
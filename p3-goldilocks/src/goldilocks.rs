@@ -0,0 +1,490 @@
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use p3_field::{
+    exp_10540996611094048183, exp_u64_by_squaring, AbstractField, Field, FieldArray, PrimeField,
+    PrimeField64, TwoAdicField,
+};
+use p3_util::{assume, branch_hint};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+const P: u64 = 0xFFFF_FFFF_0000_0001;
+
+/// `2^64 mod P`, i.e. `2^32 - 1`.
+const EPSILON: u64 = (1 << 32) - 1;
+
+/// The prime field `2^64 - 2^32 + 1`, a.k.a. the Goldilocks field.
+///
+/// Elements are stored as a `u64` which is not necessarily canonical: any value in `0..2^64` is
+/// a valid representation of its residue mod `P`. Comparisons and hashing use the canonical form.
+#[derive(Copy, Clone, Default)]
+#[repr(transparent)]
+pub struct Goldilocks {
+    value: u64,
+}
+
+impl Goldilocks {
+    /// Create a new `Goldilocks` from a `u64`, which need not be canonical.
+    #[inline]
+    pub(crate) const fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl PartialEq for Goldilocks {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_canonical_u64() == other.as_canonical_u64()
+    }
+}
+
+impl Eq for Goldilocks {}
+
+impl Hash for Goldilocks {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.as_canonical_u64());
+    }
+}
+
+impl Ord for Goldilocks {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_canonical_u64().cmp(&other.as_canonical_u64())
+    }
+}
+
+impl PartialOrd for Goldilocks {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Goldilocks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_canonical_u64(), f)
+    }
+}
+
+impl Debug for Goldilocks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_canonical_u64(), f)
+    }
+}
+
+impl Distribution<Goldilocks> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Goldilocks {
+        loop {
+            let next_u64 = rng.next_u64();
+            let is_canonical = next_u64 < P;
+            if is_canonical {
+                return Goldilocks::new(next_u64);
+            }
+        }
+    }
+}
+
+impl AbstractField for Goldilocks {
+    type F = Self;
+
+    fn zero() -> Self {
+        Self::new(0)
+    }
+    fn one() -> Self {
+        Self::new(1)
+    }
+    fn two() -> Self {
+        Self::new(2)
+    }
+    fn neg_one() -> Self {
+        Self::new(P - 1)
+    }
+
+    #[inline]
+    fn from_f(f: Self::F) -> Self {
+        f
+    }
+
+    #[inline]
+    fn from_bool(b: bool) -> Self {
+        Self::new(b as u64)
+    }
+
+    #[inline]
+    fn from_canonical_u8(n: u8) -> Self {
+        Self::new(n as u64)
+    }
+
+    #[inline]
+    fn from_canonical_u16(n: u16) -> Self {
+        Self::new(n as u64)
+    }
+
+    #[inline]
+    fn from_canonical_u32(n: u32) -> Self {
+        Self::new(n as u64)
+    }
+
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        debug_assert!(n < P);
+        Self::new(n)
+    }
+
+    #[inline]
+    fn from_canonical_usize(n: usize) -> Self {
+        Self::from_canonical_u64(n as u64)
+    }
+
+    #[inline]
+    fn from_wrapped_u32(n: u32) -> Self {
+        Self::new(n as u64)
+    }
+
+    #[inline]
+    fn from_wrapped_u64(n: u64) -> Self {
+        // Every `u64` is a valid, if not necessarily canonical, representation.
+        Self::new(n)
+    }
+
+    #[inline]
+    fn generator() -> Self {
+        Self::new(7)
+    }
+}
+
+impl Field for Goldilocks {
+    type Packing = FieldArray<Self, 4>;
+
+    #[inline]
+    fn exp_u64_generic<AF: AbstractField<F = Self>>(val: AF, power: u64) -> AF {
+        match power {
+            10540996611094048183 => exp_10540996611094048183(val), // used to compute x^{1/7}
+            _ => exp_u64_by_squaring(val, power),
+        }
+    }
+
+    fn try_inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // From Fermat's little theorem, in a prime field `F_p`, the inverse of `a` is `a^(p-2)`.
+        // Here p-2 = 0xFFFFFFFEFFFFFFFF, i.e. 31 ones, a zero, then 32 ones.
+        // Uses 63 Squares + 9 Multiplications => 72 Operations total.
+
+        let p1 = *self;
+        let p11 = p1.square() * p1;
+        let p111 = p11.square() * p1;
+        let p111111 = p111.exp_power_of_2(3) * p111;
+        let p111111111111 = p111111.exp_power_of_2(6) * p111111;
+        let p1_24 = p111111111111.exp_power_of_2(12) * p111111111111;
+        let p1_30 = p1_24.exp_power_of_2(6) * p111111;
+        let p1_31 = p1_30.square() * p1;
+        let p1_31_0_1_31 = p1_31.exp_power_of_2(32) * p1_31;
+        let p1_31_0_1_32 = p1_31_0_1_31.square() * p1;
+
+        Some(p1_31_0_1_32)
+    }
+}
+
+impl PrimeField for Goldilocks {}
+
+impl PrimeField64 for Goldilocks {
+    const ORDER_U64: u64 = P;
+
+    #[inline]
+    fn as_canonical_u64(&self) -> u64 {
+        let mut c = self.value;
+        // A single subtraction suffices, since 2 * P > 2^64.
+        if c >= P {
+            c -= P;
+        }
+        c
+    }
+
+    #[inline]
+    fn linear_combination_u64<const N: usize>(u: [u64; N], v: &[Self; N]) -> Self {
+        // sum(u) <= 2^32 keeps the dot product below 2^96, so it fits in a u128.
+        debug_assert!(u.iter().sum::<u64>() <= (1u64 << 32));
+
+        let mut dot = u[0] as u128 * v[0].value as u128;
+        for i in 1..N {
+            dot += u[i] as u128 * v[i].value as u128;
+        }
+        reduce128(dot)
+    }
+}
+
+impl TwoAdicField for Goldilocks {
+    const TWO_ADICITY: usize = 32;
+
+    fn two_adic_generator(bits: usize) -> Self {
+        assert!(bits <= Self::TWO_ADICITY);
+        let base = Self::new(1_753_635_133_440_165_772); // generates the whole 2^TWO_ADICITY group
+        base.exp_power_of_2(Self::TWO_ADICITY - bits)
+    }
+}
+
+impl Add for Goldilocks {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        let (sum, over) = self.value.overflowing_add(rhs.value);
+        let (mut sum, over) = sum.overflowing_add(u64::from(over) * EPSILON);
+        if over {
+            // A second overflow needs both inputs to be non-canonical, so it is very rare.
+            assume(self.value > P && rhs.value > P);
+            branch_hint();
+            sum += EPSILON; // Cannot overflow.
+        }
+        Self::new(sum)
+    }
+}
+
+impl AddAssign for Goldilocks {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Goldilocks {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x + y).unwrap_or(Self::zero())
+    }
+}
+
+impl Sub for Goldilocks {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        let (diff, under) = self.value.overflowing_sub(rhs.value);
+        let (mut diff, under) = diff.overflowing_sub(u64::from(under) * EPSILON);
+        if under {
+            // As in `add`, a second underflow only happens for non-canonical `rhs`.
+            assume(self.value < EPSILON - 1 && rhs.value > P);
+            branch_hint();
+            diff -= EPSILON; // Cannot underflow.
+        }
+        Self::new(diff)
+    }
+}
+
+impl SubAssign for Goldilocks {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Goldilocks {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::new(P - self.as_canonical_u64())
+    }
+}
+
+impl Mul for Goldilocks {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        reduce128(u128::from(self.value) * u128::from(rhs.value))
+    }
+}
+
+impl MulAssign for Goldilocks {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Product for Goldilocks {
+    #[inline]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x * y).unwrap_or(Self::one())
+    }
+}
+
+impl Div for Goldilocks {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl<const N: usize> Add<FieldArray<Self, N>> for Goldilocks {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn add(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) + rhs
+    }
+}
+
+impl<const N: usize> Mul<FieldArray<Self, N>> for Goldilocks {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn mul(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) * rhs
+    }
+}
+
+impl<const N: usize> Sub<FieldArray<Self, N>> for Goldilocks {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn sub(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) - rhs
+    }
+}
+
+/// Reduces a 128-bit value mod `P`, using `2^64 = EPSILON` and `2^96 = -1` (mod `P`).
+///
+/// Writing `x = x_lo + 2^64 x_hi_lo + 2^96 x_hi_hi`, the result is
+/// `x_lo - x_hi_hi + EPSILON x_hi_lo`, which is not necessarily canonical.
+#[inline]
+#[must_use]
+fn reduce128(x: u128) -> Goldilocks {
+    let x_lo = x as u64;
+    let x_hi = (x >> 64) as u64;
+    let x_hi_hi = x_hi >> 32;
+    let x_hi_lo = x_hi & EPSILON;
+
+    let (mut t0, borrow) = x_lo.overflowing_sub(x_hi_hi);
+    if borrow {
+        branch_hint(); // A borrow is exceedingly rare. It is faster to branch.
+        t0 -= EPSILON; // Cannot underflow.
+    }
+    let t1 = x_hi_lo * EPSILON;
+
+    let (t2, carry) = t0.overflowing_add(t1);
+    // Cannot overflow, as t1 <= (2^32 - 1)^2 leaves room for EPSILON.
+    Goldilocks::new(t2 + EPSILON * u64::from(carry))
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::PrimeField64;
+
+    use super::*;
+
+    type F = Goldilocks;
+
+    #[test]
+    fn test_goldilocks() {
+        let f = F::from_canonical_u32(100);
+        assert_eq!(f.as_canonical_u64(), 100);
+
+        let f = F::from_canonical_u64(0);
+        assert!(f.is_zero());
+
+        let f = F::from_wrapped_u64(F::ORDER_U64);
+        assert!(f.is_zero());
+
+        let f_1 = F::one();
+        let f_1_copy = F::from_canonical_u32(1);
+
+        let expected_result = F::zero();
+        assert_eq!(f_1 - f_1_copy, expected_result);
+
+        let expected_result = F::two();
+        assert_eq!(f_1 + f_1_copy, expected_result);
+
+        let f_2 = F::from_canonical_u32(2);
+        let expected_result = F::from_canonical_u32(3);
+        assert_eq!(f_1 + f_1_copy * f_2, expected_result);
+
+        let expected_result = F::from_canonical_u32(5);
+        assert_eq!(f_1 + f_2 * f_2, expected_result);
+
+        let f_p_minus_1 = F::from_canonical_u64(F::ORDER_U64 - 1);
+        let expected_result = F::zero();
+        assert_eq!(f_1 + f_p_minus_1, expected_result);
+
+        let f_p_minus_2 = F::from_canonical_u64(F::ORDER_U64 - 2);
+        let expected_result = F::from_canonical_u64(F::ORDER_U64 - 3);
+        assert_eq!(f_p_minus_1 + f_p_minus_2, expected_result);
+
+        let expected_result = F::from_canonical_u32(1);
+        assert_eq!(f_p_minus_1 - f_p_minus_2, expected_result);
+
+        let expected_result = f_p_minus_1;
+        assert_eq!(f_p_minus_2 - f_p_minus_1, expected_result);
+
+        let expected_result = f_p_minus_2;
+        assert_eq!(f_p_minus_1 - f_1, expected_result);
+
+        let m1 = F::from_canonical_u64(0x8b1e_a5a0_2d3f_6a47);
+        let m2 = F::from_canonical_u64(0x1c0a_33b9_7e12_f0d5);
+        let expected_prod = F::from_canonical_u64(
+            ((0x8b1e_a5a0_2d3f_6a47u128 * 0x1c0a_33b9_7e12_f0d5u128) % F::ORDER_U64 as u128) as u64,
+        );
+        assert_eq!(m1 * m2, expected_prod);
+
+        assert_eq!(m1.exp_u64(10540996611094048183).exp_const_u64::<7>(), m1);
+        assert_eq!(m2.exp_u64(10540996611094048183).exp_const_u64::<7>(), m2);
+        assert_eq!(f_2.exp_u64(10540996611094048183).exp_const_u64::<7>(), f_2);
+    }
+
+    #[test]
+    fn non_canonical_representations() {
+        // `P + k` is a valid representation of `k`.
+        let five = F::new(F::ORDER_U64 + 5);
+        assert_eq!(five, F::from_canonical_u32(5));
+        assert_eq!(five.as_canonical_u64(), 5);
+
+        let max = F::new(u64::MAX);
+        assert_eq!(max.as_canonical_u64(), EPSILON - 1);
+        assert_eq!(max + max, F::from_canonical_u64(2 * (EPSILON - 1)));
+        assert_eq!(F::zero() - max, -F::from_canonical_u64(EPSILON - 1));
+        assert_eq!(
+            max * max,
+            F::from_canonical_u64((EPSILON - 1) * (EPSILON - 1))
+        );
+        assert_eq!(-F::new(F::ORDER_U64), F::zero());
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(F::zero().try_inverse(), None);
+        assert_eq!(F::one().inverse(), F::one());
+        let mut x = F::generator();
+        for _ in 0..100 {
+            assert_eq!(x * x.inverse(), F::one());
+            x = x.square() + F::one();
+        }
+    }
+
+    #[test]
+    fn two_adic_generator() {
+        let g = F::two_adic_generator(F::TWO_ADICITY);
+        assert_eq!(g.exp_power_of_2(F::TWO_ADICITY), F::one());
+        assert_eq!(g.exp_power_of_2(F::TWO_ADICITY - 1), F::neg_one());
+        assert_eq!(F::two_adic_generator(1), F::neg_one());
+        assert_eq!(F::two_adic_generator(0), F::one());
+    }
+
+    #[test]
+    fn linear_combination() {
+        let v = [F::neg_one(), F::new(u64::MAX), F::two()];
+        let u = [3, 1 << 20, 5];
+        let expected = v.iter().zip(u).map(|(&v, u)| v * F::new(u)).sum::<F>();
+        assert_eq!(F::linear_combination_u64(u, &v), expected);
+    }
+}
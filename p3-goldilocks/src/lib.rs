@@ -0,0 +1,6 @@
+#![no_std]
+
+mod extension;
+mod goldilocks;
+
+pub use goldilocks::*;
@@ -0,0 +1,106 @@
+use p3_field::extension::{
+    BinomialExtensionField, BinomiallyExtendable, HasTwoAdicBionmialExtension,
+};
+use p3_field::{AbstractExtensionField, AbstractField};
+
+use crate::Mersenne31;
+
+/// The complex extension `F_p[i] = F_p[X]/(X^2 + 1)` of the Mersenne31 field.
+///
+/// Unlike the base field, whose multiplicative group has two-adicity 1, the multiplicative group
+/// of this extension has order `p^2 - 1 = 2^32 (2^30 - 1)`, so it supports FFTs of size `2^32`.
+pub type Mersenne31Complex = BinomialExtensionField<Mersenne31, 2>;
+
+impl BinomiallyExtendable<2> for Mersenne31 {
+    // X^2 + 1 is irreducible since p = 3 (mod 4), so -1 is not a square.
+    fn w() -> Self {
+        Self::neg_one()
+    }
+
+    // DTH_ROOT = W^((p - 1)/2) = (-1)^(2^30 - 1)
+    fn dth_root() -> Self {
+        Self::neg_one()
+    }
+
+    fn ext_generator() -> [Self; 2] {
+        [Self::new(12), Self::one()]
+    }
+}
+
+impl HasTwoAdicBionmialExtension<2> for Mersenne31 {
+    const EXT_TWO_ADICITY: usize = 32;
+
+    fn ext_two_adic_generator(bits: usize) -> [Self; 2] {
+        assert!(bits <= Self::EXT_TWO_ADICITY);
+        // Generates the whole 2^EXT_TWO_ADICITY group.
+        let base = Mersenne31Complex::from_base_slice(&[Self::new(21189756), Self::new(42379512)]);
+        let generator = base.exp_power_of_2(Self::EXT_TWO_ADICITY - bits);
+        let coeffs = generator.as_base_slice();
+        [coeffs[0], coeffs[1]]
+    }
+}
+
+#[cfg(test)]
+mod test_complex_extension {
+    use p3_field::extension::{BinomiallyExtendable, HasTwoAdicBionmialExtension};
+    use p3_field::{AbstractExtensionField, AbstractField, Field, PrimeField64};
+
+    use crate::{Mersenne31, Mersenne31Complex};
+
+    type F = Mersenne31;
+    type EF = Mersenne31Complex;
+
+    fn complex(re: u32, im: u32) -> EF {
+        EF::from_base_slice(&[F::from_canonical_u32(re), F::from_canonical_u32(im)])
+    }
+
+    #[test]
+    fn i_squared_is_minus_one() {
+        let i = <EF as AbstractExtensionField<F>>::monomial(1);
+        assert_eq!(i.square(), EF::neg_one());
+        assert_eq!(
+            complex(3, 4) * complex(1, 2),
+            complex(F::ORDER_U64 as u32 - 5, 10)
+        );
+    }
+
+    #[test]
+    fn dth_root() {
+        let w = <F as BinomiallyExtendable<2>>::w();
+        assert_eq!(w.exp_u64((F::ORDER_U64 - 1) / 2), F::dth_root());
+    }
+
+    #[test]
+    fn ext_generator() {
+        // The prime factors of p^2 - 1 = 2^32 (2^30 - 1).
+        const FACTORS: [u64; 7] = [2, 3, 7, 11, 31, 151, 331];
+        let g = EF::generator();
+        let order = F::ORDER_U64 * F::ORDER_U64 - 1;
+        for q in FACTORS {
+            assert_ne!(
+                g.exp_u64(order / q),
+                EF::one(),
+                "generator has order dividing (p^2 - 1) / {q}"
+            );
+        }
+    }
+
+    #[test]
+    fn ext_two_adic_generator() {
+        let bits = F::EXT_TWO_ADICITY;
+        let g = EF::from_base_slice(&F::ext_two_adic_generator(bits));
+        assert_eq!(g.exp_power_of_2(bits), EF::one());
+        assert_eq!(g.exp_power_of_2(bits - 1), EF::neg_one());
+        assert_eq!(
+            EF::from_base_slice(&F::ext_two_adic_generator(2)),
+            <EF as AbstractExtensionField<F>>::monomial(1)
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        let x = complex(3, 5);
+        assert_eq!(x * x.inverse(), EF::one());
+        assert_eq!(x.square(), x * x);
+    }
+}
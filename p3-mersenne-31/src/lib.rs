@@ -0,0 +1,7 @@
+#![no_std]
+
+mod complex;
+mod mersenne_31;
+
+pub use complex::*;
+pub use mersenne_31::*;
@@ -0,0 +1,455 @@
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use p3_field::{
+    exp_1717986917, exp_u64_by_squaring, AbstractField, Field, FieldArray, PrimeField,
+    PrimeField32, PrimeField64,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+const P: u32 = (1 << 31) - 1;
+
+/// The prime field `2^31 - 1`, a.k.a. the Mersenne31 field.
+///
+/// Elements are stored in canonical form. Since `2^31 = 1 (mod P)`, reduction only needs shifts,
+/// masks and additions: the bits above position 31 are folded back in by adding them to the low
+/// 31 bits.
+#[derive(Copy, Clone, Default, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct Mersenne31 {
+    value: u32,
+}
+
+impl Mersenne31 {
+    /// Create a new `Mersenne31` from a canonical `u32`.
+    #[inline]
+    pub(crate) const fn new(value: u32) -> Self {
+        debug_assert!(value < P);
+        Self { value }
+    }
+}
+
+impl Ord for Mersenne31 {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl PartialOrd for Mersenne31 {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Mersenne31 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.value, f)
+    }
+}
+
+impl Debug for Mersenne31 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.value, f)
+    }
+}
+
+impl Distribution<Mersenne31> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Mersenne31 {
+        loop {
+            let next_u31 = rng.next_u32() >> 1;
+            let is_canonical = next_u31 < P;
+            if is_canonical {
+                return Mersenne31::new(next_u31);
+            }
+        }
+    }
+}
+
+impl AbstractField for Mersenne31 {
+    type F = Self;
+
+    fn zero() -> Self {
+        Self::new(0)
+    }
+    fn one() -> Self {
+        Self::new(1)
+    }
+    fn two() -> Self {
+        Self::new(2)
+    }
+    fn neg_one() -> Self {
+        Self::new(P - 1)
+    }
+
+    #[inline]
+    fn from_f(f: Self::F) -> Self {
+        f
+    }
+
+    #[inline]
+    fn from_bool(b: bool) -> Self {
+        Self::new(b as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u8(n: u8) -> Self {
+        Self::new(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u16(n: u16) -> Self {
+        Self::new(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_u32(n: u32) -> Self {
+        debug_assert!(n < P);
+        Self::new(n)
+    }
+
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        debug_assert!(n < P as u64);
+        Self::from_canonical_u32(n as u32)
+    }
+
+    #[inline]
+    fn from_canonical_usize(n: usize) -> Self {
+        debug_assert!(n < P as usize);
+        Self::from_canonical_u32(n as u32)
+    }
+
+    #[inline]
+    fn from_wrapped_u32(n: u32) -> Self {
+        // n = 2^31 n_hi + n_lo = n_hi + n_lo (mod P), and the sum is at most P + 1.
+        Self::new(reduce_sum((n & P) + (n >> 31)))
+    }
+
+    #[inline]
+    fn from_wrapped_u64(n: u64) -> Self {
+        Self::new((n % P as u64) as u32)
+    }
+
+    #[inline]
+    fn generator() -> Self {
+        Self::new(7)
+    }
+}
+
+impl Field for Mersenne31 {
+    type Packing = FieldArray<Self, 4>;
+
+    #[inline]
+    fn mul_2exp_u64(&self, exp: u64) -> Self {
+        // Multiplying by 2^31 = 1 is the identity, so multiplying by 2^exp rotates the 31-bit
+        // representation left by `exp mod 31`. A canonical value is never all ones, and neither
+        // is its rotation, so the result is canonical.
+        let exp = (exp % 31) as u32;
+        let left = (self.value << exp) & P;
+        let right = self.value >> (31 - exp);
+        Self::new(left | right)
+    }
+
+    #[inline]
+    fn div_2exp_u64(&self, exp: u64) -> Self {
+        // As in `mul_2exp_u64`, but rotating right.
+        let exp = (exp % 31) as u32;
+        let left = (self.value << (31 - exp)) & P;
+        let right = self.value >> exp;
+        Self::new(left | right)
+    }
+
+    #[inline]
+    fn exp_u64_generic<AF: AbstractField<F = Self>>(val: AF, power: u64) -> AF {
+        match power {
+            1717986917 => exp_1717986917(val), // used to compute x^{1/5}
+            _ => exp_u64_by_squaring(val, power),
+        }
+    }
+
+    fn try_inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // From Fermat's little theorem, in a prime field `F_p`, the inverse of `a` is `a^(p-2)`.
+        // Here p-2 = 2147483645 = 1111111111111111111111111111101_2.
+        // Uses 30 Squares + 7 Multiplications => 37 Operations total.
+
+        let p1 = *self;
+        let p101 = p1.exp_power_of_2(2) * p1;
+        let p1111 = p101.square() * p101;
+        let p11111111 = p1111.exp_power_of_2(4) * p1111;
+        let p111111110000 = p11111111.exp_power_of_2(4);
+        let p111111111111 = p111111110000 * p1111;
+        let p1111111111111111 = p111111110000.exp_power_of_2(4) * p11111111;
+        let p1111111111111111111111111111 = p1111111111111111.exp_power_of_2(12) * p111111111111;
+        let p1111111111111111111111111111101 =
+            p1111111111111111111111111111.exp_power_of_2(3) * p101;
+
+        Some(p1111111111111111111111111111101)
+    }
+}
+
+impl PrimeField for Mersenne31 {}
+
+impl PrimeField64 for Mersenne31 {
+    const ORDER_U64: u64 = <Self as PrimeField32>::ORDER_U32 as u64;
+
+    #[inline]
+    fn as_canonical_u64(&self) -> u64 {
+        u64::from(self.as_canonical_u32())
+    }
+
+    #[inline]
+    fn linear_combination_u64<const N: usize>(u: [u64; N], v: &[Self; N]) -> Self {
+        // In order not to overflow a u64, we must have sum(u) <= 2^32.
+        debug_assert!(u.iter().sum::<u64>() <= (1u64 << 32));
+
+        let mut dot = u[0] * v[0].value as u64;
+        for i in 1..N {
+            dot += u[i] * v[i].value as u64;
+        }
+        Self::from_wrapped_u64(dot)
+    }
+}
+
+impl PrimeField32 for Mersenne31 {
+    const ORDER_U32: u32 = P;
+
+    #[inline]
+    fn as_canonical_u32(&self) -> u32 {
+        self.value
+    }
+}
+
+impl Add for Mersenne31 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(reduce_sum(self.value + rhs.value))
+    }
+}
+
+impl AddAssign for Mersenne31 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Mersenne31 {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x + y).unwrap_or(Self::zero())
+    }
+}
+
+impl Sub for Mersenne31 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        // On underflow the wrapped difference is `lhs - rhs + 2^32`. Subtracting one and clearing
+        // the top bit leaves `lhs - rhs + 2^31 - 1 = lhs - rhs + P`.
+        let (diff, over) = self.value.overflowing_sub(rhs.value);
+        Self::new(diff.wrapping_sub(over as u32) & P)
+    }
+}
+
+impl SubAssign for Mersenne31 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Mersenne31 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+impl Mul for Mersenne31 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        // prod = 2^31 prod_hi + prod_lo = prod_hi + prod_lo (mod P). Here prod_lo <= P and,
+        // since prod <= (P - 1)^2 < 2^31 (P - 2), prod_hi < P - 2, so the sum is below 2 P.
+        let prod = u64::from(self.value) * u64::from(rhs.value);
+        let prod_lo = (prod as u32) & P;
+        let prod_hi = (prod >> 31) as u32;
+        Self::new(reduce_sum(prod_lo + prod_hi))
+    }
+}
+
+impl MulAssign for Mersenne31 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Product for Mersenne31 {
+    #[inline]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x * y).unwrap_or(Self::one())
+    }
+}
+
+impl Div for Mersenne31 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl<const N: usize> Add<FieldArray<Self, N>> for Mersenne31 {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn add(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) + rhs
+    }
+}
+
+impl<const N: usize> Mul<FieldArray<Self, N>> for Mersenne31 {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn mul(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) * rhs
+    }
+}
+
+impl<const N: usize> Sub<FieldArray<Self, N>> for Mersenne31 {
+    type Output = FieldArray<Self, N>;
+
+    #[inline]
+    fn sub(self, rhs: FieldArray<Self, N>) -> FieldArray<Self, N> {
+        FieldArray::from(self) - rhs
+    }
+}
+
+/// Reduces a value in `0..2 P` to canonical form.
+#[inline]
+#[must_use]
+fn reduce_sum(sum: u32) -> u32 {
+    let (corr_sum, over) = sum.overflowing_sub(P);
+    if over {
+        sum
+    } else {
+        corr_sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::PrimeField64;
+
+    use super::*;
+
+    type F = Mersenne31;
+
+    #[test]
+    fn test_mersenne_31() {
+        let f = F::from_canonical_u32(100);
+        assert_eq!(f.as_canonical_u64(), 100);
+
+        let f = F::from_canonical_u32(0);
+        assert!(f.is_zero());
+
+        let f = F::from_wrapped_u32(F::ORDER_U32);
+        assert!(f.is_zero());
+
+        let f_1 = F::one();
+        let f_1_copy = F::from_canonical_u32(1);
+
+        let expected_result = F::zero();
+        assert_eq!(f_1 - f_1_copy, expected_result);
+
+        let expected_result = F::two();
+        assert_eq!(f_1 + f_1_copy, expected_result);
+
+        let f_2 = F::from_canonical_u32(2);
+        let expected_result = F::from_canonical_u32(3);
+        assert_eq!(f_1 + f_1_copy * f_2, expected_result);
+
+        let expected_result = F::from_canonical_u32(5);
+        assert_eq!(f_1 + f_2 * f_2, expected_result);
+
+        let f_p_minus_1 = F::from_canonical_u32(F::ORDER_U32 - 1);
+        let expected_result = F::zero();
+        assert_eq!(f_1 + f_p_minus_1, expected_result);
+
+        let f_p_minus_2 = F::from_canonical_u32(F::ORDER_U32 - 2);
+        let expected_result = F::from_canonical_u32(F::ORDER_U32 - 3);
+        assert_eq!(f_p_minus_1 + f_p_minus_2, expected_result);
+
+        let expected_result = F::from_canonical_u32(1);
+        assert_eq!(f_p_minus_1 - f_p_minus_2, expected_result);
+
+        let expected_result = f_p_minus_1;
+        assert_eq!(f_p_minus_2 - f_p_minus_1, expected_result);
+
+        let expected_result = f_p_minus_2;
+        assert_eq!(f_p_minus_1 - f_1, expected_result);
+
+        let m1 = F::from_canonical_u32(0x34167c58);
+        let m2 = F::from_canonical_u32(0x61f3207b);
+        let expected_prod =
+            F::from_canonical_u32(((0x34167c58u64 * 0x61f3207bu64) % F::ORDER_U64) as u32);
+        assert_eq!(m1 * m2, expected_prod);
+        assert_eq!(f_p_minus_1 * f_p_minus_1, f_1);
+
+        assert_eq!(m1.exp_u64(1717986917).exp_const_u64::<5>(), m1);
+        assert_eq!(m2.exp_u64(1717986917).exp_const_u64::<5>(), m2);
+        assert_eq!(f_2.exp_u64(1717986917).exp_const_u64::<5>(), f_2);
+    }
+
+    #[test]
+    fn from_wrapped() {
+        assert_eq!(F::from_wrapped_u32(u32::MAX), F::one());
+        assert_eq!(F::from_wrapped_u32(P + 5), F::from_canonical_u32(5));
+        assert_eq!(F::from_wrapped_u32(P - 1), F::neg_one());
+        // 2^64 = 2^2 (mod P).
+        assert_eq!(F::from_wrapped_u64(u64::MAX), F::from_canonical_u32(3));
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(F::zero().try_inverse(), None);
+        let mut x = F::generator();
+        for _ in 0..100 {
+            assert_eq!(x * x.inverse(), F::one());
+            x = x.square() + F::one();
+        }
+    }
+
+    #[test]
+    fn mul_and_div_2exp() {
+        let mut x = F::from_canonical_u32(0x2345_6789);
+        for exp in [0, 1, 5, 30, 31, 32, 100] {
+            let power = F::two().exp_u64(exp);
+            assert_eq!(x.mul_2exp_u64(exp), x * power);
+            assert_eq!(x.div_2exp_u64(exp), x / power);
+            x = x.square();
+        }
+        assert_eq!(F::zero().mul_2exp_u64(7), F::zero());
+        assert_eq!(F::neg_one().mul_2exp_u64(7), -F::from_canonical_u32(1 << 7));
+    }
+}
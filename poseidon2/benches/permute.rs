@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use p3_baby_bear::BabyBear;
+use p3_field::{Field, PackedField};
+use p3_poseidon2::{poseidon2_baby_bear_16, poseidon2_baby_bear_24, Poseidon2BabyBear};
+use rand::Rng;
+
+type Packed = <BabyBear as Field>::Packing;
+
+fn bench_poseidon2<const WIDTH: usize>(c: &mut Criterion, perm: Poseidon2BabyBear<WIDTH>) {
+    let mut rng = rand::thread_rng();
+    let input: [BabyBear; WIDTH] = core::array::from_fn(|_| rng.gen());
+    c.bench_function(&format!("poseidon2 BabyBear width {WIDTH}"), |b| {
+        b.iter(|| perm.permute(black_box(input)))
+    });
+
+    // Permutes `Packed::WIDTH` states at once.
+    let input: [Packed; WIDTH] = core::array::from_fn(|_| Packed::from_fn(|_| rng.gen()));
+    c.bench_function(
+        &format!("poseidon2 BabyBear width {WIDTH} packed x{}", Packed::WIDTH),
+        |b| b.iter(|| perm.permute(black_box(input))),
+    );
+}
+
+fn bench_width_16(c: &mut Criterion) {
+    bench_poseidon2(c, poseidon2_baby_bear_16());
+}
+
+fn bench_width_24(c: &mut Criterion) {
+    bench_poseidon2(c, poseidon2_baby_bear_24());
+}
+
+criterion_group!(poseidon2, bench_width_16, bench_width_24);
+criterion_main!(poseidon2);
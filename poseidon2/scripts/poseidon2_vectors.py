@@ -0,0 +1,101 @@
+#!/usr/bin/env python3
+"""Regenerates the known-answer vectors of p3-poseidon2.
+
+This is a standalone implementation of Poseidon2 (Grassi, Khovratovich and Schofnegger,
+"Poseidon2: A Faster Version of the Poseidon Hash Function", IACR ePrint 2023/323), written with
+exact integers and dense matrices, and sharing no code with the crate. The round constants come
+from the Grain LFSR of the reference parameter scripts (`generate_parameters_grain.sage` of
+Poseidon and `poseidon2_rust_params.sage` of HorizenLabs/poseidon2): `(R_F + R_P) t` field
+elements drawn in round order, of which a partial round keeps only the first of its `t`.
+
+The Grain LFSR is first checked against the published round constants of Poseidon over BN254
+with `t = 3`, `R_F = 8` and `R_P = 57`, the instance of circomlib and of the Poseidon paper.
+
+The linear layers are those of the crate: the external layer `circ(2 M4, M4, ..., M4)` of the
+paper, and the internal layer `J + diag(d)` with the diagonals of Plonky3. The diagonals differ
+from those of HorizenLabs/poseidon2, so the outputs do too, though the constants agree.
+
+Run it with `python3 poseidon2/scripts/poseidon2_vectors.py`; it needs nothing beyond the
+standard library.
+"""
+
+
+def grain(field_bits, t, rounds_f, rounds_p):
+    """The output bits of the Grain LFSR seeded for a prime field and an S-box `x -> x^d`."""
+    state = []
+    for value, bits in [(1, 2), (0, 4), (field_bits, 12), (t, 12), (rounds_f, 10),
+                        (rounds_p, 10), (2**30 - 1, 30)]:
+        state += [int(b) for b in bin(value)[2:].zfill(bits)]
+
+    def clock():
+        bit = state[62] ^ state[51] ^ state[38] ^ state[23] ^ state[13] ^ state[0]
+        state.pop(0)
+        state.append(bit)
+        return bit
+
+    for _ in range(160):
+        clock()
+    while True:
+        keep, bit = clock(), clock()
+        if keep:
+            yield bit
+
+
+def round_constants(p, t, rounds_f, rounds_p):
+    """`(R_F + R_P) t` field elements, sampled by rejection, most significant bit first."""
+    field_bits = p.bit_length()
+    bits = grain(field_bits, t, rounds_f, rounds_p)
+    constants = []
+    while len(constants) < (rounds_f + rounds_p) * t:
+        value = 0
+        for _ in range(field_bits):
+            value = (value << 1) | next(bits)
+        if value < p:
+            constants.append(value)
+    return constants
+
+
+BN254 = 0x30644E72E131A029B85045B68181585D2833E84879B9709143E1F593F0000001
+assert [hex(c) for c in round_constants(BN254, 3, 8, 57)[:3]] == [
+    "0xee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e",
+    "0xf1445235f2148c5986587169fc1bcd887b08d4d00868df5696fff40956e864",
+    "0x8dff3487e8ac99e1f29a058d0fa80b930c728730b7ab36ce879f3890ecf73f5",
+], "the Grain LFSR does not match the Poseidon constants over BN254"
+
+P = 15 * 2**27 + 1
+M4 = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]]
+
+# The diagonals of the internal layers, as `(n, k)` for the entry `n / 2^k`.
+DIAGONALS = {
+    16: [(-2, 0), (1, 0), (2, 0), (1, 1), (3, 0), (4, 0), (-1, 1), (-3, 0), (-4, 0), (1, 8),
+         (1, 2), (1, 3), (1, 27), (-1, 8), (-1, 4), (-1, 27)],
+    24: [(-2, 0), (1, 0), (2, 0), (1, 1), (3, 0), (4, 0), (-1, 1), (-3, 0), (-4, 0), (1, 8),
+         (1, 2), (1, 3), (1, 4), (1, 7), (1, 9), (1, 27), (-1, 8), (-1, 2), (-1, 3), (-1, 4),
+         (-1, 5), (-1, 6), (-1, 7), (-1, 27)],
+}
+
+
+def permute(t, rounds_f, rounds_p, state):
+    rc = round_constants(P, t, rounds_f, rounds_p)
+    rc = [rc[t * r : t * (r + 1)] for r in range(rounds_f + rounds_p)]
+    diagonal = [n * pow(2, -k, P) % P for n, k in DIAGONALS[t]]
+    external = [[M4[i % 4][j % 4] * (2 if i // 4 == j // 4 else 1) for j in range(t)]
+                for i in range(t)]
+    internal = [[1 + (diagonal[i] if i == j else 0) for j in range(t)] for i in range(t)]
+    mul = lambda m, s: [sum(m[i][j] * s[j] for j in range(t)) % P for i in range(t)]
+
+    state = mul(external, state)
+    for r in range(rounds_f + rounds_p):
+        if rounds_f // 2 <= r < rounds_f // 2 + rounds_p:
+            state[0] = pow(state[0] + rc[r][0], 7, P)
+            state = mul(internal, state)
+        else:
+            state = [pow(x + c, 7, P) for x, c in zip(state, rc[r])]
+            state = mul(external, state)
+    return state
+
+
+for t, rounds_p in [(16, 13), (24, 21)]:
+    print("BabyBear, width", t)
+    print("  round_constants[..4]:", round_constants(P, t, 8, rounds_p)[:4])
+    print("  permute([0, 1, ..]):", permute(t, 8, rounds_p, list(range(t))))
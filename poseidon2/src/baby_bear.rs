@@ -0,0 +1,327 @@
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, Field, PrimeField64};
+
+use crate::{DiagonalInternalLayer, Poseidon2};
+
+/// Poseidon2 over Baby Bear, with the S-box `x -> x^7`, the smallest degree that is a
+/// permutation of Baby Bear (`gcd(7, p - 1) = 1`).
+pub type Poseidon2BabyBear<const WIDTH: usize> = Poseidon2<BabyBear, WIDTH, 7>;
+
+/// The number of full rounds of the Baby Bear instances, for 128 bits of security.
+pub const BABY_BEAR_ROUNDS_F: usize = 8;
+/// The number of partial rounds of the width 16 Baby Bear instance.
+pub const BABY_BEAR_ROUNDS_P_16: usize = 13;
+/// The number of partial rounds of the width 24 Baby Bear instance.
+pub const BABY_BEAR_ROUNDS_P_24: usize = 21;
+
+/// The diagonal of the width 16 internal layer, as `(n, k)` for the entry `n / 2^k`.
+///
+/// The entries are small multiples of powers of two, so multiplying by them reduces to shifts in
+/// implementations specialized to the field.
+const DIAGONAL_16: [(i32, u32); 16] = [
+    (-2, 0),
+    (1, 0),
+    (2, 0),
+    (1, 1),
+    (3, 0),
+    (4, 0),
+    (-1, 1),
+    (-3, 0),
+    (-4, 0),
+    (1, 8),
+    (1, 2),
+    (1, 3),
+    (1, 27),
+    (-1, 8),
+    (-1, 4),
+    (-1, 27),
+];
+
+/// The diagonal of the width 24 internal layer, as in [DIAGONAL_16].
+const DIAGONAL_24: [(i32, u32); 24] = [
+    (-2, 0),
+    (1, 0),
+    (2, 0),
+    (1, 1),
+    (3, 0),
+    (4, 0),
+    (-1, 1),
+    (-3, 0),
+    (-4, 0),
+    (1, 8),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (1, 7),
+    (1, 9),
+    (1, 27),
+    (-1, 8),
+    (-1, 2),
+    (-1, 3),
+    (-1, 4),
+    (-1, 5),
+    (-1, 6),
+    (-1, 7),
+    (-1, 27),
+];
+
+fn diagonal<const WIDTH: usize>(entries: [(i32, u32); WIDTH]) -> [BabyBear; WIDTH] {
+    let half = BabyBear::two().inverse();
+    entries.map(|(n, k)| {
+        let x = BabyBear::from_canonical_u32(n.unsigned_abs()) * half.exp_u64(k as u64);
+        if n < 0 {
+            -x
+        } else {
+            x
+        }
+    })
+}
+
+pub fn baby_bear_internal_layer_16() -> DiagonalInternalLayer<BabyBear, 16> {
+    DiagonalInternalLayer::new(diagonal(DIAGONAL_16))
+}
+
+pub fn baby_bear_internal_layer_24() -> DiagonalInternalLayer<BabyBear, 24> {
+    DiagonalInternalLayer::new(diagonal(DIAGONAL_24))
+}
+
+/// The width 16 Baby Bear instance, with round constants from `Poseidon2::new_from_grain`.
+pub fn poseidon2_baby_bear_16() -> Poseidon2BabyBear<16> {
+    poseidon2_baby_bear_16_generic()
+}
+
+/// The width 16 Baby Bear instance over another representation `F` of the Baby Bear field, such
+/// as `BabyBearCanonical`. It has the same constants as `poseidon2_baby_bear_16`, so it computes
+/// the same permutation.
+pub fn poseidon2_baby_bear_16_generic<F: PrimeField64 + From<BabyBear>>() -> Poseidon2<F, 16, 7> {
+    assert_eq!(
+        F::ORDER_U64,
+        BabyBear::ORDER_U64,
+        "not a representation of Baby Bear"
+    );
+    let diagonal = baby_bear_internal_layer_16().diagonal().map(F::from);
+    Poseidon2::new_from_grain(
+        BABY_BEAR_ROUNDS_F,
+        BABY_BEAR_ROUNDS_P_16,
+        DiagonalInternalLayer::new(diagonal),
+    )
+}
+
+/// The width 24 Baby Bear instance, with round constants from `Poseidon2::new_from_grain`.
+pub fn poseidon2_baby_bear_24() -> Poseidon2BabyBear<24> {
+    Poseidon2::new_from_grain(
+        BABY_BEAR_ROUNDS_F,
+        BABY_BEAR_ROUNDS_P_24,
+        baby_bear_internal_layer_24(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_field::{PackedField, PrimeField32, SymbolicField};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use p3_baby_bear::BabyBearCanonical;
+
+    use super::*;
+
+    const P: u64 = 15 * (1 << 27) + 1;
+
+    /// A direct implementation of the specification over `u64`, with dense matrices.
+    fn reference_permute<const WIDTH: usize>(
+        perm: &Poseidon2BabyBear<WIDTH>,
+        input: [BabyBear; WIDTH],
+    ) -> [BabyBear; WIDTH] {
+        const M4: [[u64; 4]; 4] = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+        let external = |i: usize, j: usize| {
+            let m = M4[i % 4][j % 4];
+            if i / 4 == j / 4 {
+                2 * m
+            } else {
+                m
+            }
+        };
+        let diagonal = perm
+            .internal_layer
+            .diagonal()
+            .map(|d| d.as_canonical_u32() as u64);
+        let internal = |i: usize, j: usize| if i == j { diagonal[i] + 1 } else { 1 };
+        let mat_mul = |m: &dyn Fn(usize, usize) -> u64, x: [u64; WIDTH]| {
+            core::array::from_fn(|i| (0..WIDTH).map(|j| m(i, j) * x[j] % P).sum::<u64>() % P)
+        };
+        let sbox = |x: u64| (0..7).fold(1, |acc, _| acc * x % P);
+        let canonical = |c: &BabyBear| c.as_canonical_u32() as u64;
+
+        let mut state = mat_mul(&external, input.map(|x| canonical(&x)));
+        let half = perm.rounds_f() / 2;
+        for (r, constants) in perm.external_constants.iter().enumerate() {
+            if r == half {
+                for c in &perm.internal_constants {
+                    state[0] = sbox((state[0] + canonical(c)) % P);
+                    state = mat_mul(&internal, state);
+                }
+            }
+            for (x, c) in state.iter_mut().zip(constants) {
+                *x = sbox((*x + canonical(c)) % P);
+            }
+            state = mat_mul(&external, state);
+        }
+        state.map(BabyBear::from_canonical_u64)
+    }
+
+    fn test_matches_reference<const WIDTH: usize>(perm: &Poseidon2BabyBear<WIDTH>) {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for _ in 0..10 {
+            let input: [BabyBear; WIDTH] = core::array::from_fn(|_| rng.gen());
+            assert_eq!(perm.permute(input), reference_permute(perm, input));
+        }
+    }
+
+    fn test_packed_matches_scalar<const WIDTH: usize>(perm: &Poseidon2BabyBear<WIDTH>) {
+        type Packed = <BabyBear as Field>::Packing;
+        let mut rng = SmallRng::seed_from_u64(2);
+        let inputs: Vec<[BabyBear; WIDTH]> = (0..Packed::WIDTH)
+            .map(|_| core::array::from_fn(|_| rng.gen()))
+            .collect();
+        let packed: [Packed; WIDTH] =
+            core::array::from_fn(|i| Packed::from_fn(|lane| inputs[lane][i]));
+        let outputs = perm.permute(packed);
+        for (lane, input) in inputs.into_iter().enumerate() {
+            let expected = perm.permute(input);
+            for i in 0..WIDTH {
+                assert_eq!(outputs[i].as_slice()[lane], expected[i]);
+            }
+        }
+    }
+
+    /// Checks that `J + diag(d)` is invertible, by Gaussian elimination.
+    fn test_internal_layer_invertible<const WIDTH: usize>(
+        layer: &DiagonalInternalLayer<BabyBear, WIDTH>,
+    ) {
+        let mut m: [[BabyBear; WIDTH]; WIDTH] = core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                let d = if i == j {
+                    layer.diagonal()[i]
+                } else {
+                    BabyBear::zero()
+                };
+                d + BabyBear::one()
+            })
+        });
+        for col in 0..WIDTH {
+            let pivot = (col..WIDTH)
+                .find(|&row| !m[row][col].is_zero())
+                .expect("the internal layer is singular");
+            m.swap(col, pivot);
+            let inv = m[col][col].inverse();
+            let pivot_row = m[col];
+            for row in &mut m[col + 1..] {
+                let factor = row[col] * inv;
+                for (x, &p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *x -= p * factor;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn width_16() {
+        let perm = poseidon2_baby_bear_16();
+        assert_eq!((perm.rounds_f(), perm.rounds_p()), (8, 13));
+        test_matches_reference(&perm);
+        test_packed_matches_scalar(&perm);
+        test_internal_layer_invertible(&baby_bear_internal_layer_16());
+    }
+
+    #[test]
+    fn width_24() {
+        let perm = poseidon2_baby_bear_24();
+        assert_eq!((perm.rounds_f(), perm.rounds_p()), (8, 21));
+        test_matches_reference(&perm);
+        test_packed_matches_scalar(&perm);
+        test_internal_layer_invertible(&baby_bear_internal_layer_24());
+    }
+
+    #[test]
+    fn symbolic_matches_scalar() {
+        let perm = poseidon2_baby_bear_16();
+        let vars: [SymbolicField<BabyBear, usize>; 16] =
+            core::array::from_fn(SymbolicField::Variable);
+        let outputs = perm.permute(vars);
+
+        let input: [BabyBear; 16] = SmallRng::seed_from_u64(3).gen();
+        let expected = perm.permute(input);
+        for (output, expected) in outputs.iter().zip(expected) {
+            assert_eq!(output.eval(|&v| input[v]), expected);
+        }
+    }
+
+    #[test]
+    fn sbox_is_a_permutation() {
+        let mut rng = SmallRng::seed_from_u64(4);
+        for _ in 0..100 {
+            let x: BabyBear = rng.gen();
+            // 7 * 1725656503 = 1 (mod p - 1).
+            assert_eq!(x.exp_const_u64::<7>().exp_u64(1725656503), x);
+        }
+    }
+
+    #[test]
+    fn generic_instance_matches() {
+        let perm = poseidon2_baby_bear_16_generic::<BabyBearCanonical>();
+        let mut rng = SmallRng::seed_from_u64(4);
+        for _ in 0..10 {
+            let input: [BabyBear; 16] = core::array::from_fn(|_| rng.gen());
+            let output = perm.permute(input.map(BabyBearCanonical::from));
+            assert_eq!(
+                output.map(|x| x.as_canonical_u32()),
+                poseidon2_baby_bear_16()
+                    .permute(input)
+                    .map(|x| x.as_canonical_u32())
+            );
+        }
+    }
+
+    // The expected values below are printed by `poseidon2/scripts/poseidon2_vectors.py`, a
+    // standalone Python implementation of Poseidon2 whose Grain LFSR is checked against the
+    // published Poseidon constants over BN254.
+
+    #[test]
+    fn grain_round_constants() {
+        let perm = poseidon2_baby_bear_16();
+        assert_eq!(
+            perm.external_constants[0][..4],
+            [1774958255, 1185780729, 1621102414, 1796380621].map(BabyBear::from_canonical_u32)
+        );
+        let perm = poseidon2_baby_bear_24();
+        assert_eq!(
+            perm.external_constants[0][..4],
+            [262278199, 127253399, 314968988, 246143118].map(BabyBear::from_canonical_u32)
+        );
+    }
+
+    #[test]
+    fn known_answers() {
+        let input: [BabyBear; 16] = core::array::from_fn(BabyBear::from_canonical_usize);
+        let expected: [u32; 16] = [
+            1948410319, 1253827334, 1905901584, 496674636, 1272569005, 1900875619, 367984092,
+            105936715, 245126375, 1429164228, 1080957290, 550043846, 846005424, 963599100,
+            1468317374, 1125697280,
+        ];
+        let output = poseidon2_baby_bear_16().permute(input);
+        assert_eq!(output, expected.map(BabyBear::from_canonical_u32));
+
+        let input: [BabyBear; 24] = core::array::from_fn(BabyBear::from_canonical_usize);
+        let expected: [u32; 24] = [
+            254527232, 1401160319, 1560946146, 424128231, 1305291696, 36096407, 1653211725,
+            1316822820, 818540231, 1296867631, 1581025036, 128823342, 222219455, 611254996,
+            68412171, 693672275, 1896209170, 1792186281, 1869033723, 1219923754, 50458723,
+            1416014218, 1041207870, 1530560421,
+        ];
+        let output = poseidon2_baby_bear_24().permute(input);
+        assert_eq!(output, expected.map(BabyBear::from_canonical_u32));
+    }
+}
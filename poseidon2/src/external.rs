@@ -0,0 +1,86 @@
+use p3_field::AbstractField;
+
+/// Multiplies a chunk of 4 elements by the matrix
+///
+/// ```text
+/// [ 5 7 1 3 ]
+/// [ 4 6 1 1 ]
+/// [ 1 3 5 7 ]
+/// [ 1 1 4 6 ]
+/// ```
+///
+/// of the Poseidon2 paper, with 8 additions and 4 doublings and no multiplications.
+#[inline(always)]
+pub fn apply_mat4<AF: AbstractField>(x: &mut [AF]) {
+    let t0 = x[0].clone() + x[1].clone();
+    let t1 = x[2].clone() + x[3].clone();
+    let t2 = x[1].double() + t1.clone();
+    let t3 = x[3].double() + t0.clone();
+    let t4 = t1.double().double() + t3.clone();
+    let t5 = t0.double().double() + t2.clone();
+    let t6 = t3 + t5.clone();
+    let t7 = t2 + t4.clone();
+    x[0] = t6;
+    x[1] = t5;
+    x[2] = t7;
+    x[3] = t4;
+}
+
+/// The external linear layer of Poseidon2.
+///
+/// For a width of 4 this is the matrix of [apply_mat4], `M4`. For larger widths it is the block
+/// circulant matrix `circ(2 M4, M4, ..., M4)`, which is applied by multiplying each chunk by
+/// `M4` and then adding to each element the sum of the elements in the same position of every
+/// chunk.
+pub fn mds_light_permutation<AF: AbstractField, const WIDTH: usize>(state: &mut [AF; WIDTH]) {
+    for chunk in state.chunks_exact_mut(4) {
+        apply_mat4(chunk);
+    }
+    if WIDTH == 4 {
+        return;
+    }
+
+    let sums: [AF; 4] =
+        core::array::from_fn(|k| state.iter().skip(k).step_by(4).cloned().sum::<AF>());
+    for (i, x) in state.iter_mut().enumerate() {
+        *x += sums[i % 4].clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    const M4: [[u32; 4]; 4] = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+
+    #[test]
+    fn mds_light_is_block_circulant() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let x: [BabyBear; 12] = rng.gen();
+        let mut y = x;
+        mds_light_permutation(&mut y);
+        for (i, &y_i) in y.iter().enumerate() {
+            let expected = (0..12)
+                .map(|j| {
+                    let scale = if i / 4 == j / 4 { 2 } else { 1 };
+                    x[j] * BabyBear::from_canonical_u32(scale * M4[i % 4][j % 4])
+                })
+                .sum::<BabyBear>();
+            assert_eq!(y_i, expected);
+        }
+
+        // A width of 4 is `M4` alone.
+        let mut z = [x[0], x[1], x[2], x[3]];
+        mds_light_permutation(&mut z);
+        for (i, &z_i) in z.iter().enumerate() {
+            let expected = (0..4)
+                .map(|j| x[j] * BabyBear::from_canonical_u32(M4[i][j]))
+                .sum::<BabyBear>();
+            assert_eq!(z_i, expected);
+        }
+    }
+}
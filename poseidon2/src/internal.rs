@@ -0,0 +1,32 @@
+use p3_field::{AbstractField, Field};
+
+/// The internal linear layer of Poseidon2, `J + diag(d)` where `J` is the all-ones matrix.
+///
+/// Multiplying by it costs `WIDTH` multiplications and `2 WIDTH` additions rather than the
+/// `WIDTH^2` multiplications of a dense matrix: each element becomes `d_i x_i + sum(x)`.
+#[derive(Clone, Debug)]
+pub struct DiagonalInternalLayer<F: Field, const WIDTH: usize> {
+    diagonal: [F; WIDTH],
+}
+
+impl<F: Field, const WIDTH: usize> DiagonalInternalLayer<F, WIDTH> {
+    /// The layer `J + diag(diagonal)`.
+    ///
+    /// The caller is responsible for choosing a diagonal for which the matrix is invertible and
+    /// satisfies the other conditions of the Poseidon2 paper.
+    pub const fn new(diagonal: [F; WIDTH]) -> Self {
+        Self { diagonal }
+    }
+
+    pub fn diagonal(&self) -> &[F; WIDTH] {
+        &self.diagonal
+    }
+
+    #[inline]
+    pub fn permute_mut<AF: AbstractField<F = F>>(&self, state: &mut [AF; WIDTH]) {
+        let sum = state.iter().cloned().sum::<AF>();
+        for (x, &d) in state.iter_mut().zip(&self.diagonal) {
+            *x = x.clone() * AF::from_f(d) + sum.clone();
+        }
+    }
+}
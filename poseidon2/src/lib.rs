@@ -0,0 +1,163 @@
+//! The Poseidon2 permutation.
+//!
+//! The permutation is generic over `AbstractField`, so the same code runs on scalar field
+//! elements, on `F::Packing` to permute several states at once, and on `SymbolicField` to extract
+//! the constraints of a round.
+
+#![no_std]
+
+extern crate alloc;
+
+mod baby_bear;
+mod external;
+mod internal;
+mod round_constants;
+
+use alloc::vec::Vec;
+
+pub use baby_bear::*;
+pub use external::*;
+pub use internal::*;
+use p3_field::{AbstractField, Field, PrimeField64};
+use p3_symmetric::{CryptographicPermutation, Permutation};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+pub use round_constants::*;
+
+/// The Poseidon2 permutation of `WIDTH` elements of `F`, with S-box `x -> x^D`.
+///
+/// The state first goes through the external linear layer, then through `rounds_f / 2` full
+/// rounds, `rounds_p` partial rounds and another `rounds_f / 2` full rounds. A full round adds a
+/// constant to each element, applies the S-box to each element and then the external linear
+/// layer; a partial round only adds a constant to and applies the S-box to the first element,
+/// followed by the internal linear layer.
+#[derive(Clone, Debug)]
+pub struct Poseidon2<F: Field, const WIDTH: usize, const D: u64> {
+    /// The constants of the full rounds, in round order.
+    external_constants: Vec<[F; WIDTH]>,
+    /// The constants of the partial rounds, in round order.
+    internal_constants: Vec<F>,
+    internal_layer: DiagonalInternalLayer<F, WIDTH>,
+}
+
+impl<F: Field, const WIDTH: usize, const D: u64> Poseidon2<F, WIDTH, D> {
+    pub fn new(
+        external_constants: Vec<[F; WIDTH]>,
+        internal_constants: Vec<F>,
+        internal_layer: DiagonalInternalLayer<F, WIDTH>,
+    ) -> Self {
+        assert_eq!(
+            external_constants.len() % 2,
+            0,
+            "the number of full rounds must be even"
+        );
+        assert_eq!(WIDTH % 4, 0, "the width must be a multiple of 4");
+        Self {
+            external_constants,
+            internal_constants,
+            internal_layer,
+        }
+    }
+
+    /// Samples the round constants from `rng`.
+    pub fn new_from_rng<R: Rng>(
+        rounds_f: usize,
+        rounds_p: usize,
+        internal_layer: DiagonalInternalLayer<F, WIDTH>,
+        rng: &mut R,
+    ) -> Self
+    where
+        Standard: Distribution<F>,
+    {
+        let external_constants = (0..rounds_f)
+            .map(|_| core::array::from_fn(|_| rng.gen()))
+            .collect();
+        let internal_constants = (0..rounds_p).map(|_| rng.gen()).collect();
+        Self::new(external_constants, internal_constants, internal_layer)
+    }
+
+    /// Derives the round constants from the Grain LFSR, seeded with the parameters of the instance.
+    ///
+    /// As in the reference implementations, `WIDTH` constants are drawn for every round in round
+    /// order: `rounds_f / 2` full rounds, `rounds_p` partial rounds and another `rounds_f / 2` full
+    /// rounds. A partial round only uses the first of its constants.
+    pub fn new_from_grain(
+        rounds_f: usize,
+        rounds_p: usize,
+        internal_layer: DiagonalInternalLayer<F, WIDTH>,
+    ) -> Self
+    where
+        F: PrimeField64,
+    {
+        let mut grain = GrainLfsr::new::<F>(WIDTH, rounds_f, rounds_p);
+        let mut full_round = || core::array::from_fn(|_| grain.next_field_element());
+        let mut external_constants: Vec<[F; WIDTH]> =
+            (0..rounds_f / 2).map(|_| full_round()).collect();
+        let internal_constants = (0..rounds_p).map(|_| full_round()[0]).collect();
+        external_constants.extend((0..rounds_f / 2).map(|_| full_round()));
+        Self::new(external_constants, internal_constants, internal_layer)
+    }
+
+    pub fn rounds_f(&self) -> usize {
+        self.external_constants.len()
+    }
+
+    pub fn rounds_p(&self) -> usize {
+        self.internal_constants.len()
+    }
+
+    pub fn permute<AF: AbstractField<F = F>>(&self, mut state: [AF; WIDTH]) -> [AF; WIDTH] {
+        self.permute_mut(&mut state);
+        state
+    }
+
+    pub fn permute_mut<AF: AbstractField<F = F>>(&self, state: &mut [AF; WIDTH]) {
+        let (first_full, last_full) = self
+            .external_constants
+            .split_at(self.external_constants.len() / 2);
+
+        mds_light_permutation(state);
+        for constants in first_full {
+            self.full_round(state, constants);
+        }
+        for &constant in &self.internal_constants {
+            state[0] += AF::from_f(constant);
+            state[0] = state[0].exp_const_u64::<D>();
+            self.internal_layer.permute_mut(state);
+        }
+        for constants in last_full {
+            self.full_round(state, constants);
+        }
+    }
+
+    #[inline]
+    fn full_round<AF: AbstractField<F = F>>(
+        &self,
+        state: &mut [AF; WIDTH],
+        constants: &[F; WIDTH],
+    ) {
+        for (x, &c) in state.iter_mut().zip(constants) {
+            *x += AF::from_f(c);
+            *x = x.exp_const_u64::<D>();
+        }
+        mds_light_permutation(state);
+    }
+}
+
+impl<F, AF, const WIDTH: usize, const D: u64> Permutation<[AF; WIDTH]> for Poseidon2<F, WIDTH, D>
+where
+    F: Field,
+    AF: AbstractField<F = F>,
+{
+    fn permute_mut(&self, input: &mut [AF; WIDTH]) {
+        Poseidon2::permute_mut(self, input);
+    }
+}
+
+impl<F, AF, const WIDTH: usize, const D: u64> CryptographicPermutation<[AF; WIDTH]>
+    for Poseidon2<F, WIDTH, D>
+where
+    F: Field,
+    AF: AbstractField<F = F>,
+{
+}
@@ -0,0 +1,82 @@
+use p3_field::PrimeField64;
+
+/// The Grain LFSR with which the reference implementations of Poseidon and Poseidon2 derive their
+/// round constants.
+///
+/// The 80-bit state is seeded with the parameters of the instance, so different instances get
+/// independent constants. Field elements are sampled by rejection from the output bits, most
+/// significant bit first.
+#[derive(Clone, Debug)]
+pub struct GrainLfsr {
+    /// The state, oldest bit in the least significant position.
+    state: u128,
+    field_bits: u32,
+    order: u64,
+}
+
+impl GrainLfsr {
+    /// Seeds the LFSR for a permutation of `width` elements of `F` with S-box `x -> x^D`, `D > 0`.
+    pub fn new<F: PrimeField64>(width: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        let field_bits = 64 - (F::ORDER_U64 - 1).leading_zeros();
+        // Field type (1 for a prime field), S-box (0 for a positive power), field size, width,
+        // full rounds, partial rounds, padding.
+        let fields: [(u64, u32); 7] = [
+            (1, 2),
+            (0, 4),
+            (field_bits as u64, 12),
+            (width as u64, 12),
+            (rounds_f as u64, 10),
+            (rounds_p as u64, 10),
+            ((1 << 30) - 1, 30),
+        ];
+
+        let mut lfsr = Self {
+            state: 0,
+            field_bits,
+            order: F::ORDER_U64,
+        };
+        let mut pos = 0;
+        for (value, bits) in fields {
+            for i in (0..bits).rev() {
+                lfsr.state |= (((value >> i) & 1) as u128) << pos;
+                pos += 1;
+            }
+        }
+        for _ in 0..160 {
+            lfsr.clock();
+        }
+        lfsr
+    }
+
+    /// Shifts in one bit, `b_80 = b_62 + b_51 + b_38 + b_23 + b_13 + b_0`, and returns it.
+    fn clock(&mut self) -> bool {
+        let s = self.state;
+        let bit = ((s >> 62) ^ (s >> 51) ^ (s >> 38) ^ (s >> 23) ^ (s >> 13) ^ s) & 1;
+        self.state = (s >> 1) | (bit << 79);
+        bit == 1
+    }
+
+    /// The next output bit. Bits are clocked in pairs, and the second bit of a pair is output
+    /// only if the first one is set.
+    pub fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.clock();
+            let bit = self.clock();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// The next field element, sampled by rejection from `field_bits` output bits.
+    pub fn next_field_element<F: PrimeField64>(&mut self) -> F {
+        debug_assert_eq!(F::ORDER_U64, self.order);
+        loop {
+            let value =
+                (0..self.field_bits).fold(0u64, |acc, _| (acc << 1) | self.next_bit() as u64);
+            if value < self.order {
+                return F::from_canonical_u64(value);
+            }
+        }
+    }
+}
@@ -178,12 +178,7 @@ pub fn benchmark<F: Elem>(c: &mut Criterion, name: &str) {
             },
             |(mut x, mut y, mut z, mut w)| {
                 for _ in 0..10000 {
-                    (x, y, z, w) = (
-                        x.clone() * x.clone(),
-                        y.clone() * y.clone(),
-                        z.clone() * z.clone(),
-                        w.clone() * w.clone(),
-                    );
+                    (x, y, z, w) = (x * x, y * y, z * z, w * w);
                 }
                 (x, y, z, w)
             },
@@ -196,7 +191,7 @@ pub fn benchmark<F: Elem>(c: &mut Criterion, name: &str) {
             || F::random(&mut rng),
             |mut x| {
                 for _ in 0..10000 {
-                    x = x.clone() * x.clone();
+                    x = x * x;
                 }
                 x
             },
@@ -27,6 +27,10 @@ pub mod baby_bear_canonical;
 
 pub mod baby_bear_montgomery;
 
+mod p3_adapter;
+
+mod p3_conversions;
+
 /// A pair of fields, one of which is an extension field of the other.
 pub trait Field {
     /// An element of the base field
@@ -267,9 +271,9 @@ mod tests {
             }
         }
     }
-    pub fn test_field_ops<F: Elem>(p_u64: u64)
+    pub fn test_field_ops<F>(p_u64: u64)
     where
-        F: Into<u64> + From<u64> + Debug,
+        F: Elem + Into<u64> + From<u64> + Debug,
     {
         // For testng, we do 128-bit arithmetic so we don't have to worry about
         // overflows.
@@ -294,9 +298,9 @@ mod tests {
             let xi: u128 = x.into() as _;
             let yi: u128 = y.into() as _;
 
-            assert_eq!((x + y).into() as u128, (&xi + &yi) % p);
-            assert_eq!((x * y).into() as u128, (&xi * &yi) % p);
-            assert_eq!((x - y).into() as u128, (&xi + p - &yi) % p);
+            assert_eq!((x + y).into() as u128, (xi + yi) % p);
+            assert_eq!((x * y).into() as u128, (xi * yi) % p);
+            assert_eq!((x - y).into() as u128, (xi + p - yi) % p);
 
             let xinv = x.inv();
             if x != F::ONE {
@@ -0,0 +1,364 @@
+//! Implementations of the `p3_field` traits for the RISC Zero field types
+//!
+//! These let generic Plonky3 code (batch inversion, powers, extension
+//! arithmetic, DFTs, ...) run unchanged on [baby_bear_montgomery::Elem],
+//! [baby_bear_canonical::Elem] and their [ExtElem](crate::ExtElem)s, so that
+//! each algorithm can be benchmarked across all implementations with the same
+//! generic code. `rand`'s `Standard` distribution is implemented too, as the
+//! generic benchmarks sample their inputs with it.
+//!
+//! [baby_bear_montgomery::Elem]: crate::baby_bear_montgomery::Elem
+//! [baby_bear_canonical::Elem]: crate::baby_bear_canonical::Elem
+
+/// Implements the `p3_field` traits for the `Elem` and `ExtElem` of a Baby
+/// Bear module. Only the public API of the module is used, so the same code
+/// serves both the Montgomery and the canonical representation.
+macro_rules! impl_p3_field {
+    ($module:ident) => {
+        const _: () = {
+            use core::fmt;
+            use core::hash::{Hash, Hasher};
+            use core::iter::{Product, Sum};
+            use core::ops::Div;
+
+            use p3_field::{
+                AbstractField, Field, PrimeField, PrimeField32, PrimeField64, TwoAdicField,
+            };
+
+            use rand::distributions::{Distribution, Standard};
+            use rand::Rng;
+
+            use crate::$module::{Elem, ExtElem};
+            use crate::{Elem as _, ExtElem as _, RootsOfUnity};
+
+            const P: u32 = 15 * (1 << 27) + 1;
+
+            impl Hash for Elem {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    u32::from(*self).hash(state);
+                }
+            }
+
+            impl fmt::Display for Elem {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Display::fmt(&u32::from(*self), f)
+                }
+            }
+
+            impl Sum for Elem {
+                fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::ZERO, |acc, x| acc + x)
+                }
+            }
+
+            impl Product for Elem {
+                fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::ONE, |acc, x| acc * x)
+                }
+            }
+
+            impl Div for Elem {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn div(self, rhs: Self) -> Self {
+                    self * rhs.inverse()
+                }
+            }
+
+            impl Distribution<Elem> for Standard {
+                fn sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> Elem {
+                    Elem::random(&mut rng)
+                }
+            }
+
+            impl AbstractField for Elem {
+                type F = Self;
+
+                fn zero() -> Self {
+                    Self::ZERO
+                }
+                fn one() -> Self {
+                    Self::ONE
+                }
+                fn two() -> Self {
+                    Self::new(2)
+                }
+                fn neg_one() -> Self {
+                    Self::new(P - 1)
+                }
+
+                fn from_f(f: Self::F) -> Self {
+                    f
+                }
+                fn from_bool(b: bool) -> Self {
+                    Self::new(b as u32)
+                }
+                fn from_canonical_u8(n: u8) -> Self {
+                    Self::new(n as u32)
+                }
+                fn from_canonical_u16(n: u16) -> Self {
+                    Self::new(n as u32)
+                }
+                fn from_canonical_u32(n: u32) -> Self {
+                    debug_assert!(n < P);
+                    Self::new(n)
+                }
+                fn from_canonical_u64(n: u64) -> Self {
+                    debug_assert!(n < P as u64);
+                    Self::new(n as u32)
+                }
+                fn from_canonical_usize(n: usize) -> Self {
+                    debug_assert!(n < P as usize);
+                    Self::new(n as u32)
+                }
+                fn from_wrapped_u32(n: u32) -> Self {
+                    Self::new(n)
+                }
+                fn from_wrapped_u64(n: u64) -> Self {
+                    Self::from(n)
+                }
+
+                fn generator() -> Self {
+                    Self::new(31)
+                }
+            }
+
+            impl Field for Elem {
+                type Packing = Self;
+
+                fn try_inverse(&self) -> Option<Self> {
+                    // `inv` maps zero to zero rather than failing.
+                    (*self != Self::ZERO).then(|| self.inv())
+                }
+            }
+
+            impl PrimeField for Elem {}
+
+            impl PrimeField64 for Elem {
+                const ORDER_U64: u64 = P as u64;
+
+                fn as_canonical_u64(&self) -> u64 {
+                    u64::from(*self)
+                }
+
+                fn linear_combination_u64<const N: usize>(u: [u64; N], v: &[Self; N]) -> Self {
+                    // In order not to overflow a u64, we must have sum(u) <= 2^32.
+                    debug_assert!(u.iter().sum::<u64>() <= (1u64 << 32));
+
+                    let dot = u
+                        .iter()
+                        .zip(v)
+                        .map(|(&u, v)| u * u64::from(*v))
+                        .sum::<u64>();
+                    Self::from(dot)
+                }
+            }
+
+            impl PrimeField32 for Elem {
+                const ORDER_U32: u32 = P;
+
+                fn as_canonical_u32(&self) -> u32 {
+                    u32::from(*self)
+                }
+            }
+
+            impl TwoAdicField for Elem {
+                const TWO_ADICITY: usize = Self::MAX_ROU_PO2;
+
+                fn two_adic_generator(bits: usize) -> Self {
+                    assert!(bits <= Self::TWO_ADICITY);
+                    Self::ROU_FWD[bits]
+                }
+            }
+
+            impl Hash for ExtElem {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.elems().hash(state);
+                }
+            }
+
+            impl fmt::Display for ExtElem {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Debug::fmt(self, f)
+                }
+            }
+
+            impl Sum for ExtElem {
+                fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::ZERO, |acc, x| acc + x)
+                }
+            }
+
+            impl Product for ExtElem {
+                fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::ONE, |acc, x| acc * x)
+                }
+            }
+
+            impl Div for ExtElem {
+                type Output = Self;
+
+                #[allow(clippy::suspicious_arithmetic_impl)]
+                fn div(self, rhs: Self) -> Self {
+                    self * rhs.inverse()
+                }
+            }
+
+            impl Distribution<ExtElem> for Standard {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ExtElem {
+                    ExtElem::from_subelems(rng.sample_iter(Standard).take(4))
+                }
+            }
+
+            impl AbstractField for ExtElem {
+                type F = Self;
+
+                fn zero() -> Self {
+                    Self::ZERO
+                }
+                fn one() -> Self {
+                    Self::ONE
+                }
+                fn two() -> Self {
+                    Self::from(Elem::two())
+                }
+                fn neg_one() -> Self {
+                    Self::from(Elem::neg_one())
+                }
+
+                fn from_f(f: Self::F) -> Self {
+                    f
+                }
+                fn from_bool(b: bool) -> Self {
+                    Self::from(Elem::from_bool(b))
+                }
+                fn from_canonical_u8(n: u8) -> Self {
+                    Self::from(Elem::from_canonical_u8(n))
+                }
+                fn from_canonical_u16(n: u16) -> Self {
+                    Self::from(Elem::from_canonical_u16(n))
+                }
+                fn from_canonical_u32(n: u32) -> Self {
+                    Self::from(Elem::from_canonical_u32(n))
+                }
+                fn from_canonical_u64(n: u64) -> Self {
+                    Self::from(Elem::from_canonical_u64(n))
+                }
+                fn from_canonical_usize(n: usize) -> Self {
+                    Self::from(Elem::from_canonical_usize(n))
+                }
+                fn from_wrapped_u32(n: u32) -> Self {
+                    Self::from(Elem::from_wrapped_u32(n))
+                }
+                fn from_wrapped_u64(n: u64) -> Self {
+                    Self::from(Elem::from_wrapped_u64(n))
+                }
+
+                // The multiplicative group of `F_p[X] / (X^4 + 11)` is generated by `X + 3`.
+                fn generator() -> Self {
+                    Self::new(Elem::new(3), Elem::ONE, Elem::ZERO, Elem::ZERO)
+                }
+            }
+
+            impl Field for ExtElem {
+                type Packing = Self;
+
+                fn try_inverse(&self) -> Option<Self> {
+                    // `inv` maps zero to zero rather than failing.
+                    (*self != Self::ZERO).then(|| self.inv())
+                }
+            }
+        };
+    };
+}
+
+impl_p3_field!(baby_bear_montgomery);
+impl_p3_field!(baby_bear_canonical);
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use p3_field::{batch_multiplicative_inverse, Field, PrimeField32, TwoAdicField};
+    use rand::{Rng, SeedableRng};
+
+    use crate::{baby_bear_canonical, baby_bear_montgomery};
+
+    /// Exercises `F` only through the `p3_field` traits.
+    fn test_p3_field<F: PrimeField32 + TwoAdicField>() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        let p = F::ORDER_U64;
+        assert_eq!(p, 15 * (1 << 27) + 1);
+        assert_eq!(F::neg_one() + F::one(), F::zero());
+        assert_eq!(F::two(), F::one().double());
+        assert_eq!(F::from_wrapped_u64(p + 5), F::from_canonical_u8(5));
+        assert_eq!(F::zero().try_inverse(), None);
+
+        let xs: Vec<u64> = (0..100).map(|_| rng.gen_range(1..p)).collect();
+        let elems: Vec<F> = xs.iter().map(|&x| F::from_canonical_u64(x)).collect();
+        let inverses = batch_multiplicative_inverse(&elems);
+        for ((&x, &x_inv), &xi) in elems.iter().zip(&inverses).zip(&xs) {
+            assert_eq!(x * x_inv, F::one());
+            assert_eq!(x.as_canonical_u64(), xi);
+            assert_eq!(x.square().as_canonical_u64(), xi * xi % p);
+        }
+        let powers: Vec<F> = elems[0].powers().take(10).collect();
+        assert_eq!(powers[9], elems[0].exp_u64(9));
+
+        let sum = F::linear_combination_u64([1 << 20, 3], &[elems[0], elems[1]]);
+        assert_eq!(
+            sum,
+            elems[0] * F::from_canonical_u32(1 << 20) + elems[1] * F::from_canonical_u8(3)
+        );
+
+        let g = F::generator();
+        for q in [2, 3, 5] {
+            assert_ne!(g.exp_u64((p - 1) / q), F::one());
+        }
+        for bits in 0..=F::TWO_ADICITY {
+            let h = F::two_adic_generator(bits);
+            assert_eq!(h.exp_power_of_2(bits), F::one());
+            if bits > 0 {
+                assert_ne!(h.exp_power_of_2(bits - 1), F::one());
+            }
+        }
+    }
+
+    /// Checks the generator of the quartic extension against the prime factors
+    /// of `p^4 - 1`.
+    fn test_p3_ext_field<EF: Field>() {
+        const FACTORS: [u128; 8] = [2, 3, 5, 31, 97, 12241, 32472031, 1706804017873];
+        let p = 15 * (1u128 << 27) + 1;
+        let order = p.pow(4) - 1;
+        let g = EF::generator();
+        for q in FACTORS {
+            let e = order / q;
+            // `e` doesn't fit in a u64, so split it as `e = e_hi 2^64 + e_lo`.
+            let (e_hi, e_lo) = ((e >> 64) as u64, e as u64);
+            let g_e = g.exp_u64(e_hi).exp_power_of_2(64) * g.exp_u64(e_lo);
+            assert_ne!(
+                g_e,
+                EF::one(),
+                "generator has order dividing (p^4 - 1) / {q}"
+            );
+        }
+        let x = g.square() + EF::one();
+        assert_eq!(x * x.inverse(), EF::one());
+        assert_eq!(g / x * x, g);
+        assert_eq!(EF::zero().try_inverse(), None);
+        assert_eq!(EF::neg_one() + EF::one(), EF::zero());
+    }
+
+    #[test]
+    fn montgomery() {
+        test_p3_field::<baby_bear_montgomery::Elem>();
+        test_p3_ext_field::<baby_bear_montgomery::ExtElem>();
+    }
+
+    #[test]
+    fn canonical() {
+        test_p3_field::<baby_bear_canonical::Elem>();
+        test_p3_ext_field::<baby_bear_canonical::ExtElem>();
+    }
+}
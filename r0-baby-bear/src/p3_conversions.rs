@@ -0,0 +1,207 @@
+//! Conversions between the RISC Zero and the Plonky3 Baby Bear types
+//!
+//! All base field types represent the same field, so the base conversions
+//! preserve the value of an element. Both Montgomery representations store
+//! `x R mod P`, but with different radices: `R = 2^32` here and `R = 2^31` in
+//! Plonky3, so converting between them is a halving or doubling mod `P`.
+//!
+//! The quartic extensions are different presentations of `F_{p^4}`: here
+//! `ExtElem` is `F_p[Y] / (Y^4 + 11)`, while Plonky3's
+//! `BinomialExtensionField<_, 4>` is `F_p[X] / (X^4 - 11)`. The same
+//! coefficients therefore mean different elements. If `z` is a primitive 8th
+//! root of unity, then `(z Y)^4 = -Y^4 = 11`, so `X -> z Y` is an isomorphism,
+//! which maps the coefficient of `X^i` to the coefficient `z^i` times as
+//! large of `Y^i`. The `From` impls between the extension types apply this
+//! isomorphism (or its inverse), so they preserve addition, multiplication and
+//! inversion.
+
+use p3_baby_bear::{BabyBear, BabyBearCanonical};
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{AbstractExtensionField, AbstractField};
+
+use crate::{baby_bear_canonical, baby_bear_montgomery};
+
+/// The modulus of the field.
+const P: u32 = 15 * (1 << 27) + 1;
+
+/// `z^i` for the primitive 8th root of unity `z = ROU_FWD[3]`, which has
+/// `z^4 = -1`.
+const Z_POWERS: [u32; 4] = [1, 1801542727, 284861408, 420899707];
+
+/// `z^-i` for `z` as in [Z_POWERS].
+const Z_INV_POWERS: [u32; 4] = [1, 1592366214, 1728404513, 211723194];
+
+impl From<baby_bear_montgomery::Elem> for BabyBear {
+    fn from(x: baby_bear_montgomery::Elem) -> Self {
+        // x 2^31 = (x 2^32) / 2 mod P.
+        let m = x.as_u32_montgomery();
+        let half = if m & 1 == 0 { m >> 1 } else { (m + P) >> 1 };
+        BabyBear::from_monty_u32(half)
+    }
+}
+
+impl From<BabyBear> for baby_bear_montgomery::Elem {
+    fn from(x: BabyBear) -> Self {
+        // x 2^32 = 2 (x 2^31) mod P.
+        let double = x.as_monty_u32() << 1;
+        let m = if double >= P { double - P } else { double };
+        baby_bear_montgomery::Elem::new_raw(m)
+    }
+}
+
+/// Implements the canonical-value conversions from and to a base field type.
+macro_rules! impl_canonical_conversions {
+    ($r0:ty, $($other:ty),+) => {
+        $(
+            impl From<$r0> for $other {
+                fn from(x: $r0) -> Self {
+                    <$other>::from_canonical_u32(u32::from(x))
+                }
+            }
+
+            impl From<$other> for $r0 {
+                fn from(x: $other) -> Self {
+                    <$r0>::from(u64::from(p3_field::PrimeField32::as_canonical_u32(&x)))
+                }
+            }
+        )+
+    };
+}
+
+impl_canonical_conversions!(
+    baby_bear_montgomery::Elem,
+    BabyBearCanonical,
+    baby_bear_canonical::Elem
+);
+impl_canonical_conversions!(baby_bear_canonical::Elem, BabyBear, BabyBearCanonical);
+
+/// Implements the isomorphism described in the module documentation between
+/// an `ExtElem` and a Plonky3 quartic extension.
+macro_rules! impl_ext_isomorphism {
+    ($module:ident, $($base:ty),+) => {
+        $(
+            impl From<crate::$module::ExtElem> for BinomialExtensionField<$base, 4> {
+                fn from(x: crate::$module::ExtElem) -> Self {
+                    let y = x.elems();
+                    let coeffs: [$base; 4] = core::array::from_fn(|i| {
+                        <$base>::from(y[i]) * <$base>::from_canonical_u32(Z_INV_POWERS[i])
+                    });
+                    Self::from_base_slice(&coeffs)
+                }
+            }
+
+            impl From<BinomialExtensionField<$base, 4>> for crate::$module::ExtElem {
+                fn from(x: BinomialExtensionField<$base, 4>) -> Self {
+                    let a: &[$base] = x.as_base_slice();
+                    let coeffs: [crate::$module::Elem; 4] = core::array::from_fn(|i| {
+                        (a[i] * <$base>::from_canonical_u32(Z_POWERS[i])).into()
+                    });
+                    Self::from(coeffs)
+                }
+            }
+        )+
+    };
+}
+
+impl_ext_isomorphism!(baby_bear_montgomery, BabyBear, BabyBearCanonical);
+impl_ext_isomorphism!(baby_bear_canonical, BabyBear, BabyBearCanonical);
+
+impl From<baby_bear_montgomery::ExtElem> for baby_bear_canonical::ExtElem {
+    fn from(x: baby_bear_montgomery::ExtElem) -> Self {
+        // Both are defined modulo `Y^4 + 11`, so the coefficients carry over.
+        let coeffs: [baby_bear_canonical::Elem; 4] = core::array::from_fn(|i| x.elems()[i].into());
+        Self::from(coeffs)
+    }
+}
+
+impl From<baby_bear_canonical::ExtElem> for baby_bear_montgomery::ExtElem {
+    fn from(x: baby_bear_canonical::ExtElem) -> Self {
+        let coeffs: [baby_bear_montgomery::Elem; 4] = core::array::from_fn(|i| x.elems()[i].into());
+        Self::from(coeffs)
+    }
+}
+
+// `z` must be a primitive 8th root of unity for the maps to be isomorphisms.
+const _: () = assert!(Z_POWERS[3] as u64 * Z_POWERS[1] as u64 % P as u64 == (P - 1) as u64);
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{Field, PrimeField32};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::Elem as _;
+
+    type EF4 = BinomialExtensionField<BabyBear, 4>;
+    type R0Elem = baby_bear_montgomery::Elem;
+    type R0ExtElem = baby_bear_montgomery::ExtElem;
+    type R0CanonicalExtElem = baby_bear_canonical::ExtElem;
+
+    #[test]
+    fn base_conversions() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let (x, y): (BabyBear, BabyBear) = (rng.gen(), rng.gen());
+            let (a, b) = (R0Elem::from(x), R0Elem::from(y));
+            assert_eq!(u32::from(a), x.as_canonical_u32());
+            assert_eq!(BabyBear::from(a), x);
+            assert_eq!(R0Elem::from(x + y), a + b);
+            assert_eq!(R0Elem::from(x * y), a * b);
+            assert_eq!(R0Elem::from(x.inverse()), a.inv());
+
+            let c = baby_bear_canonical::Elem::from(x);
+            assert_eq!(baby_bear_canonical::Elem::from(a), c);
+            assert_eq!(R0Elem::from(c), a);
+            assert_eq!(BabyBear::from(c), x);
+            let d = BabyBearCanonical::from(x);
+            assert_eq!(BabyBearCanonical::from(a), d);
+            assert_eq!(BabyBearCanonical::from(c), d);
+            assert_eq!(R0Elem::from(d), a);
+            assert_eq!(baby_bear_canonical::Elem::from(d), c);
+        }
+    }
+
+    #[test]
+    fn ext_isomorphism_preserves_operations() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for _ in 0..1000 {
+            let (x, y): (EF4, EF4) = (rng.gen(), rng.gen());
+            let (a, b) = (R0ExtElem::from(x), R0ExtElem::from(y));
+            assert_eq!(EF4::from(a), x);
+            assert_eq!(R0ExtElem::from(x + y), a + b);
+            assert_eq!(R0ExtElem::from(x - y), a - b);
+            assert_eq!(R0ExtElem::from(x * y), a * b);
+            assert_eq!(R0ExtElem::from(x.inverse()), a.inv());
+
+            let c = R0CanonicalExtElem::from(x);
+            assert_eq!(R0CanonicalExtElem::from(a), c);
+            assert_eq!(R0ExtElem::from(c), a);
+            assert_eq!(
+                R0CanonicalExtElem::from(x * y),
+                c * R0CanonicalExtElem::from(y)
+            );
+            assert_eq!(R0CanonicalExtElem::from(x.inverse()), c.inv());
+
+            let xc = BinomialExtensionField::<BabyBearCanonical, 4>::from(a);
+            assert_eq!(R0ExtElem::from(xc), a);
+            assert_eq!(BinomialExtensionField::<BabyBearCanonical, 4>::from(c), xc);
+        }
+    }
+
+    #[test]
+    fn ext_isomorphism_maps_generator() {
+        // The image of X is a fourth root of 11, and embeds the base field.
+        let x = <EF4 as AbstractExtensionField<BabyBear>>::monomial(1);
+        let y = R0ExtElem::from(x);
+        assert_eq!(y.pow(4), R0ExtElem::from(R0Elem::new(11)));
+        assert_eq!(
+            R0ExtElem::from(EF4::from_base(BabyBear::from_canonical_u32(5))),
+            R0ExtElem::from(R0Elem::new(5))
+        );
+
+        // Copying the coefficients instead is not a homomorphism.
+        let naive = R0ExtElem::from([R0Elem::ZERO, R0Elem::ONE, R0Elem::ZERO, R0Elem::ZERO]);
+        assert_ne!(naive.pow(4), R0ExtElem::from(R0Elem::new(11)));
+    }
+}
@@ -0,0 +1,307 @@
+//! Differential tests of the Baby Bear implementations against a `u64` reference model.
+//!
+//! Each case is a random program: a few registers initialized with a mix of random and edge-case
+//! values, followed by a random sequence of operations on them. The program is run on every
+//! implementation through the `p3_field` traits, and after every step the registers are compared
+//! with the model. `Convert` steps round-trip a register through all the other representations.
+//! Quartic extension elements are compared in the Plonky3 basis (`x^4 - 11`), so the RISC Zero
+//! `ExtElem`s (`x^4 + 11`) go through the isomorphism on the way in and out.
+//!
+//! The number of programs and the seed can be set with `DIFFERENTIAL_CASES` and
+//! `DIFFERENTIAL_SEED`; a failure reports the seed and program that reproduce it.
+
+use std::fmt::Debug;
+
+use p3_baby_bear::{BabyBear, BabyBearCanonical};
+use p3_field::extension::BinomialExtensionField;
+use p3_field::{AbstractExtensionField, AbstractField, Field, PrimeField32};
+use r0_baby_bear::{baby_bear_canonical, baby_bear_montgomery};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+const P: u64 = 15 * (1 << 27) + 1;
+const W: u64 = 11;
+const REGISTERS: usize = 4;
+const PROGRAM_LEN: usize = 24;
+
+type EF4 = BinomialExtensionField<BabyBear, 4>;
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Add(usize, usize, usize),
+    Sub(usize, usize, usize),
+    Mul(usize, usize, usize),
+    Neg(usize, usize),
+    Square(usize, usize),
+    /// Maps zero to zero.
+    Inverse(usize, usize),
+    Pow(usize, usize, u64),
+    Convert(usize),
+}
+
+fn random_op(rng: &mut SmallRng) -> Op {
+    let mut reg = || rng.gen_range(0..REGISTERS);
+    let (d, a, b) = (reg(), reg(), reg());
+    match rng.gen_range(0..8) {
+        0 => Op::Add(d, a, b),
+        1 => Op::Sub(d, a, b),
+        2 => Op::Mul(d, a, b),
+        3 => Op::Neg(d, a),
+        4 => Op::Square(d, a),
+        5 => Op::Inverse(d, a),
+        6 => {
+            let exp = match rng.gen_range(0..4) {
+                0 => rng.gen_range(0..8),
+                1 => P - 2,
+                2 => 1725656503,
+                _ => rng.gen(),
+            };
+            Op::Pow(d, a, exp)
+        }
+        _ => Op::Convert(d),
+    }
+}
+
+/// Random canonical values, biased towards the edges of the range.
+fn random_u64(rng: &mut SmallRng) -> u64 {
+    match rng.gen_range(0..8) {
+        0 => 0,
+        1 => 1,
+        2 => P - 1,
+        3 => P - 2,
+        4 => (P - 1) / 2,
+        5 => 1 << rng.gen_range(0..31),
+        _ => rng.gen_range(0..P),
+    }
+}
+
+struct Case {
+    seed: u64,
+    base: [u64; REGISTERS],
+    ext: [[u64; 4]; REGISTERS],
+    ops: Vec<Op>,
+}
+
+fn cases() -> impl Iterator<Item = Case> {
+    let env = |name: &str, default: u64| {
+        std::env::var(name).map_or(default, |v| v.parse().expect("expected an integer"))
+    };
+    let n = env("DIFFERENTIAL_CASES", 2000);
+    let first_seed = env("DIFFERENTIAL_SEED", 0);
+    (first_seed..first_seed + n).map(|seed| {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let base = [(); REGISTERS].map(|_| random_u64(&mut rng));
+        let ext = [(); REGISTERS].map(|_| [(); 4].map(|_| random_u64(&mut rng)));
+        let ops = (0..PROGRAM_LEN).map(|_| random_op(&mut rng)).collect();
+        Case {
+            seed,
+            base,
+            ext,
+            ops,
+        }
+    })
+}
+
+/// Arithmetic of the reference model, which is also used for the extension field.
+trait Model: Copy + Debug + PartialEq {
+    const ONE: Self;
+    fn add(self, rhs: Self) -> Self;
+    fn neg(self) -> Self;
+    fn mul(self, rhs: Self) -> Self;
+    /// `|F^*|`, so that `x^(ORDER - 1)` is the inverse of non-zero `x`.
+    const MULTIPLICATIVE_ORDER: u128;
+
+    fn pow(self, mut exp: u128) -> Self {
+        let (mut base, mut acc) = (self, Self::ONE);
+        while exp != 0 {
+            if exp & 1 == 1 {
+                acc = acc.mul(base);
+            }
+            base = base.mul(base);
+            exp >>= 1;
+        }
+        acc
+    }
+
+    fn inverse(self) -> Self {
+        self.pow(Self::MULTIPLICATIVE_ORDER - 1)
+    }
+
+    fn apply(regs: &mut [Self; REGISTERS], op: Op) {
+        match op {
+            Op::Add(d, a, b) => regs[d] = regs[a].add(regs[b]),
+            Op::Sub(d, a, b) => regs[d] = regs[a].add(regs[b].neg()),
+            Op::Mul(d, a, b) => regs[d] = regs[a].mul(regs[b]),
+            Op::Neg(d, a) => regs[d] = regs[a].neg(),
+            Op::Square(d, a) => regs[d] = regs[a].mul(regs[a]),
+            Op::Inverse(d, a) => regs[d] = regs[a].inverse(),
+            Op::Pow(d, a, exp) => regs[d] = regs[a].pow(exp as u128),
+            Op::Convert(_) => {}
+        }
+    }
+}
+
+impl Model for u64 {
+    const ONE: Self = 1;
+    const MULTIPLICATIVE_ORDER: u128 = (P - 1) as u128;
+
+    fn add(self, rhs: Self) -> Self {
+        (self + rhs) % P
+    }
+    fn neg(self) -> Self {
+        (P - self) % P
+    }
+    fn mul(self, rhs: Self) -> Self {
+        self * rhs % P
+    }
+}
+
+impl Model for [u64; 4] {
+    const ONE: Self = [1, 0, 0, 0];
+    const MULTIPLICATIVE_ORDER: u128 = (P as u128).pow(4) - 1;
+
+    fn add(self, rhs: Self) -> Self {
+        [0, 1, 2, 3].map(|i| self[i].add(rhs[i]))
+    }
+    fn neg(self) -> Self {
+        self.map(Model::neg)
+    }
+    /// Multiplication modulo `x^4 - W`.
+    fn mul(self, rhs: Self) -> Self {
+        let mut res = [0; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                let mut term = self[i].mul(rhs[j]);
+                if i + j >= 4 {
+                    term = term.mul(W);
+                }
+                res[(i + j) % 4] = res[(i + j) % 4].add(term);
+            }
+        }
+        res
+    }
+}
+
+fn apply<F: Field>(regs: &mut [F; REGISTERS], op: Op, convert: impl Fn(F) -> F) {
+    match op {
+        Op::Add(d, a, b) => regs[d] = regs[a] + regs[b],
+        Op::Sub(d, a, b) => regs[d] = regs[a] - regs[b],
+        Op::Mul(d, a, b) => regs[d] = regs[a] * regs[b],
+        Op::Neg(d, a) => regs[d] = -regs[a],
+        Op::Square(d, a) => regs[d] = regs[a].square(),
+        Op::Inverse(d, a) => regs[d] = regs[a].try_inverse().unwrap_or(F::zero()),
+        Op::Pow(d, a, exp) => regs[d] = regs[a].exp_u64(exp),
+        Op::Convert(d) => regs[d] = convert(regs[d]),
+    }
+}
+
+/// Runs every case on `F`, comparing the registers with the model after each step.
+fn run<F, M>(
+    name: &str,
+    initial: impl Fn(&Case) -> [M; REGISTERS],
+    to_f: impl Fn(M) -> F,
+    to_model: impl Fn(F) -> M,
+    convert: impl Fn(F) -> F,
+) where
+    F: Field,
+    M: Model,
+{
+    for case in cases() {
+        let mut model = initial(&case);
+        let mut regs = model.map(&to_f);
+        for (step, &op) in case.ops.iter().enumerate() {
+            M::apply(&mut model, op);
+            apply(&mut regs, op, &convert);
+            assert_eq!(
+                regs.map(&to_model),
+                model,
+                "{name}: mismatch after step {step} of seed {} (set DIFFERENTIAL_SEED={} \
+                 DIFFERENTIAL_CASES=1 to reproduce); program: {:?}",
+                case.seed,
+                case.seed,
+                case.ops,
+            );
+        }
+    }
+}
+
+/// Round-trips through every representation, crossing both Montgomery radices.
+fn round_trip_base(x: BabyBear) -> BabyBear {
+    let x = baby_bear_montgomery::Elem::from(x);
+    let x = BabyBearCanonical::from(baby_bear_canonical::Elem::from(x));
+    BabyBear::from(baby_bear_montgomery::Elem::from(x))
+}
+
+/// Round-trips through both `ExtElem`s, crossing the isomorphism twice.
+fn round_trip_ext(x: EF4) -> EF4 {
+    let x = baby_bear_montgomery::ExtElem::from(x);
+    EF4::from(baby_bear_canonical::ExtElem::from(x))
+}
+
+fn run_base<F>(name: &str)
+where
+    F: PrimeField32 + From<BabyBear>,
+    BabyBear: From<F>,
+{
+    let convert = |x: F| F::from(round_trip_base(BabyBear::from(x)));
+    run(
+        name,
+        |case| case.base,
+        |x| F::from_canonical_u64(x),
+        |x| x.as_canonical_u32() as u64,
+        convert,
+    );
+}
+
+fn run_ext<E>(name: &str)
+where
+    E: Field + From<EF4>,
+    EF4: From<E>,
+{
+    let to_ext = |x: [u64; 4]| {
+        let coeffs = x.map(BabyBear::from_canonical_u64);
+        E::from(EF4::from_base_slice(&coeffs))
+    };
+    let to_model = |x: E| {
+        let x = EF4::from(x);
+        let coeffs: &[BabyBear] = x.as_base_slice();
+        [0, 1, 2, 3].map(|i| coeffs[i].as_canonical_u32() as u64)
+    };
+    let convert = |x: E| E::from(round_trip_ext(EF4::from(x)));
+    run(name, |case| case.ext, to_ext, to_model, convert);
+}
+
+#[test]
+fn p3_baby_bear() {
+    run_base::<BabyBear>("p3 BabyBear");
+}
+
+#[test]
+fn p3_baby_bear_canonical() {
+    run_base::<BabyBearCanonical>("p3 BabyBearCanonical");
+}
+
+#[test]
+fn r0_montgomery() {
+    run_base::<baby_bear_montgomery::Elem>("r0 Montgomery Elem");
+}
+
+#[test]
+fn r0_canonical() {
+    run_base::<baby_bear_canonical::Elem>("r0 canonical Elem");
+}
+
+#[test]
+fn p3_quartic_extension() {
+    run_ext::<EF4>("p3 BinomialExtensionField<BabyBear, 4>");
+}
+
+#[test]
+fn r0_montgomery_extension() {
+    run_ext::<baby_bear_montgomery::ExtElem>("r0 Montgomery ExtElem");
+}
+
+#[test]
+fn r0_canonical_extension() {
+    run_ext::<baby_bear_canonical::ExtElem>("r0 canonical ExtElem");
+}
@@ -0,0 +1,40 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use p3_baby_bear::BabyBear;
+use p3_poseidon2::{poseidon2_baby_bear_16, Poseidon2BabyBear};
+use p3_rescue::{rescue_prime_baby_bear, BABY_BEAR_RATE};
+use p3_symmetric::{CryptographicHasher, CryptographicPermutation, PaddedSponge};
+use rand::Rng;
+
+/// Both permutations have width 16, and both hashes use the same sponge with rate 8.
+type Sponge<P> = PaddedSponge<P, 16, BABY_BEAR_RATE, BABY_BEAR_RATE>;
+
+fn bench_hash<P>(c: &mut Criterion, name: &str, perm: P)
+where
+    P: CryptographicPermutation<[BabyBear; 16]>,
+{
+    let mut rng = rand::thread_rng();
+    let state: [BabyBear; 16] = core::array::from_fn(|_| rng.gen());
+    c.bench_function(&format!("{name} permutation"), |b| {
+        b.iter(|| perm.permute(black_box(state)))
+    });
+
+    let sponge = Sponge::new(perm);
+    // 7 elements fit in a single block with the padding; 1024 take 129 blocks.
+    for len in [7, 1024] {
+        let input: Vec<BabyBear> = (0..len).map(|_| rng.gen()).collect();
+        c.bench_function(&format!("{name} hash {len} elements"), |b| {
+            b.iter(|| sponge.hash_slice(black_box(&input)))
+        });
+    }
+}
+
+fn bench_rescue(c: &mut Criterion) {
+    bench_hash(c, "Rescue-Prime BabyBear", rescue_prime_baby_bear());
+}
+
+fn bench_poseidon2(c: &mut Criterion) {
+    bench_hash::<Poseidon2BabyBear<16>>(c, "Poseidon2 BabyBear", poseidon2_baby_bear_16());
+}
+
+criterion_group!(hash, bench_rescue, bench_poseidon2);
+criterion_main!(hash);
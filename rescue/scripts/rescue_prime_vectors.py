@@ -0,0 +1,126 @@
+#!/usr/bin/env python3
+"""Regenerates the test vectors of p3-rescue.
+
+This is a standalone implementation of the Rescue-Prime specification (Szepieniec, Ashur and
+Dhooghe, "Rescue-Prime: a Standard Specification (SoK)", IACR ePrint 2020/1143), written with
+exact integers and the SHAKE256 of `hashlib`, and sharing no code with the crate. It follows the
+parameter derivation of the specification's reference implementation: the number of rounds from
+the Groebner basis bound with a 50% margin, the MDS matrix from the Vandermonde matrix of the
+smallest generator, and the round constants from SHAKE256 on the seed
+"Rescue-XLIX(p,m,capacity,security_level)".
+
+Run it with `python3 rescue/scripts/rescue_prime_vectors.py`; it needs nothing beyond the
+standard library.
+"""
+
+import hashlib
+import math
+
+
+def num_rounds(p, m, capacity, security_level, alpha):
+    rate = m - capacity
+    dcon = lambda n: math.floor(0.5 * (alpha - 1) * m * (n - 1) + 2)
+    v = lambda n: m * (n - 1) + rate
+    target = 2**security_level
+    for l1 in range(1, 25):
+        if math.comb(v(l1) + dcon(l1), v(l1)) ** 2 > target:
+            break
+    return math.ceil(1.5 * max(5, l1))
+
+
+def prime_factors(n):
+    factors, d = [], 2
+    while d * d <= n:
+        if n % d == 0:
+            factors.append(d)
+            while n % d == 0:
+                n //= d
+        d += 1
+    if n > 1:
+        factors.append(n)
+    return factors
+
+
+def smallest_generator(p):
+    g = 2
+    while any(pow(g, (p - 1) // q, p) == 1 for q in prime_factors(p - 1)):
+        g += 1
+    return g
+
+
+def mds_matrix(p, m):
+    """The right half of the reduced row echelon form of the m x 2m Vandermonde matrix of the
+    smallest generator, transposed."""
+    g = smallest_generator(p)
+    v = [[pow(g, i * j, p) for j in range(2 * m)] for i in range(m)]
+    for c in range(m):
+        pivot = next(r for r in range(c, m) if v[r][c] % p)
+        v[c], v[pivot] = v[pivot], v[c]
+        inv = pow(v[c][c], p - 2, p)
+        v[c] = [x * inv % p for x in v[c]]
+        for r in range(m):
+            if r != c and v[r][c]:
+                f = v[r][c]
+                v[r] = [(x - f * y) % p for x, y in zip(v[r], v[c])]
+    right = [row[m:] for row in v]
+    return [[right[j][i] for j in range(m)] for i in range(m)]
+
+
+def round_constants(p, m, capacity, security_level, n):
+    bytes_per_int = math.ceil(len(bin(p)[2:]) / 8) + 1
+    num_bytes = bytes_per_int * 2 * m * n
+    seed = "Rescue-XLIX(%i,%i,%i,%i)" % (p, m, capacity, security_level)
+    stream = hashlib.shake_256(seed.encode("ascii")).digest(num_bytes)
+    return [
+        int.from_bytes(stream[bytes_per_int * i : bytes_per_int * (i + 1)], "little") % p
+        for i in range(2 * m * n)
+    ]
+
+
+def permute(p, m, alpha, n, mds, rc, state):
+    alpha_inv = pow(alpha, -1, p - 1)
+    mul = lambda s: [sum(mds[i][j] * s[j] for j in range(m)) % p for i in range(m)]
+    for i in range(n):
+        state = mul([pow(x, alpha, p) for x in state])
+        state = [(x + rc[2 * m * i + j]) % p for j, x in enumerate(state)]
+        state = mul([pow(x, alpha_inv, p) for x in state])
+        state = [(x + rc[2 * m * i + m + j]) % p for j, x in enumerate(state)]
+    return state
+
+
+def rescue_hash(p, m, capacity, alpha, n, mds, rc, message):
+    """The sponge of the specification: pad with a one and then zeros to a multiple of the rate,
+    absorb by addition, and squeeze one rate's worth of elements."""
+    rate = m - capacity
+    padded = message + [1]
+    while len(padded) % rate:
+        padded.append(0)
+    state = [0] * m
+    for k in range(0, len(padded), rate):
+        for i in range(rate):
+            state[i] = (state[i] + padded[k + i]) % p
+        state = permute(p, m, alpha, n, mds, rc, state)
+    return state[:rate]
+
+
+INSTANCES = [
+    # name, p, width, capacity, alpha
+    ("BabyBear", 15 * 2**27 + 1, 16, 8, 7),
+    ("Mersenne31", 2**31 - 1, 12, 6, 5),
+]
+
+for name, p, m, capacity, alpha in INSTANCES:
+    n = num_rounds(p, m, capacity, 128, alpha)
+    mds = mds_matrix(p, m)
+    rc = round_constants(p, m, capacity, 128, n)
+    print(name)
+    print("  rounds:", n, "alpha_inv:", pow(alpha, -1, p - 1), "generator:", smallest_generator(p))
+    print("  mds[0][..4]:", mds[0][:4])
+    print("  round_constants[..4]:", rc[:4])
+    print("  permute([0, 1, ..]):", permute(p, m, alpha, n, mds, rc, list(range(m))))
+    print("  hash([]):", rescue_hash(p, m, capacity, alpha, n, mds, rc, []))
+    print("  hash([0, 1, .., 9]):", rescue_hash(p, m, capacity, alpha, n, mds, rc, list(range(10))))
+
+print("SHAKE256")
+print("  shake256(b'', 40):", hashlib.shake_256(b"").hexdigest(40))
+print("  shake256(0..200, 300)[280..]:", hashlib.shake_256(bytes(range(200))).hexdigest(300)[-40:])
@@ -0,0 +1,103 @@
+use p3_baby_bear::BabyBear;
+use p3_symmetric::PaddedSponge;
+
+use crate::Rescue;
+
+/// Rescue over Baby Bear, with the S-box `x -> x^7`, the smallest degree that is a permutation of
+/// Baby Bear. The inverse S-box is `x -> x^1725656503`.
+pub type RescueBabyBear = Rescue<BabyBear, 16, 7>;
+
+/// The capacity of the Baby Bear instance: 8 elements, or 248 bits.
+pub const BABY_BEAR_CAPACITY: usize = 8;
+/// The rate of the Baby Bear instance.
+pub const BABY_BEAR_RATE: usize = 8;
+
+/// The Rescue-Prime hash over Baby Bear, which outputs the whole rate.
+pub type RescuePrimeHashBabyBear = PaddedSponge<RescueBabyBear, 16, BABY_BEAR_RATE, BABY_BEAR_RATE>;
+
+/// The Rescue-Prime permutation of width 16 over Baby Bear, for 128 bits of security.
+pub fn rescue_prime_baby_bear() -> RescueBabyBear {
+    Rescue::new_rescue_prime(BABY_BEAR_CAPACITY, 128)
+}
+
+pub fn rescue_prime_hash_baby_bear() -> RescuePrimeHashBabyBear {
+    PaddedSponge::new(rescue_prime_baby_bear())
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, Field, PackedField};
+    use p3_symmetric::CryptographicHasher;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn from_u32s<const N: usize>(xs: [u32; N]) -> [BabyBear; N] {
+        xs.map(BabyBear::from_canonical_u32)
+    }
+
+    // The expected values below are printed by `rescue/scripts/rescue_prime_vectors.py`, a
+    // standalone Python implementation of the Rescue-Prime specification (ePrint 2020/1143).
+
+    #[test]
+    fn parameters() {
+        let perm = rescue_prime_baby_bear();
+        assert_eq!(perm.num_rounds(), 8);
+        assert_eq!(perm.alpha_inv, 1725656503);
+        assert_eq!(
+            perm.round_constants[..4],
+            from_u32s([208538733, 1862076767, 1405095346, 588020603])
+        );
+    }
+
+    #[test]
+    fn permutation_known_answer() {
+        let input: [BabyBear; 16] = core::array::from_fn(BabyBear::from_canonical_usize);
+        let expected = from_u32s([
+            660356918, 455993105, 1042155240, 1240803171, 1731677104, 18478896, 1793689478,
+            160380643, 1404805494, 459948817, 1624435381, 1727794897, 1795253082, 12437390,
+            816690982, 371795400,
+        ]);
+        assert_eq!(rescue_prime_baby_bear().permute(input), expected);
+    }
+
+    #[test]
+    fn hash_known_answers() {
+        let hash = rescue_prime_hash_baby_bear();
+        assert_eq!(
+            hash.hash_iter::<[BabyBear; 0]>([]),
+            from_u32s([
+                1260182038, 812697052, 242826493, 952293008, 430289051, 781203939, 1991705802,
+                1611294268,
+            ])
+        );
+        // Two blocks.
+        let input: [BabyBear; 10] = core::array::from_fn(BabyBear::from_canonical_usize);
+        assert_eq!(
+            hash.hash_slice(&input),
+            from_u32s([
+                1788483671, 607685902, 225640161, 1328244379, 650148224, 871898157, 669653172,
+                1226384033,
+            ])
+        );
+    }
+
+    #[test]
+    fn packed_matches_scalar() {
+        type Packed = <BabyBear as Field>::Packing;
+        let perm = rescue_prime_baby_bear();
+        let mut rng = SmallRng::seed_from_u64(0);
+        let inputs: [[BabyBear; 16]; Packed::WIDTH] =
+            core::array::from_fn(|_| core::array::from_fn(|_| rng.gen()));
+        let packed: [Packed; 16] =
+            core::array::from_fn(|i| Packed::from_fn(|lane| inputs[lane][i]));
+        let outputs = perm.permute(packed);
+        for (lane, input) in inputs.into_iter().enumerate() {
+            let expected = perm.permute(input);
+            for (output, expected) in outputs.iter().zip(expected) {
+                assert_eq!(output.as_slice()[lane], expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! The Rescue-Prime permutation and hash.
+//!
+//! Each round of Rescue applies the S-box `x -> x^ALPHA` and its inverse `x -> x^(1/ALPHA)`,
+//! whose exponent is large. The inverse S-box is computed with `exp_u64`, so it uses the addition
+//! chains of the fields that provide them, such as `exp_1725656503` for `x^(1/7)` in Baby Bear
+//! and `exp_1717986917` for `x^(1/5)` in Mersenne31.
+
+#![no_std]
+
+extern crate alloc;
+
+mod baby_bear;
+mod mds;
+mod round_constants;
+mod shake;
+
+use alloc::vec::Vec;
+
+pub use baby_bear::*;
+pub use mds::*;
+use p3_field::{AbstractField, PrimeField64};
+use p3_symmetric::{CryptographicPermutation, Permutation};
+pub use round_constants::*;
+
+/// The Rescue permutation of `WIDTH` elements of `F`, with S-box `x -> x^ALPHA`.
+///
+/// Each round applies the S-box to every element, the MDS matrix and the first `WIDTH` round
+/// constants, then the inverse S-box to every element, the MDS matrix and the next `WIDTH` round
+/// constants.
+#[derive(Clone, Debug)]
+pub struct Rescue<F: PrimeField64, const WIDTH: usize, const ALPHA: u64> {
+    num_rounds: usize,
+    mds: [[F; WIDTH]; WIDTH],
+    /// `2 WIDTH` constants per round, in round order.
+    round_constants: Vec<F>,
+    /// `1 / ALPHA mod (p - 1)`.
+    alpha_inv: u64,
+}
+
+impl<F: PrimeField64, const WIDTH: usize, const ALPHA: u64> Rescue<F, WIDTH, ALPHA> {
+    pub fn new(num_rounds: usize, mds: [[F; WIDTH]; WIDTH], round_constants: Vec<F>) -> Self {
+        assert_eq!(round_constants.len(), 2 * WIDTH * num_rounds);
+        let alpha_inv = inverse_mod(ALPHA, F::ORDER_U64 - 1)
+            .expect("x -> x^ALPHA must be a permutation of the field");
+        Self {
+            num_rounds,
+            mds,
+            round_constants,
+            alpha_inv,
+        }
+    }
+
+    /// The Rescue-Prime instance with the given capacity and security level (in bits), with the
+    /// number of rounds, MDS matrix and round constants all derived as in the specification.
+    pub fn new_rescue_prime(capacity: usize, security_level: usize) -> Self {
+        let num_rounds = rescue_prime_num_rounds(WIDTH, capacity, security_level, ALPHA);
+        Self::new(
+            num_rounds,
+            rescue_prime_mds(),
+            rescue_prime_round_constants(WIDTH, capacity, security_level, num_rounds),
+        )
+    }
+
+    pub fn num_rounds(&self) -> usize {
+        self.num_rounds
+    }
+
+    pub fn permute<AF: AbstractField<F = F>>(&self, mut state: [AF; WIDTH]) -> [AF; WIDTH] {
+        self.permute_mut(&mut state);
+        state
+    }
+
+    pub fn permute_mut<AF: AbstractField<F = F>>(&self, state: &mut [AF; WIDTH]) {
+        for constants in self.round_constants.chunks_exact(2 * WIDTH) {
+            let (first, second) = constants.split_at(WIDTH);
+
+            for x in state.iter_mut() {
+                *x = x.exp_const_u64::<ALPHA>();
+            }
+            self.mds_layer(state);
+            add_constants(state, first);
+
+            for x in state.iter_mut() {
+                *x = x.exp_u64(self.alpha_inv);
+            }
+            self.mds_layer(state);
+            add_constants(state, second);
+        }
+    }
+
+    fn mds_layer<AF: AbstractField<F = F>>(&self, state: &mut [AF; WIDTH]) {
+        let input = state.clone();
+        for (x, row) in state.iter_mut().zip(&self.mds) {
+            *x = input
+                .iter()
+                .zip(row)
+                .map(|(y, &m)| y.clone() * AF::from_f(m))
+                .sum();
+        }
+    }
+}
+
+fn add_constants<AF: AbstractField>(state: &mut [AF], constants: &[AF::F]) {
+    for (x, &c) in state.iter_mut().zip(constants) {
+        *x += AF::from_f(c);
+    }
+}
+
+/// The inverse of `a` modulo `n`, if it exists.
+fn inverse_mod(a: u64, n: u64) -> Option<u64> {
+    // Extended Euclid, tracking only the coefficient of `a`.
+    let (mut r0, mut r1) = (n as i128, a as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    (r0 == 1).then(|| t0.rem_euclid(n as i128) as u64)
+}
+
+impl<F, AF, const WIDTH: usize, const ALPHA: u64> Permutation<[AF; WIDTH]>
+    for Rescue<F, WIDTH, ALPHA>
+where
+    F: PrimeField64,
+    AF: AbstractField<F = F>,
+{
+    fn permute_mut(&self, input: &mut [AF; WIDTH]) {
+        Rescue::permute_mut(self, input);
+    }
+}
+
+impl<F, AF, const WIDTH: usize, const ALPHA: u64> CryptographicPermutation<[AF; WIDTH]>
+    for Rescue<F, WIDTH, ALPHA>
+where
+    F: PrimeField64,
+    AF: AbstractField<F = F>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::AbstractField;
+    use p3_mersenne_31::Mersenne31;
+
+    use super::*;
+
+    #[test]
+    fn inverse_mod() {
+        assert_eq!(super::inverse_mod(7, 15 << 27), Some(1725656503));
+        assert_eq!(super::inverse_mod(5, (1 << 31) - 2), Some(1717986917));
+        assert_eq!(super::inverse_mod(5, 15 << 27), None);
+    }
+
+    /// Mersenne31 has `x^5` as its S-box, whose inverse goes through `exp_1717986917`.
+    #[test]
+    fn mersenne_31_known_answer() {
+        // Printed by `rescue/scripts/rescue_prime_vectors.py`.
+        let perm = Rescue::<Mersenne31, 12, 5>::new_rescue_prime(6, 128);
+        assert_eq!(perm.num_rounds(), 8);
+        let input: [Mersenne31; 12] = core::array::from_fn(Mersenne31::from_canonical_usize);
+        let expected = [
+            1174355075, 506638036, 1293741855, 669671042, 881673047, 1403310363, 1489659750,
+            106483224, 1578796769, 289825640, 498340024, 564347160,
+        ];
+        assert_eq!(
+            perm.permute(input),
+            expected.map(Mersenne31::from_canonical_u32)
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use p3_field::PrimeField64;
+
+/// The MDS matrix of Rescue-Prime, as given by the specification.
+///
+/// Let `g` be the smallest generator of the multiplicative group and `V` the `WIDTH x 2 WIDTH`
+/// Vandermonde matrix with entries `g^(i j)`. The reduced row echelon form of `V` is
+/// `[I | A]`, a systematic generator matrix of a Reed-Solomon code, and the MDS matrix is `A^T`.
+pub fn rescue_prime_mds<F: PrimeField64, const WIDTH: usize>() -> [[F; WIDTH]; WIDTH] {
+    let g = smallest_generator::<F>();
+    let vandermonde = |i: usize, j: usize| g.exp_u64((i * j) as u64);
+    let mut rows: [[F; WIDTH]; WIDTH] =
+        core::array::from_fn(|i| core::array::from_fn(|j| vandermonde(i, j)));
+    let mut right: [[F; WIDTH]; WIDTH] =
+        core::array::from_fn(|i| core::array::from_fn(|j| vandermonde(i, WIDTH + j)));
+
+    // Gauss-Jordan elimination on [rows | right]. The left half is an invertible Vandermonde
+    // matrix, so a pivot always exists.
+    for col in 0..WIDTH {
+        let pivot = (col..WIDTH).find(|&r| !rows[r][col].is_zero()).unwrap();
+        rows.swap(col, pivot);
+        right.swap(col, pivot);
+        let inv = rows[col][col].inverse();
+        for x in rows[col].iter_mut().chain(right[col].iter_mut()) {
+            *x *= inv;
+        }
+        let (pivot_left, pivot_right) = (rows[col], right[col]);
+        for r in (0..WIDTH).filter(|&r| r != col) {
+            let factor = rows[r][col];
+            for (x, &p) in rows[r].iter_mut().zip(&pivot_left) {
+                *x -= p * factor;
+            }
+            for (x, &p) in right[r].iter_mut().zip(&pivot_right) {
+                *x -= p * factor;
+            }
+        }
+    }
+
+    core::array::from_fn(|i| core::array::from_fn(|j| right[j][i]))
+}
+
+/// The smallest element, counting up from 2, that generates the multiplicative group.
+///
+/// This is what the specification uses rather than the field's `generator`.
+pub(crate) fn smallest_generator<F: PrimeField64>() -> F {
+    let order = F::ORDER_U64 - 1;
+    (2..)
+        .map(F::from_canonical_u64)
+        .find(|g| {
+            prime_factors(order)
+                .into_iter()
+                .flatten()
+                .all(|q| g.exp_u64(order / q) != F::one())
+        })
+        .unwrap()
+}
+
+/// The distinct prime factors of `n`, by trial division.
+fn prime_factors(mut n: u64) -> [Option<u64>; 16] {
+    let mut factors = [None; 16];
+    let mut count = 0;
+    let mut d = 2;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            factors[count] = Some(d);
+            count += 1;
+            while n.is_multiple_of(d) {
+                n /= d;
+            }
+        }
+        d += 1;
+    }
+    if n > 1 {
+        factors[count] = Some(n);
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::{AbstractField, Field, PrimeField32};
+
+    use super::*;
+
+    #[test]
+    fn baby_bear_mds() {
+        assert_eq!(
+            smallest_generator::<BabyBear>(),
+            BabyBear::from_canonical_u32(31)
+        );
+
+        let mds = rescue_prime_mds::<BabyBear, 16>();
+        // Printed by `rescue/scripts/rescue_prime_vectors.py`.
+        assert_eq!(
+            mds[0][..4]
+                .iter()
+                .map(|x| x.as_canonical_u32())
+                .collect::<alloc::vec::Vec<_>>(),
+            [1009419883, 491504098, 1391098513, 932575684]
+        );
+
+        // Every 2x2 minor is non-singular, as it must be for an MDS matrix.
+        for (i0, i1) in [(0, 1), (3, 9), (14, 15)] {
+            for (j0, j1) in [(0, 1), (2, 7), (5, 15)] {
+                let det = mds[i0][j0] * mds[i1][j1] - mds[i0][j1] * mds[i1][j0];
+                assert!(!det.is_zero());
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use p3_field::PrimeField64;
+
+use crate::shake::shake256;
+
+/// The number of rounds of Rescue-Prime, as given by the specification.
+///
+/// This is the smallest `l` for which the Gröbner basis attack on `l` rounds costs more than
+/// `2^security_level`, with a minimum of 5, plus a 50% security margin.
+pub fn rescue_prime_num_rounds(
+    width: usize,
+    capacity: usize,
+    security_level: usize,
+    alpha: u64,
+) -> usize {
+    let rate = width - capacity;
+    let dcon = |n: usize| ((alpha as usize - 1) * width * (n - 1)) / 2 + 2;
+    let v = |n: usize| width * (n - 1) + rate;
+
+    let l1 = (1..25)
+        .find(|&l| {
+            let binomial = binomial(v(l) + dcon(l), v(l));
+            exceeds_power_of_two(&square(&binomial), security_level)
+        })
+        .unwrap_or(24);
+    (3 * l1.max(5)).div_ceil(2)
+}
+
+/// The round constants of Rescue-Prime, as given by the specification: the output of SHAKE256 on
+/// `Rescue-XLIX(p,m,capacity,security_level)`, read as little-endian integers of one byte more
+/// than the size of `p`, reduced modulo `p`.
+pub fn rescue_prime_round_constants<F: PrimeField64>(
+    width: usize,
+    capacity: usize,
+    security_level: usize,
+    num_rounds: usize,
+) -> Vec<F> {
+    let p = F::ORDER_U64;
+    let bytes_per_int = (64 - p.leading_zeros() as usize).div_ceil(8) + 1;
+    let num_constants = 2 * width * num_rounds;
+
+    let seed = format!("Rescue-XLIX({p},{width},{capacity},{security_level})");
+    let bytes = shake256(seed.as_bytes(), bytes_per_int * num_constants);
+    bytes
+        .chunks_exact(bytes_per_int)
+        .map(|chunk| {
+            let value = chunk
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &b| ((acc << 8) | b as u128) % p as u128);
+            F::from_canonical_u64(value as u64)
+        })
+        .collect()
+}
+
+// The binomials in the round number formula overflow any machine integer for larger widths and
+// security levels, so they are computed with little-endian base `2^32` big integers.
+
+fn binomial(n: usize, k: usize) -> Vec<u32> {
+    // C(n - k + i, i) = C(n - k + i - 1, i - 1) (n - k + i) / i, with an exact division.
+    let mut c = vec![1u32];
+    for i in 1..=k {
+        let mut carry = 0u64;
+        for limb in c.iter_mut() {
+            let x = *limb as u64 * (n - k + i) as u64 + carry;
+            *limb = x as u32;
+            carry = x >> 32;
+        }
+        if carry != 0 {
+            c.push(carry as u32);
+        }
+
+        let mut rem = 0u64;
+        for limb in c.iter_mut().rev() {
+            let x = (rem << 32) | *limb as u64;
+            *limb = (x / i as u64) as u32;
+            rem = x % i as u64;
+        }
+        debug_assert_eq!(rem, 0);
+        while c.last() == Some(&0) {
+            c.pop();
+        }
+    }
+    c
+}
+
+fn square(x: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; 2 * x.len()];
+    for (i, &a) in x.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &b) in x.iter().enumerate() {
+            let t = a as u64 * b as u64 + result[i + j] as u64 + carry;
+            result[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        result[i + x.len()] = carry as u32;
+    }
+    while result.last() == Some(&0) {
+        result.pop();
+    }
+    result
+}
+
+/// Whether `x > 2^log`.
+fn exceeds_power_of_two(x: &[u32], log: usize) -> bool {
+    let Some(&top) = x.last() else {
+        return false;
+    };
+    let bits = 32 * x.len() - top.leading_zeros() as usize;
+    let is_power_of_two = top.is_power_of_two() && x[..x.len() - 1].iter().all(|&l| l == 0);
+    bits > log + 1 || (bits == log + 1 && !is_power_of_two)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_integers() {
+        // C(100, 50) = 100891344545564193334812497256
+        let c = binomial(100, 50);
+        let value = c
+            .iter()
+            .rev()
+            .fold(0u128, |acc, &limb| (acc << 32) | limb as u128);
+        assert_eq!(value, 100891344545564193334812497256);
+        assert_eq!(binomial(10, 0), [1]);
+
+        assert!(exceeds_power_of_two(&[5], 2));
+        assert!(!exceeds_power_of_two(&[4], 2));
+        assert!(!exceeds_power_of_two(&[0, 1], 32));
+        assert!(exceeds_power_of_two(&[1, 1], 32));
+        assert_eq!(square(&[0, 1]), [0, 0, 1]);
+        assert_eq!(square(&[u32::MAX]), [1, u32::MAX - 1]);
+    }
+}
@@ -0,0 +1,142 @@
+//! SHAKE256, which the Rescue-Prime specification uses to derive its round constants.
+//!
+//! Only used at setup time, so this is a straightforward rather than a fast implementation.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The rate of SHAKE256 in bytes, `(1600 - 2 * 256) / 8`.
+const RATE: usize = 136;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation of each lane in the combined rho and pi steps, in the order of [PI_LANES].
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// The lanes visited by the pi step, starting from lane 1.
+const PI_LANES: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// The Keccak-f[1600] permutation, with lane `(x, y)` at index `x + 5 y`.
+fn keccak_f(a: &mut [u64; 25]) {
+    for rc in ROUND_CONSTANTS {
+        // Theta.
+        let c: [u64; 5] =
+            core::array::from_fn(|x| a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20]);
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                a[x + 5 * y] ^= d;
+            }
+        }
+
+        // Rho and pi.
+        let mut last = a[1];
+        for (&lane, &rotation) in PI_LANES.iter().zip(&RHO) {
+            let next = a[lane];
+            a[lane] = last.rotate_left(rotation);
+            last = next;
+        }
+
+        // Chi.
+        for y in 0..5 {
+            let row: [u64; 5] = core::array::from_fn(|x| a[x + 5 * y]);
+            for x in 0..5 {
+                a[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // Iota.
+        a[0] ^= rc;
+    }
+}
+
+fn xor_block(state: &mut [u64; 25], block: &[u8; RATE]) {
+    for (lane, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+        *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+}
+
+/// The first `output_len` bytes of SHAKE256 of `input`.
+pub(crate) fn shake256(input: &[u8], output_len: usize) -> Vec<u8> {
+    let mut state = [0u64; 25];
+
+    let mut chunks = input.chunks_exact(RATE);
+    for chunk in chunks.by_ref() {
+        xor_block(&mut state, chunk.try_into().unwrap());
+        keccak_f(&mut state);
+    }
+    // Pad with the SHAKE domain separator, then 0*1.
+    let remainder = chunks.remainder();
+    let mut last = [0u8; RATE];
+    last[..remainder.len()].copy_from_slice(remainder);
+    last[remainder.len()] ^= 0x1f;
+    last[RATE - 1] ^= 0x80;
+    xor_block(&mut state, &last);
+    keccak_f(&mut state);
+
+    let mut output = vec![0u8; output_len];
+    for (i, chunk) in output.chunks_mut(RATE).enumerate() {
+        if i > 0 {
+            keccak_f(&mut state);
+        }
+        let bytes = state.iter().flat_map(|lane| lane.to_le_bytes());
+        for (o, b) in chunk.iter_mut().zip(bytes) {
+            *o = b;
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> alloc::string::String {
+        bytes.iter().map(|b| alloc::format!("{b:02x}")).collect()
+    }
+
+    /// The SHAKE256 of `hashlib`, as printed by `rescue/scripts/rescue_prime_vectors.py`.
+    #[test]
+    fn known_answers() {
+        assert_eq!(
+            hex(&shake256(b"", 40)),
+            "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200"
+        );
+        // Absorbs more than one block and squeezes more than one block.
+        let input: Vec<u8> = (0..200).collect();
+        let output = shake256(&input, 300);
+        assert_eq!(
+            hex(&output[280..]),
+            "01358ae64f3f0ccedfa05b29e84e1a11a635bfe7"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use crate::CryptographicPermutation;
+
+/// A function that compresses `N` chunks of `T` into one, such as the two children of a Merkle
+/// tree node into the node.
+///
+/// This is only required to be collision resistant for inputs of a fixed length, unlike a hash.
+pub trait PseudoCompressionFunction<T, const N: usize>: Clone {
+    fn compress(&self, input: [T; N]) -> T;
+}
+
+/// Compresses `N` chunks of `CHUNK` elements by writing them into the state of a permutation of
+/// `WIDTH >= N * CHUNK` elements, permuting, and keeping the first `CHUNK` elements.
+#[derive(Copy, Clone, Debug)]
+pub struct TruncatedPermutation<P, const N: usize, const CHUNK: usize, const WIDTH: usize> {
+    permutation: P,
+}
+
+impl<P, const N: usize, const CHUNK: usize, const WIDTH: usize>
+    TruncatedPermutation<P, N, CHUNK, WIDTH>
+{
+    pub fn new(permutation: P) -> Self {
+        assert!(N * CHUNK <= WIDTH, "the chunks must fit in the state");
+        Self { permutation }
+    }
+}
+
+impl<T, P, const N: usize, const CHUNK: usize, const WIDTH: usize>
+    PseudoCompressionFunction<[T; CHUNK], N> for TruncatedPermutation<P, N, CHUNK, WIDTH>
+where
+    T: Copy + Default,
+    P: CryptographicPermutation<[T; WIDTH]>,
+{
+    fn compress(&self, input: [[T; CHUNK]; N]) -> [T; CHUNK] {
+        let mut state = [T::default(); WIDTH];
+        for (dst, chunk) in state.chunks_exact_mut(CHUNK).zip(&input) {
+            dst.copy_from_slice(chunk);
+        }
+        self.permutation.permute_mut(&mut state);
+        core::array::from_fn(|i| state[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Permutation;
+
+    /// Not cryptographic, but enough to see where the inputs went.
+    #[derive(Clone)]
+    struct Reverse;
+
+    impl Permutation<[u32; 5]> for Reverse {
+        fn permute_mut(&self, input: &mut [u32; 5]) {
+            input.reverse();
+        }
+    }
+
+    impl CryptographicPermutation<[u32; 5]> for Reverse {}
+
+    #[test]
+    fn truncation() {
+        let compress = TruncatedPermutation::<_, 2, 2, 5>::new(Reverse);
+        // The state is [1, 2, 3, 4, 0] before the permutation.
+        assert_eq!(compress.compress([[1, 2], [3, 4]]), [0, 4]);
+    }
+}
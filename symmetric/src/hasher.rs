@@ -0,0 +1,14 @@
+/// A hash function from a sequence of `Item`s to an `Out`.
+pub trait CryptographicHasher<Item: Clone, Out>: Clone {
+    fn hash_iter<I>(&self, input: I) -> Out
+    where
+        I: IntoIterator<Item = Item>;
+
+    fn hash_slice(&self, input: &[Item]) -> Out {
+        self.hash_iter(input.iter().cloned())
+    }
+
+    fn hash_item(&self, input: Item) -> Out {
+        self.hash_iter([input])
+    }
+}
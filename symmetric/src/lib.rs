@@ -0,0 +1,13 @@
+//! Symmetric cryptographic primitives built from permutations.
+
+#![no_std]
+
+mod compression;
+mod hasher;
+mod permutation;
+mod sponge;
+
+pub use compression::*;
+pub use hasher::*;
+pub use permutation::*;
+pub use sponge::*;
@@ -0,0 +1,16 @@
+/// A permutation of `T`, typically an array of field elements.
+///
+/// Implementations are generally generic over the element type, so that the same permutation can
+/// be applied to scalar, packed or symbolic states.
+pub trait Permutation<T: Clone>: Clone + Sync {
+    fn permute(&self, mut input: T) -> T {
+        self.permute_mut(&mut input);
+        input
+    }
+
+    fn permute_mut(&self, input: &mut T);
+}
+
+/// A permutation that is believed to be cryptographically secure, i.e. indistinguishable from a
+/// random permutation. Marks the permutations that may be used in sponges and compressions.
+pub trait CryptographicPermutation<T: Clone>: Permutation<T> {}
@@ -0,0 +1,84 @@
+use core::iter;
+
+use p3_field::AbstractField;
+
+use crate::{CryptographicHasher, CryptographicPermutation};
+
+/// A sponge hash over a permutation of `WIDTH` elements, absorbing `RATE` elements at a time and
+/// squeezing `OUT <= RATE` elements.
+///
+/// The input is padded with a one followed by zeros up to a multiple of `RATE`, as in the
+/// Rescue-Prime specification, so that inputs of different lengths never collide trivially.
+/// Absorbed elements are added to the rate part of the state, which starts out zero.
+#[derive(Copy, Clone, Debug)]
+pub struct PaddedSponge<P, const WIDTH: usize, const RATE: usize, const OUT: usize> {
+    permutation: P,
+}
+
+impl<P, const WIDTH: usize, const RATE: usize, const OUT: usize> PaddedSponge<P, WIDTH, RATE, OUT> {
+    pub fn new(permutation: P) -> Self {
+        assert!(RATE < WIDTH, "the capacity must not be empty");
+        assert!(OUT <= RATE, "the output can't be larger than the rate");
+        Self { permutation }
+    }
+}
+
+impl<AF, P, const WIDTH: usize, const RATE: usize, const OUT: usize>
+    CryptographicHasher<AF, [AF; OUT]> for PaddedSponge<P, WIDTH, RATE, OUT>
+where
+    AF: AbstractField,
+    P: CryptographicPermutation<[AF; WIDTH]>,
+{
+    fn hash_iter<I>(&self, input: I) -> [AF; OUT]
+    where
+        I: IntoIterator<Item = AF>,
+    {
+        let mut state: [AF; WIDTH] = core::array::from_fn(|_| AF::zero());
+        let mut input = input.into_iter().chain(iter::once(AF::one())).peekable();
+        // The zero padding doesn't change the state, so only the padded length matters.
+        while input.peek().is_some() {
+            for (x, v) in state.iter_mut().zip(input.by_ref().take(RATE)) {
+                *x += v;
+            }
+            self.permutation.permute_mut(&mut state);
+        }
+        core::array::from_fn(|i| state[i].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+
+    use super::*;
+    use crate::Permutation;
+
+    /// Not cryptographic, but enough to see what was absorbed.
+    #[derive(Clone)]
+    struct Rotate;
+
+    impl Permutation<[BabyBear; 4]> for Rotate {
+        fn permute_mut(&self, input: &mut [BabyBear; 4]) {
+            input.rotate_right(1);
+            input[0] += BabyBear::two();
+        }
+    }
+
+    impl CryptographicPermutation<[BabyBear; 4]> for Rotate {}
+
+    #[test]
+    fn padding() {
+        let sponge = PaddedSponge::<_, 4, 2, 2>::new(Rotate);
+        let f = BabyBear::from_canonical_u32;
+        let perm = |s: [u32; 4]| Rotate.permute(s.map(f));
+
+        assert_eq!(sponge.hash_iter::<[BabyBear; 0]>([]), [f(2), f(1)]);
+        assert_eq!(sponge.hash_slice(&[f(5)]), [f(2), f(5)]);
+
+        // A full block is followed by a block of padding.
+        let mut expected = perm([5, 6, 0, 0]);
+        expected[0] += BabyBear::one();
+        expected = Rotate.permute(expected);
+        assert_eq!(sponge.hash_slice(&[f(5), f(6)]), [expected[0], expected[1]]);
+    }
+}
@@ -7,7 +7,7 @@ use core::hint::unreachable_unchecked;
 /// Computes `ceil(a / b)`. Assumes `a + b` does not overflow.
 #[must_use]
 pub const fn ceil_div_usize(a: usize, b: usize) -> usize {
-    (a + b - 1) / b
+    a.div_ceil(b)
 }
 
 /// Computes `ceil(log_2(n))`.
@@ -33,6 +33,48 @@ pub fn log2_strict_usize(n: usize) -> usize {
     res as usize
 }
 
+/// Reverses the low `bit_len` bits of `x`.
+#[must_use]
+#[inline]
+pub const fn reverse_bits_len(x: usize, bit_len: usize) -> usize {
+    // NB: `checked_shr` rather than `>>` so that `bit_len == 0`, a shift by `usize::BITS`, yields 0
+    // instead of overflowing. (`overflowing_shr` would shift by `usize::BITS % usize::BITS = 0`.)
+    match x.reverse_bits().checked_shr(usize::BITS - bit_len as u32) {
+        Some(r) => r,
+        None => 0,
+    }
+}
+
+/// Permutes `vals` in place so that the element at index `i` moves to index `rev(i)`, where `rev`
+/// reverses the low `log2(vals.len())` bits.
+///
+/// # Panics
+/// Panics if the length of `vals` is not a power of two.
+pub fn reverse_slice_index_bits<T>(vals: &mut [T]) {
+    reverse_matrix_index_bits(vals, 1);
+}
+
+/// Like `reverse_slice_index_bits`, but treats `vals` as a row-major matrix of the given `width`
+/// and permutes whole rows.
+///
+/// # Panics
+/// Panics if the number of rows is not a power of two.
+pub fn reverse_matrix_index_bits<T>(vals: &mut [T], width: usize) {
+    if vals.is_empty() {
+        return;
+    }
+    debug_assert_eq!(vals.len() % width, 0);
+    let height = vals.len() / width;
+    let log_height = log2_strict_usize(height);
+    for i in 0..height {
+        let j = reverse_bits_len(i, log_height);
+        if i < j {
+            let (lo, hi) = vals.split_at_mut(j * width);
+            lo[i * width..(i + 1) * width].swap_with_slice(&mut hi[..width]);
+        }
+    }
+}
+
 /// Returns `[0, ..., N - 1]`.
 #[must_use]
 pub const fn indices_arr<const N: usize>() -> [usize; N] {
@@ -85,3 +127,18 @@ pub fn branch_hint() {
         core::arch::asm!("", options(nomem, nostack, preserves_flags));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_bits_len_edge_cases() {
+        assert_eq!(reverse_bits_len(5, 0), 0);
+        assert_eq!(reverse_bits_len(0b1011, 4), 0b1101);
+        assert_eq!(
+            reverse_bits_len(1, usize::BITS as usize),
+            1 << (usize::BITS - 1)
+        );
+    }
+}